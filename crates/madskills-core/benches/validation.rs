@@ -0,0 +1,96 @@
+//! Validation throughput on a generated corpus of skills
+//!
+//! Run with `cargo bench -p madskills-core`. `sequential` validates one skill
+//! after another; `parallel` is what `madskills lint` does.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use madskills_core::discovery::discover_skills;
+use madskills_core::validator::{ValidationConfig, Validator};
+use madskills_core::{DiscoveryConfig, Skill};
+use std::hint::black_box;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Corpus sizes to measure
+const SIZES: [usize; 2] = [50, 400];
+
+/// Write `count` skills, each with a reference file and a script, and
+/// discover them
+fn corpus(count: usize) -> (TempDir, Vec<Skill>) {
+    let dir = TempDir::new().unwrap();
+    let skills_dir = dir.path().join(".github/skills");
+    for i in 0..count {
+        write_skill(&skills_dir.join(format!("processing-data-{}", i)));
+    }
+
+    let config = DiscoveryConfig {
+        root_path: dir.path().to_path_buf(),
+        skills_base_path: skills_dir,
+        include_patterns: vec![],
+        exclude_patterns: vec![],
+    };
+    let skills = discover_skills(&config).unwrap().skills;
+    assert_eq!(skills.len(), count);
+    (dir, skills)
+}
+
+fn write_skill(root: &Path) {
+    std::fs::create_dir_all(root).unwrap();
+    let name = root.file_name().unwrap().to_string_lossy();
+
+    let mut skill_md = format!(
+        "---\nname: {}\ndescription: Processes data files. Use when the user asks for reports.\n---\n\n# Processing data\n\n## Workflow\n\n1. Run `scripts.py`\n2. Read [the reference](reference.md)\n",
+        name
+    );
+    for section in 0..40 {
+        skill_md.push_str(&format!(
+            "\n## Section {}\n\nCustomers upload files in 2024-01-{:02}; errors go to the log.\nSee path\\to\\file for details.\n",
+            section,
+            section % 28 + 1
+        ));
+    }
+    std::fs::write(root.join("SKILL.md"), skill_md).unwrap();
+
+    let reference: String = (0..150)
+        .map(|line| format!("Reference line {}\n", line))
+        .collect();
+    std::fs::write(root.join("reference.md"), reference).unwrap();
+    std::fs::write(
+        root.join("scripts.py"),
+        "TIMEOUT = 30\nMAX_RETRIES = 5\nprint('done')\n",
+    )
+    .unwrap();
+}
+
+fn validation(c: &mut Criterion) {
+    let validator = Validator::new(ValidationConfig {
+        check_markdown: true,
+        ..Default::default()
+    });
+    let mut group = c.benchmark_group("validate");
+    group.sample_size(10);
+
+    for count in SIZES {
+        let (_dir, skills) = corpus(count);
+        group.bench_with_input(
+            BenchmarkId::new("sequential", count),
+            &skills,
+            |b, skills| {
+                b.iter(|| {
+                    skills
+                        .iter()
+                        .map(|skill| validator.validate_skill(black_box(skill)))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("parallel", count), &skills, |b, skills| {
+            b.iter(|| validator.validate_all(black_box(skills)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, validation);
+criterion_main!(benches);
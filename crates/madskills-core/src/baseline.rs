@@ -0,0 +1,487 @@
+//! Baseline files for adopting lint on existing skill repositories
+//!
+//! A baseline records fingerprints of the current findings so later runs
+//! only report new ones. Fingerprints are built from the skill name, the
+//! rule code, the file relative to the skill and the message with digits
+//! normalized, so unrelated edits that shift line numbers do not invalidate
+//! the baseline. Paths are made relative to the skill, or failing that to
+//! the workspace, so a baseline matches on every checkout.
+
+use crate::error::{CoreError, CoreResult};
+use crate::models::{
+    ValidationErrorKind, ValidationResult, ValidationWarningKind, ViolationLocation,
+};
+use crate::rules::RuleRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Current baseline file format version
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Identity of a finding, stable across line number changes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// Skill name (the skill directory name)
+    pub skill: String,
+    /// Rule code (`AS010`) or spec check kind (`missing-required-field`)
+    pub code: String,
+    /// File relative to the skill root (empty when not tied to a file)
+    pub file: String,
+    /// Message with paths stripped and numbers normalized
+    pub message: String,
+}
+
+/// A baselined finding and how many times it occurs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    #[serde(flatten)]
+    pub fingerprint: Fingerprint,
+    /// Number of identical findings
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+/// Set of known findings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Format version
+    pub version: u32,
+    /// Known findings, sorted by fingerprint
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Outcome of filtering results through a baseline
+#[derive(Debug, Clone, Default)]
+pub struct BaselineReport {
+    /// Number of findings hidden by the baseline
+    pub suppressed: usize,
+    /// Baseline entries that no longer occur (with their missing counts)
+    pub fixed: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Build a baseline from every finding in `results`, linted from the
+    /// project at `workspace`
+    pub fn from_results(results: &[ValidationResult], workspace: &Path) -> Self {
+        let mut counts: BTreeMap<Fingerprint, usize> = BTreeMap::new();
+        for result in results {
+            for fingerprint in fingerprints(result, workspace) {
+                *counts.entry(fingerprint).or_default() += 1;
+            }
+        }
+
+        Self {
+            version: BASELINE_VERSION,
+            entries: counts
+                .into_iter()
+                .map(|(fingerprint, count)| BaselineEntry { fingerprint, count })
+                .collect(),
+        }
+    }
+
+    /// Number of findings recorded in the baseline
+    pub fn len(&self) -> usize {
+        self.entries.iter().map(|e| e.count).sum()
+    }
+
+    /// Whether the baseline records no findings
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Load a baseline file
+    pub fn load(path: &Path) -> CoreResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| baseline_error(path, e))?;
+        let baseline: Self = serde_json::from_str(&content).map_err(|e| baseline_error(path, e))?;
+
+        if baseline.version != BASELINE_VERSION {
+            return Err(baseline_error(
+                path,
+                format!(
+                    "unsupported version {} (expected {})",
+                    baseline.version, BASELINE_VERSION
+                ),
+            ));
+        }
+
+        Ok(baseline)
+    }
+
+    /// Reject entries whose code is neither a spec check nor a rule in
+    /// `registry`, so that typos are not silently reported as fixed
+    pub fn check_codes(&self, path: &Path, registry: &RuleRegistry) -> CoreResult<()> {
+        let known = |code: &str| {
+            ValidationErrorKind::ALL.iter().any(|k| k.as_str() == code)
+                || ValidationWarningKind::ALL
+                    .iter()
+                    .any(|k| k.as_str() == code)
+                || registry.code(code).is_some()
+        };
+        match self.entries.iter().find(|e| !known(&e.fingerprint.code)) {
+            Some(entry) => Err(baseline_error(
+                path,
+                format!("unknown rule code '{}'", entry.fingerprint.code),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Write the baseline as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> CoreResult<()> {
+        let mut json = serde_json::to_string_pretty(self).map_err(|e| baseline_error(path, e))?;
+        json.push('\n');
+        std::fs::write(path, json).map_err(|e| baseline_error(path, e))
+    }
+
+    /// Remove baselined findings from `results`
+    pub fn apply(&self, results: &mut [ValidationResult], workspace: &Path) -> BaselineReport {
+        let mut remaining: BTreeMap<Fingerprint, usize> = self
+            .entries
+            .iter()
+            .map(|e| (e.fingerprint.clone(), e.count))
+            .collect();
+        let mut suppressed = 0;
+
+        let mut known = |fingerprint: Fingerprint| match remaining.get_mut(&fingerprint) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                suppressed += 1;
+                true
+            }
+            _ => false,
+        };
+
+        for result in results.iter_mut() {
+            let skill = skill_name(&result.skill_path);
+            let root = result.skill_path.clone();
+            let roots = Roots {
+                skill: &root,
+                workspace,
+            };
+
+            result.errors.retain(|e| {
+                !known(fingerprint(
+                    &skill,
+                    &roots,
+                    e.kind.as_str(),
+                    e.location.as_ref().map(|l| l.file.as_path()),
+                    &e.message,
+                ))
+            });
+            result.warnings.retain(|w| {
+                !known(fingerprint(
+                    &skill,
+                    &roots,
+                    w.kind.as_str(),
+                    w.location.as_ref().map(|l| l.file.as_path()),
+                    &w.message,
+                ))
+            });
+            result.best_practice_violations.retain(|v| {
+                !known(fingerprint(
+                    &skill,
+                    &roots,
+                    v.code.as_str(),
+                    violation_file(&v.location),
+                    &v.message,
+                ))
+            });
+        }
+
+        BaselineReport {
+            suppressed,
+            fixed: remaining
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(fingerprint, count)| BaselineEntry { fingerprint, count })
+                .collect(),
+        }
+    }
+}
+
+/// Directories that paths in a finding are made relative to
+struct Roots<'a> {
+    skill: &'a Path,
+    workspace: &'a Path,
+}
+
+impl Roots<'_> {
+    /// `path` relative to the skill, else to the workspace
+    fn relative<'p>(&self, path: &'p Path) -> &'p Path {
+        path.strip_prefix(self.skill)
+            .or_else(|_| path.strip_prefix(self.workspace))
+            .unwrap_or(path)
+    }
+}
+
+/// Fingerprints of every finding in a result
+fn fingerprints(result: &ValidationResult, workspace: &Path) -> Vec<Fingerprint> {
+    let skill = skill_name(&result.skill_path);
+    let roots = Roots {
+        skill: &result.skill_path,
+        workspace,
+    };
+
+    let errors = result.errors.iter().map(|e| {
+        fingerprint(
+            &skill,
+            &roots,
+            e.kind.as_str(),
+            e.location.as_ref().map(|l| l.file.as_path()),
+            &e.message,
+        )
+    });
+    let warnings = result.warnings.iter().map(|w| {
+        fingerprint(
+            &skill,
+            &roots,
+            w.kind.as_str(),
+            w.location.as_ref().map(|l| l.file.as_path()),
+            &w.message,
+        )
+    });
+    let violations = result.best_practice_violations.iter().map(|v| {
+        fingerprint(
+            &skill,
+            &roots,
+            v.code.as_str(),
+            violation_file(&v.location),
+            &v.message,
+        )
+    });
+
+    errors.chain(warnings).chain(violations).collect()
+}
+
+fn fingerprint(
+    skill: &str,
+    roots: &Roots,
+    code: &str,
+    file: Option<&Path>,
+    message: &str,
+) -> Fingerprint {
+    let file = file
+        .map(|f| roots.relative(f).to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+
+    Fingerprint {
+        skill: skill.to_string(),
+        code: code.to_string(),
+        file,
+        message: normalize_message(message, roots),
+    }
+}
+
+/// Strip the skill and workspace roots from a message and collapse digit
+/// runs to `N`
+///
+/// Workspace-level findings such as duplicate skill names quote the paths
+/// of other skills, which only the workspace root makes portable.
+fn normalize_message(message: &str, roots: &Roots) -> String {
+    let mut message = strip_root(message, roots.skill);
+    if roots.workspace != Path::new(".") {
+        message = strip_root(&message, roots.workspace);
+    }
+
+    let mut normalized = String::with_capacity(message.len());
+    let mut in_digits = false;
+    for c in message.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                normalized.push('N');
+            }
+            in_digits = true;
+        } else {
+            normalized.push(c);
+            in_digits = false;
+        }
+    }
+    normalized
+}
+
+fn strip_root(message: &str, root: &Path) -> String {
+    let root = root.display().to_string();
+    if root.is_empty() {
+        message.to_string()
+    } else {
+        message
+            .replace(&format!("{}/", root), "")
+            .replace(&root, "")
+    }
+}
+
+fn skill_name(skill_path: &Path) -> String {
+    skill_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| skill_path.display().to_string())
+}
+
+fn violation_file(location: &Option<ViolationLocation>) -> Option<&Path> {
+    match location {
+        Some(ViolationLocation::File { path, .. })
+        | Some(ViolationLocation::Script { path, .. }) => Some(path.as_path()),
+        _ => None,
+    }
+}
+
+fn baseline_error(path: &Path, message: impl ToString) -> CoreError {
+    CoreError::Baseline {
+        path: PathBuf::from(path),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BestPracticeCode, BestPracticeViolation, Severity, ValidationError, ValidationErrorKind,
+    };
+    use tempfile::TempDir;
+
+    fn result_with(root: &str, lines: &[usize]) -> ValidationResult {
+        let root = PathBuf::from(root);
+        let mut result = ValidationResult::new(root.clone());
+        for line in lines {
+            result.best_practice_violations.push(BestPracticeViolation {
+                code: BestPracticeCode::AS018,
+                severity: Severity::Warning,
+                message: format!("Undocumented constant in run.py line {}", line),
+                location: Some(ViolationLocation::Script {
+                    path: root.join("run.py"),
+                    line: Some(*line),
+                }),
+                fix: None,
+            });
+        }
+        result
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_numbers() {
+        let before = Baseline::from_results(&[result_with("skills/demo", &[3])], Path::new("."));
+        let after = Baseline::from_results(&[result_with("skills/demo", &[7])], Path::new("."));
+
+        assert_eq!(before, after);
+        let entry = &before.entries[0].fingerprint;
+        assert_eq!(entry.skill, "demo");
+        assert_eq!(entry.code, "AS018");
+        assert_eq!(entry.file, "run.py");
+        assert_eq!(entry.message, "Undocumented constant in run.py line N");
+    }
+
+    #[test]
+    fn test_normalize_message_strips_skill_root() {
+        let roots = Roots {
+            skill: Path::new("/tmp/skills/demo"),
+            workspace: Path::new("."),
+        };
+        assert_eq!(
+            normalize_message("Missing file /tmp/skills/demo/reference.md", &roots),
+            "Missing file reference.md"
+        );
+    }
+
+    #[test]
+    fn test_apply_reports_only_new_findings() {
+        let baseline =
+            Baseline::from_results(&[result_with("skills/demo", &[3, 9])], Path::new("."));
+        assert_eq!(baseline.entries[0].count, 2);
+        assert_eq!(baseline.len(), 2);
+
+        let mut results = vec![result_with("skills/demo", &[4, 10, 20])];
+        let report = baseline.apply(&mut results, Path::new("."));
+
+        assert_eq!(report.suppressed, 2);
+        assert!(report.fixed.is_empty());
+        assert_eq!(results[0].best_practice_violations.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_reports_fixed_entries() {
+        let mut old = result_with("skills/demo", &[3]);
+        old.errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidFieldValue,
+            message: "Description is too long".into(),
+            location: None,
+        });
+        let baseline = Baseline::from_results(&[old], Path::new("."));
+
+        let mut results = vec![result_with("skills/demo", &[3])];
+        let report = baseline.apply(&mut results, Path::new("."));
+
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!(report.fixed[0].fingerprint.code, "invalid-field-value");
+        assert!(results[0].best_practice_violations.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".madskills-baseline.json");
+        let baseline = Baseline::from_results(&[result_with("skills/demo", &[3])], Path::new("."));
+
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("baseline.json");
+        std::fs::write(&path, r#"{"version": 99, "entries": []}"#).unwrap();
+
+        let err = Baseline::load(&path).unwrap_err();
+        assert!(err.to_string().contains("unsupported version 99"));
+    }
+
+    #[test]
+    fn test_check_codes_rejects_unknown_codes() {
+        let path = Path::new("baseline.json");
+        let registry = RuleRegistry::builtin();
+        let mut baseline =
+            Baseline::from_results(&[result_with("skills/demo", &[3])], Path::new("."));
+        assert!(baseline.check_codes(path, &registry).is_ok());
+
+        baseline.entries[0].fingerprint.code = "AS0O5".into();
+        let err = baseline.check_codes(path, &registry).unwrap_err();
+        assert!(err.to_string().contains("unknown rule code 'AS0O5'"));
+    }
+
+    #[test]
+    fn test_workspace_paths_are_portable() {
+        let duplicate = |workspace: &str| {
+            let mut result = ValidationResult::new(PathBuf::from("<workspace>"));
+            result.errors.push(ValidationError {
+                kind: ValidationErrorKind::DuplicateSkillName,
+                message: format!(
+                    "Skill name 'review' is duplicated (first: {0}/.github/skills/review, duplicate: {0}/.claude/skills/review)",
+                    workspace
+                ),
+                location: Some(crate::models::SourceLocation {
+                    file: PathBuf::from(format!("{}/.claude/skills/review/SKILL.md", workspace)),
+                    line: 2,
+                    column: 7,
+                    end_line: None,
+                    end_column: None,
+                }),
+            });
+            Baseline::from_results(&[result], Path::new(workspace))
+        };
+
+        let local = duplicate("/home/dev/project");
+        assert_eq!(local, duplicate("/builds/ci/project"));
+        let entry = &local.entries[0].fingerprint;
+        assert_eq!(entry.file, ".claude/skills/review/SKILL.md");
+        assert_eq!(
+            entry.message,
+            "Skill name 'review' is duplicated (first: .github/skills/review, duplicate: .claude/skills/review)"
+        );
+    }
+}
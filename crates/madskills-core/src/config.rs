@@ -0,0 +1,875 @@
+//! Project configuration (madskills.toml)
+//!
+//! Settings are read from the first of `madskills.toml`, `.madskills.toml` or a
+//! `pyproject.toml` containing a `[tool.madskills]` table, found by walking up
+//! from the scanned path. Command-line flags take precedence over file values,
+//! which take precedence over built-in defaults.
+
+use crate::discovery::{PathFilter, detect_skills_directory, detect_skills_roots};
+use crate::error::{CoreError, CoreResult};
+use crate::format::FormatConfig;
+use crate::markdown::{MarkdownConfig, MarkdownFlavor, parse_markdown_rule};
+use crate::models::{BestPracticeCode, DiscoveryConfig, RuleLevel, SkillsRoot};
+use crate::rules::{DeclarativeRule, RuleDefinition, RuleRegistry};
+use crate::validator::{RuleSettings, ValidationConfig, parse_rule_selector};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Config file names, in lookup order within a single directory
+pub const CONFIG_FILE_NAMES: &[&str] = &["madskills.toml", ".madskills.toml", "pyproject.toml"];
+
+/// Settings as written in a config file (or collected from CLI flags)
+///
+/// Every value is optional so that layers can be merged; unset values fall
+/// through to the next layer.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// `[lint]` table
+    pub lint: LintSection,
+    /// `[fmt]` table
+    pub fmt: FmtSection,
+    /// `[discovery]` table
+    pub discovery: DiscoverySection,
+    /// `[markdown]` table
+    pub markdown: MarkdownSection,
+    /// `[rules]` table: rule code to level (`off`, `info`, `warning`, `error`)
+    ///
+    /// Codes are checked against the rule registry in use when the rule
+    /// settings are built, so they may name rules registered by other crates.
+    pub rules: BTreeMap<String, RuleLevel>,
+    /// `[[custom-rules]]` array: rules declared in the config file
+    #[serde(rename = "custom-rules")]
+    pub custom_rules: Vec<RuleDefinition>,
+}
+
+/// `[lint]` settings
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LintSection {
+    /// Treat warnings as errors
+    pub strict: Option<bool>,
+    /// Enable spec validation
+    pub spec: Option<bool>,
+    /// Enable markdown linting
+    pub mdlint: Option<bool>,
+    /// Enable best practices validation
+    pub best_practices: Option<bool>,
+    /// Markdown lint config file
+    pub mdlint_config: Option<PathBuf>,
+    /// Best practice rules to enable (codes or prefixes, checked like `[rules]`)
+    pub select: Option<Vec<String>>,
+    /// Best practice rules to disable (codes or prefixes, checked like `[rules]`)
+    pub ignore: Option<Vec<String>>,
+}
+
+/// `[fmt]` settings
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FmtSection {
+    /// Apply rumdl-based markdown fixes
+    pub mdlint: Option<bool>,
+    /// Rewrite YAML frontmatter
+    pub frontmatter: Option<bool>,
+    /// Markdown lint config file
+    pub mdlint_config: Option<PathBuf>,
+}
+
+/// `[discovery]` settings
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DiscoverySection {
+    /// Additional SKILL.md globs to include
+    pub include: Option<Vec<String>>,
+    /// Path globs to exclude
+    pub exclude: Option<Vec<String>>,
+    /// Discover skills in every detected skills directory, not just the first
+    pub all_roots: Option<bool>,
+}
+
+/// `[markdown]` settings, layered over the markdown lint config file
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MarkdownSection {
+    /// Only run these markdown rules (codes or names)
+    pub enable: Option<Vec<String>>,
+    /// Never run these markdown rules (codes or names)
+    pub disable: Option<Vec<String>>,
+    /// Markdown flavor (`standard`, `mkdocs`, `mdx`, `quarto`)
+    pub flavor: Option<MarkdownFlavor>,
+}
+
+/// A config file that was found and parsed
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    /// Path of the file the settings came from
+    pub path: PathBuf,
+    /// Parsed settings
+    pub config: Config,
+}
+
+/// Find the nearest config file, walking up from `start`
+pub fn find_config_file(start: &Path) -> CoreResult<Option<PathBuf>> {
+    let start = std::path::absolute(start)?;
+
+    for dir in start.ancestors() {
+        for name in CONFIG_FILE_NAMES {
+            let candidate = dir.join(name);
+            if !candidate.is_file() {
+                continue;
+            }
+            // pyproject.toml only counts when it has a [tool.madskills] table
+            if *name == "pyproject.toml" && !has_tool_table(&candidate)? {
+                continue;
+            }
+            return Ok(Some(candidate));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Find and load the nearest config file, walking up from `start`
+pub fn discover_config(start: &Path) -> CoreResult<Option<LoadedConfig>> {
+    match find_config_file(start)? {
+        Some(path) => {
+            let config = load_config(&path)?;
+            Ok(Some(LoadedConfig { path, config }))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Load settings from a config file
+///
+/// Relative paths inside the file are resolved against the file's directory.
+pub fn load_config(path: &Path) -> CoreResult<Config> {
+    let content = std::fs::read_to_string(path)?;
+    let table: toml::Table = toml::from_str(&content).map_err(|e| config_error(path, e))?;
+
+    let table = if path.file_name().and_then(|s| s.to_str()) == Some("pyproject.toml") {
+        match tool_table(&table) {
+            Some(t) => t.clone(),
+            None => toml::Table::new(),
+        }
+    } else {
+        table
+    };
+
+    let mut config: Config = table.try_into().map_err(|e| config_error(path, e))?;
+    validate_custom_rules(&config)
+        .and_then(|()| validate_markdown_rules(&config))
+        .map_err(|message| CoreError::Config {
+            path: path.to_path_buf(),
+            message,
+        })?;
+
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+    config.lint.mdlint_config = config.lint.mdlint_config.map(|p| base.join(p));
+    config.fmt.mdlint_config = config.fmt.mdlint_config.map(|p| base.join(p));
+
+    Ok(config)
+}
+
+/// Reject invalid or duplicate `[[custom-rules]]`
+fn validate_custom_rules(config: &Config) -> Result<(), String> {
+    let mut registry = RuleRegistry::builtin();
+    for definition in &config.custom_rules {
+        let rule = DeclarativeRule::new(definition.clone())?;
+        if registry.code(&definition.id).is_some() {
+            return Err(format!("custom rule {} is defined twice", definition.id));
+        }
+        registry.register(rule).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Reject markdown rules in `[markdown]` that rumdl does not know
+fn validate_markdown_rules(config: &Config) -> Result<(), String> {
+    let markdown_rules = config
+        .markdown
+        .enable
+        .iter()
+        .chain(&config.markdown.disable);
+    for rule in markdown_rules.flatten() {
+        parse_markdown_rule(rule)?;
+    }
+    Ok(())
+}
+
+fn has_tool_table(path: &Path) -> CoreResult<bool> {
+    let content = std::fs::read_to_string(path)?;
+    let table: toml::Table = toml::from_str(&content).map_err(|e| config_error(path, e))?;
+    Ok(tool_table(&table).is_some())
+}
+
+fn tool_table(table: &toml::Table) -> Option<&toml::Table> {
+    table.get("tool")?.as_table()?.get("madskills")?.as_table()
+}
+
+fn config_error(path: &Path, err: toml::de::Error) -> CoreError {
+    CoreError::Config {
+        path: path.to_path_buf(),
+        message: err.message().to_string(),
+    }
+}
+
+/// Where an effective setting came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in default
+    Default,
+    /// Config file
+    File(PathBuf),
+    /// Command-line flag
+    CommandLine,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::CommandLine => f.write_str("command line"),
+        }
+    }
+}
+
+/// An effective setting together with its source
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+/// Effective `[lint]` settings
+#[derive(Debug, Clone)]
+pub struct ResolvedLint {
+    pub strict: Sourced<bool>,
+    pub spec: Sourced<bool>,
+    pub mdlint: Sourced<bool>,
+    pub best_practices: Sourced<bool>,
+    pub mdlint_config: Sourced<Option<PathBuf>>,
+    pub select: Sourced<Vec<String>>,
+    pub ignore: Sourced<Vec<String>>,
+}
+
+/// Effective `[fmt]` settings
+#[derive(Debug, Clone)]
+pub struct ResolvedFmt {
+    pub mdlint: Sourced<bool>,
+    pub frontmatter: Sourced<bool>,
+    pub mdlint_config: Sourced<Option<PathBuf>>,
+}
+
+/// Effective `[discovery]` settings
+#[derive(Debug, Clone)]
+pub struct ResolvedDiscovery {
+    pub include: Sourced<Vec<String>>,
+    pub exclude: Sourced<Vec<String>>,
+    pub all_roots: Sourced<bool>,
+}
+
+/// Effective `[markdown]` settings
+#[derive(Debug, Clone)]
+pub struct ResolvedMarkdown {
+    pub enable: Sourced<Vec<String>>,
+    pub disable: Sourced<Vec<String>>,
+    pub flavor: Sourced<Option<MarkdownFlavor>>,
+}
+
+/// Effective configuration after merging defaults, config file and CLI flags
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    /// Config file that contributed settings, if any
+    pub file: Option<PathBuf>,
+    pub lint: ResolvedLint,
+    pub fmt: ResolvedFmt,
+    pub discovery: ResolvedDiscovery,
+    pub markdown: ResolvedMarkdown,
+    /// Per-rule levels keyed by rule code as written, in code order
+    ///
+    /// Codes are resolved against a registry by [`Self::rule_settings`].
+    pub rules: Vec<(String, Sourced<RuleLevel>)>,
+    /// Rules declared in the config file
+    pub custom_rules: Vec<RuleDefinition>,
+}
+
+impl ResolvedConfig {
+    /// Merge CLI overrides over an optional config file over defaults
+    pub fn resolve(file: Option<&LoadedConfig>, cli: &Config) -> Self {
+        let empty = Config::default();
+        let (file_config, file_path) = match file {
+            Some(loaded) => (&loaded.config, Some(loaded.path.as_path())),
+            None => (&empty, None),
+        };
+        let layer = Layer {
+            file_path,
+            file: file_config,
+            cli,
+        };
+
+        Self {
+            file: file_path.map(Path::to_path_buf),
+            lint: ResolvedLint {
+                strict: layer.pick(|c| c.lint.strict, false),
+                spec: layer.pick(|c| c.lint.spec, true),
+                mdlint: layer.pick(|c| c.lint.mdlint, true),
+                best_practices: layer.pick(|c| c.lint.best_practices, true),
+                mdlint_config: layer.pick_opt(|c| c.lint.mdlint_config.clone()),
+                select: layer.pick(|c| c.lint.select.clone(), Vec::new()),
+                ignore: layer.pick(|c| c.lint.ignore.clone(), Vec::new()),
+            },
+            fmt: ResolvedFmt {
+                mdlint: layer.pick(|c| c.fmt.mdlint, true),
+                frontmatter: layer.pick(|c| c.fmt.frontmatter, true),
+                mdlint_config: layer.pick_opt(|c| c.fmt.mdlint_config.clone()),
+            },
+            discovery: ResolvedDiscovery {
+                include: layer.pick(|c| c.discovery.include.clone(), Vec::new()),
+                exclude: layer.pick(|c| c.discovery.exclude.clone(), Vec::new()),
+                all_roots: layer.pick(|c| c.discovery.all_roots, false),
+            },
+            markdown: ResolvedMarkdown {
+                enable: layer.pick(|c| c.markdown.enable.clone(), Vec::new()),
+                disable: layer.pick(|c| c.markdown.disable.clone(), Vec::new()),
+                flavor: layer.pick_opt(|c| c.markdown.flavor),
+            },
+            rules: rule_codes(&[cli, file_config])
+                .into_iter()
+                .filter_map(|code| {
+                    let level = layer.pick_opt(|c| rule_level(c, &code));
+                    level.value.map(|value| {
+                        let source = level.source;
+                        (code, Sourced { value, source })
+                    })
+                })
+                .collect(),
+            custom_rules: file_config.custom_rules.clone(),
+        }
+    }
+
+    /// The built-in rules followed by the config file's custom rules
+    pub fn rule_registry(&self) -> CoreResult<RuleRegistry> {
+        let mut registry = RuleRegistry::builtin();
+        for definition in &self.custom_rules {
+            let rule =
+                DeclarativeRule::new(definition.clone()).map_err(|message| CoreError::Config {
+                    path: self.file.clone().unwrap_or_default(),
+                    message,
+                })?;
+            registry.register(rule)?;
+        }
+        Ok(registry)
+    }
+
+    /// Build the validator configuration from the effective lint settings,
+    /// for the project at `root` and the rules in `registry`
+    pub fn validation_config(
+        &self,
+        root: &Path,
+        registry: &RuleRegistry,
+    ) -> CoreResult<ValidationConfig> {
+        Ok(ValidationConfig {
+            strict: self.lint.strict.value,
+            check_spec: self.lint.spec.value,
+            check_markdown: self.lint.mdlint.value,
+            check_best_practices: self.lint.best_practices.value,
+            markdown_config: self.markdown_config(self.lint.mdlint_config.value.clone()),
+            rules: self.rule_settings(registry)?,
+            exclude: self.exclude_filter(root)?,
+        })
+    }
+
+    /// Rule selection and levels from the effective lint settings
+    ///
+    /// Fails when `[rules]` or a selector names a code `registry` does not know.
+    pub fn rule_settings(&self, registry: &RuleRegistry) -> CoreResult<RuleSettings> {
+        let levels = self
+            .rules
+            .iter()
+            .map(|(code, level)| {
+                let code = registry
+                    .parse_code(code)
+                    .map_err(|message| setting_error(&level.source, message))?;
+                Ok((code, level.value))
+            })
+            .collect::<CoreResult<_>>()?;
+        for selectors in [&self.lint.select, &self.lint.ignore] {
+            for selector in &selectors.value {
+                parse_rule_selector(selector, registry)
+                    .map_err(|message| setting_error(&selectors.source, message))?;
+            }
+        }
+
+        Ok(RuleSettings {
+            select: self.lint.select.value.clone(),
+            ignore: self.lint.ignore.value.clone(),
+            levels,
+        })
+    }
+
+    /// Build the formatter configuration from the effective fmt settings,
+    /// for the project at `root`
+    pub fn format_config(&self, root: &Path) -> CoreResult<FormatConfig> {
+        Ok(FormatConfig {
+            frontmatter: self.fmt.frontmatter.value,
+            markdown: self.fmt.mdlint.value,
+            markdown_config: self.markdown_config(self.fmt.mdlint_config.value.clone()),
+            toc: false,
+            exclude: self.exclude_filter(root)?,
+        })
+    }
+
+    /// Files inside skills to leave alone, matched below `root`
+    fn exclude_filter(&self, root: &Path) -> CoreResult<PathFilter> {
+        PathFilter::new(root, &self.discovery.exclude.value)
+    }
+
+    /// Markdown lint settings for the given config file
+    fn markdown_config(&self, config_file: Option<PathBuf>) -> MarkdownConfig {
+        MarkdownConfig {
+            config_file,
+            enable: self.markdown.enable.value.clone(),
+            disable: self.markdown.disable.value.clone(),
+            flavor: self.markdown.flavor.value,
+        }
+    }
+
+    /// Skills directories to discover in: every detected one with `all-roots`,
+    /// otherwise the first
+    pub fn skills_roots(&self, project_root: &Path) -> CoreResult<Vec<SkillsRoot>> {
+        if self.discovery.all_roots.value {
+            Ok(detect_skills_roots(project_root))
+        } else {
+            let path = detect_skills_directory(project_root)?;
+            Ok(vec![SkillsRoot::new(path)])
+        }
+    }
+
+    /// Build the discovery configuration from the effective discovery settings
+    pub fn discovery_config(
+        &self,
+        root_path: PathBuf,
+        skills_base_path: PathBuf,
+    ) -> DiscoveryConfig {
+        DiscoveryConfig {
+            root_path,
+            skills_base_path,
+            include_patterns: self.discovery.include.value.clone(),
+            exclude_patterns: self.discovery.exclude.value.clone(),
+        }
+    }
+}
+
+/// Codes set in the `[rules]` tables of `configs`, each once (keys are
+/// case-insensitive), built-in codes first
+fn rule_codes(configs: &[&Config]) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for code in configs.iter().flat_map(|c| c.rules.keys()) {
+        if !codes.iter().any(|seen| seen.eq_ignore_ascii_case(code)) {
+            codes.push(code.clone());
+        }
+    }
+    codes.sort_by_cached_key(|code| {
+        let builtin = code.parse::<BestPracticeCode>().ok();
+        (builtin.is_none(), code.to_ascii_uppercase())
+    });
+    codes
+}
+
+/// Level configured for `code` in a `[rules]` table (keys are case-insensitive)
+fn rule_level(config: &Config, code: &str) -> Option<RuleLevel> {
+    config
+        .rules
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(code))
+        .map(|(_, level)| *level)
+}
+
+/// Error for a setting that names an unknown rule, blaming the config file
+/// when it came from one
+fn setting_error(source: &ConfigSource, message: String) -> CoreError {
+    match source {
+        ConfigSource::File(path) => CoreError::Config {
+            path: path.clone(),
+            message,
+        },
+        _ => CoreError::InvalidRule(message),
+    }
+}
+
+/// Lookup helper for a single merge
+struct Layer<'a> {
+    file_path: Option<&'a Path>,
+    file: &'a Config,
+    cli: &'a Config,
+}
+
+impl Layer<'_> {
+    fn pick<T>(&self, get: impl Fn(&Config) -> Option<T>, default: T) -> Sourced<T> {
+        if let Some(value) = get(self.cli) {
+            return Sourced {
+                value,
+                source: ConfigSource::CommandLine,
+            };
+        }
+        if let (Some(value), Some(path)) = (get(self.file), self.file_path) {
+            return Sourced {
+                value,
+                source: ConfigSource::File(path.to_path_buf()),
+            };
+        }
+        Sourced {
+            value: default,
+            source: ConfigSource::Default,
+        }
+    }
+
+    fn pick_opt<T>(&self, get: impl Fn(&Config) -> Option<T>) -> Sourced<Option<T>> {
+        self.pick(|c| get(c).map(Some), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+    use crate::rules::{Diagnostic, Rule};
+    use crate::validator::SkillContext;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_config_walks_up() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("a/b/c");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            temp.path().join("madskills.toml"),
+            "[lint]\nstrict = true\n",
+        )
+        .unwrap();
+
+        let found = find_config_file(&nested).unwrap().unwrap();
+        assert_eq!(found, temp.path().join("madskills.toml"));
+    }
+
+    #[test]
+    fn test_find_config_prefers_madskills_toml() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("madskills.toml"), "").unwrap();
+        fs::write(temp.path().join(".madskills.toml"), "").unwrap();
+
+        let found = find_config_file(temp.path()).unwrap().unwrap();
+        assert_eq!(found, temp.path().join("madskills.toml"));
+    }
+
+    #[test]
+    fn test_pyproject_without_tool_table_is_skipped() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("pkg");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+        fs::write(temp.path().join(".madskills.toml"), "").unwrap();
+
+        let found = find_config_file(&nested).unwrap().unwrap();
+        assert_eq!(found, temp.path().join(".madskills.toml"));
+    }
+
+    #[test]
+    fn test_load_pyproject_tool_table() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("pyproject.toml");
+        fs::write(
+            &path,
+            "[project]\nname = \"x\"\n\n[tool.madskills.lint]\nmdlint = false\n\n[tool.madskills.discovery]\nexclude = [\"vendor\"]\n",
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.lint.mdlint, Some(false));
+        assert_eq!(config.discovery.exclude, Some(vec!["vendor".to_string()]));
+    }
+
+    #[test]
+    fn test_load_resolves_relative_paths() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(&path, "[lint]\nmdlint-config = \".rumdl.toml\"\n").unwrap();
+
+        let config = load_config(&path).unwrap();
+        assert_eq!(
+            config.lint.mdlint_config,
+            Some(temp.path().join(".rumdl.toml"))
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_keys() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(&path, "[lint]\nstrcit = true\n").unwrap();
+
+        let err = load_config(&path).unwrap_err();
+        assert!(matches!(err, CoreError::Config { .. }));
+        assert!(err.to_string().contains("strcit"));
+    }
+
+    #[test]
+    fn test_load_custom_rules() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(
+            &path,
+            "[rules]\nCFG001 = \"error\"\n\n[[custom-rules]]\nid = \"CFG001\"\nmessage = \"Mention Acme\"\ntarget = \"frontmatter\"\nfield = \"description\"\nrequired-pattern = \"Acme\"\n",
+        )
+        .unwrap();
+
+        let loaded = LoadedConfig {
+            config: load_config(&path).unwrap(),
+            path,
+        };
+        let resolved = ResolvedConfig::resolve(Some(&loaded), &Config::default());
+        let registry = resolved.rule_registry().unwrap();
+        let code = registry.parse_code("CFG001").unwrap();
+
+        assert_eq!(
+            resolved.rule_settings(&registry).unwrap().levels.get(&code),
+            Some(&RuleLevel::Error)
+        );
+        assert_eq!(registry.get(&code).unwrap().description(), "Mention Acme");
+        assert_eq!(code.description(), "Mention Acme");
+        assert!("CFG001".parse::<BestPracticeCode>().is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_duplicate_custom_rules() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        let rule = "[[custom-rules]]\nid = \"CFG002\"\nmessage = \"m\"\ntarget = \"body\"\nforbidden-pattern = \"x\"\n\n";
+        fs::write(&path, rule.repeat(2)).unwrap();
+
+        let err = load_config(&path).unwrap_err().to_string();
+        assert!(err.contains("defined twice"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_rules_table() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(
+            &path,
+            "[lint]\nignore = [\"AS015\"]\n\n[rules]\nAS004 = \"error\"\nAS012 = \"info\"\n",
+        )
+        .unwrap();
+
+        let loaded = LoadedConfig {
+            config: load_config(&path).unwrap(),
+            path: path.clone(),
+        };
+        let resolved = ResolvedConfig::resolve(Some(&loaded), &Config::default());
+        let rules = resolved.rule_settings(&RuleRegistry::builtin()).unwrap();
+
+        assert_eq!(rules.ignore, vec!["AS015".to_string()]);
+        assert_eq!(
+            rules.levels.get(&BestPracticeCode::AS004),
+            Some(&RuleLevel::Error)
+        );
+        assert_eq!(
+            rules.levels.get(&BestPracticeCode::AS012),
+            Some(&RuleLevel::Info)
+        );
+        assert_eq!(resolved.rules[0].1.source, ConfigSource::File(path));
+    }
+
+    #[test]
+    fn test_rule_settings_reject_unknown_rule_codes() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        let rule_settings = |content: &str| {
+            fs::write(&path, content).unwrap();
+            let loaded = LoadedConfig {
+                config: load_config(&path).unwrap(),
+                path: path.clone(),
+            };
+            ResolvedConfig::resolve(Some(&loaded), &Config::default())
+                .rule_settings(&RuleRegistry::builtin())
+        };
+
+        let err = rule_settings("[rules]\nAS099 = \"off\"\n").unwrap_err();
+        assert!(matches!(err, CoreError::Config { .. }));
+        assert!(err.to_string().contains("AS099"));
+
+        let err = rule_settings("[lint]\nselect = [\"XY\"]\n").unwrap_err();
+        assert!(err.to_string().contains("XY"));
+
+        let cli = Config {
+            lint: LintSection {
+                ignore: Some(vec!["XY".to_string()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = ResolvedConfig::resolve(None, &cli)
+            .rule_settings(&RuleRegistry::builtin())
+            .unwrap_err();
+        assert!(matches!(err, CoreError::InvalidRule(_)));
+
+        fs::write(&path, "[rules]\nAS001 = \"loud\"\n").unwrap();
+        assert!(load_config(&path).is_err());
+    }
+
+    #[test]
+    fn test_rule_settings_configure_registered_rules() {
+        struct NoTodo;
+
+        impl Rule for NoTodo {
+            fn id(&self) -> &str {
+                "ACME001"
+            }
+
+            fn description(&self) -> &str {
+                "No TODO markers"
+            }
+
+            fn check(&self, _ctx: &SkillContext) -> Vec<Diagnostic> {
+                Vec::new()
+            }
+        }
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(
+            &path,
+            "[lint]\nselect = [\"ACME\"]\n\n[rules]\nacme001 = \"error\"\n",
+        )
+        .unwrap();
+
+        let loaded = LoadedConfig {
+            config: load_config(&path).unwrap(),
+            path,
+        };
+        let resolved = ResolvedConfig::resolve(Some(&loaded), &Config::default());
+        let mut registry = resolved.rule_registry().unwrap();
+
+        // ACME001 is unknown until the downstream crate registers it
+        assert!(resolved.rule_settings(&registry).is_err());
+
+        let code = registry.register(NoTodo).unwrap();
+        let rules = resolved.rule_settings(&registry).unwrap();
+        assert_eq!(rules.levels.get(&code), Some(&RuleLevel::Error));
+        assert_eq!(rules.severity(&code, false), Some(Severity::Error));
+        assert!(!rules.is_selected(&BestPracticeCode::AS001));
+    }
+
+    #[test]
+    fn test_resolve_precedence_and_sources() {
+        let path = PathBuf::from("/project/madskills.toml");
+        let file = LoadedConfig {
+            path: path.clone(),
+            config: Config {
+                lint: LintSection {
+                    strict: Some(true),
+                    mdlint: Some(false),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        };
+        let cli = Config {
+            lint: LintSection {
+                mdlint: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let resolved = ResolvedConfig::resolve(Some(&file), &cli);
+        assert!(resolved.lint.strict.value);
+        assert_eq!(resolved.lint.strict.source, ConfigSource::File(path));
+        assert!(resolved.lint.mdlint.value);
+        assert_eq!(resolved.lint.mdlint.source, ConfigSource::CommandLine);
+        assert!(resolved.lint.spec.value);
+        assert_eq!(resolved.lint.spec.source, ConfigSource::Default);
+    }
+
+    #[test]
+    fn test_resolve_builds_validation_config() {
+        let cli = Config {
+            lint: LintSection {
+                strict: Some(true),
+                best_practices: Some(false),
+                ..Default::default()
+            },
+            discovery: DiscoverySection {
+                exclude: Some(vec!["drafts/".to_string()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let validation = ResolvedConfig::resolve(None, &cli)
+            .validation_config(Path::new("project"), &RuleRegistry::builtin())
+            .unwrap();
+        assert!(validation.strict);
+        assert!(validation.check_spec);
+        assert!(validation.check_markdown);
+        assert!(!validation.check_best_practices);
+        assert!(
+            validation
+                .exclude
+                .is_match(Path::new("project/skills/demo/drafts/notes.md"), false)
+        );
+    }
+
+    #[test]
+    fn test_load_markdown_table() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+        fs::write(
+            &path,
+            "[lint]\nmdlint-config = \".markdownlint.json\"\n\n[markdown]\ndisable = [\"line-length\"]\nflavor = \"mkdocs\"\n",
+        )
+        .unwrap();
+
+        let loaded = LoadedConfig {
+            config: load_config(&path).unwrap(),
+            path,
+        };
+        let resolved = ResolvedConfig::resolve(Some(&loaded), &Config::default());
+        let markdown = resolved
+            .validation_config(Path::new("."), &RuleRegistry::builtin())
+            .unwrap()
+            .markdown_config;
+
+        assert_eq!(
+            markdown.config_file,
+            Some(temp.path().join(".markdownlint.json"))
+        );
+        assert_eq!(markdown.disable, vec!["line-length".to_string()]);
+        assert_eq!(markdown.flavor, Some(MarkdownFlavor::Mkdocs));
+        assert_eq!(
+            resolved
+                .format_config(Path::new("."))
+                .unwrap()
+                .markdown_config
+                .disable,
+            markdown.disable
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_markdown_rules() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("madskills.toml");
+
+        fs::write(&path, "[markdown]\nenable = [\"MD999\"]\n").unwrap();
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("MD999"));
+
+        fs::write(&path, "[markdown]\nflavor = \"rst\"\n").unwrap();
+        assert!(load_config(&path).is_err());
+    }
+}
@@ -1,11 +1,90 @@
 //! Skill discovery - finding and loading SKILL.md files
 
 use crate::error::{CoreError, CoreResult};
-use crate::models::{DiscoveryConfig, Skill};
+use crate::models::{
+    DiscoveryConfig, Skill, SkillsRoot, SourceLocation, ValidationError, ValidationErrorKind,
+    ValidationResult,
+};
 use crate::parser::parse_frontmatter;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
+/// Project-local skills directories, in priority order
+const WELL_KNOWN_DIRS: &[&str] = &[".github/skills/", ".claude/skills/", ".codex/skills/"];
+
+/// Ignore file honored while walking, with `.gitignore` syntax
+pub const IGNORE_FILE_NAME: &str = ".madskillsignore";
+
+/// Gitignore-style path patterns, matched relative to a root directory
+///
+/// Supports `*`, `**`, negation with `!` and directory-only patterns ending in
+/// `/`. Patterns containing a slash are anchored to the root; others match at
+/// any depth.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    root: PathBuf,
+    patterns: Vec<String>,
+    matcher: Gitignore,
+}
+
+impl PathFilter {
+    /// Compile `patterns`, to be matched against paths under `root`
+    pub fn new(root: &Path, patterns: &[String]) -> CoreResult<Self> {
+        // Paths are made relative to the root before matching
+        let mut builder = GitignoreBuilder::new("");
+        for pattern in patterns {
+            builder.add_line(None, pattern).map_err(|e| {
+                CoreError::DiscoveryFailed(format!("Invalid glob pattern '{}': {}", pattern, e))
+            })?;
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| CoreError::DiscoveryFailed(e.to_string()))?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            patterns: patterns.to_vec(),
+            matcher,
+        })
+    }
+
+    /// Whether `path` or one of its parents below the root matches
+    ///
+    /// Later patterns win, so `!pattern` takes back an earlier match.
+    pub fn is_match(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+        self.matcher
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+}
+
+impl Default for PathFilter {
+    /// A filter matching nothing
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("."),
+            patterns: Vec::new(),
+            matcher: Gitignore::empty(),
+        }
+    }
+}
+
+impl PartialEq for PathFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.patterns == other.patterns
+    }
+}
+
+impl Eq for PathFilter {}
+
 /// Detect skills directory using priority-based fallback
 ///
 /// Priority order:
@@ -27,7 +106,7 @@ pub fn detect_skills_directory(project_root: &Path) -> CoreResult<PathBuf> {
     }
 
     // 2. Check AGENTS.md for /skills pattern
-    if let Some(path) = find_skills_in_agents_md(project_root)
+    if let Some(path) = find_skills_in_agents_md(project_root).into_iter().next()
         && path.is_dir()
     {
         return Ok(path);
@@ -42,33 +121,63 @@ pub fn detect_skills_directory(project_root: &Path) -> CoreResult<PathBuf> {
     Ok(apply_fallback_logic(project_root))
 }
 
-/// Search AGENTS.md for /skills pattern
-fn find_skills_in_agents_md(project_root: &Path) -> Option<PathBuf> {
-    let agents_md = project_root.join("AGENTS.md");
-    if !agents_md.exists() {
-        return None;
+/// Detect every skills directory, for repos shipping skills to several agents
+///
+/// Unlike [`detect_skills_directory`], this collects all of: the
+/// AGENT_SKILLS_DIR environment variable, every AGENTS.md reference, every
+/// well-known directory and the user-level `~/.claude/skills`. Only existing
+/// directories count, each once; when there are none, the fallback directory
+/// is returned alone.
+pub fn detect_skills_roots(project_root: &Path) -> Vec<SkillsRoot> {
+    let mut candidates = Vec::new();
+    if let Ok(env_path) = std::env::var("AGENT_SKILLS_DIR") {
+        candidates.push(PathBuf::from(env_path));
+    }
+    candidates.extend(find_skills_in_agents_md(project_root));
+    candidates.extend(WELL_KNOWN_DIRS.iter().map(|dir| project_root.join(dir)));
+    candidates.extend(user_skills_directory());
+
+    let mut seen = HashSet::new();
+    let mut roots: Vec<SkillsRoot> = candidates
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .filter(|path| seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())))
+        .map(SkillsRoot::new)
+        .collect();
+
+    if roots.is_empty() {
+        roots.push(SkillsRoot::new(apply_fallback_logic(project_root)));
     }
+    roots
+}
 
-    let content = std::fs::read_to_string(&agents_md).ok()?;
+/// Search AGENTS.md for /skills patterns, in order of appearance
+fn find_skills_in_agents_md(project_root: &Path) -> Vec<PathBuf> {
+    let agents_md = project_root.join("AGENTS.md");
+    let Ok(content) = std::fs::read_to_string(&agents_md) else {
+        return Vec::new();
+    };
 
     // Pattern: /skills followed by non-alpha character or EOL
     // Matches: "/skills/", "/skills)", "/skills.", "/skills\n", etc.
-    let re = regex::Regex::new(r"([.~/][\w/.~-]*?/skills)(?:[^a-zA-Z]|$)").ok()?;
-
-    if let Some(captures) = re.captures(&content)
-        && let Some(matched) = captures.get(1)
-    {
-        let path_str = matched.as_str();
-        let expanded = expand_home_dir(path_str);
-        let full_path = if expanded.is_relative() {
-            project_root.join(expanded)
-        } else {
-            expanded
-        };
-        return Some(full_path);
-    }
+    let re = regex::Regex::new(r"([.~/][\w/.~-]*?/skills)(?:[^a-zA-Z]|$)").unwrap();
+
+    re.captures_iter(&content)
+        .filter_map(|captures| captures.get(1))
+        .map(|matched| {
+            let expanded = expand_home_dir(matched.as_str());
+            if expanded.is_relative() {
+                project_root.join(expanded)
+            } else {
+                expanded
+            }
+        })
+        .collect()
+}
 
-    None
+/// User-level Claude skills directory (`~/.claude/skills`)
+fn user_skills_directory() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".claude/skills"))
 }
 
 fn expand_home_dir(path_str: &str) -> PathBuf {
@@ -85,16 +194,10 @@ fn expand_home_dir(path_str: &str) -> PathBuf {
 fn check_well_known_directories(project_root: &Path) -> Option<PathBuf> {
     // Only check project-local directories
     // Global directories can be used via AGENT_SKILLS_DIR env var or AGENTS.md
-    let local_candidates = vec![".github/skills/", ".claude/skills/", ".codex/skills/"];
-
-    for candidate in local_candidates {
-        let path = project_root.join(candidate);
-        if path.is_dir() {
-            return Some(path);
-        }
-    }
-
-    None
+    WELL_KNOWN_DIRS
+        .iter()
+        .map(|candidate| project_root.join(candidate))
+        .find(|path| path.is_dir())
 }
 
 fn apply_fallback_logic(project_root: &Path) -> PathBuf {
@@ -105,15 +208,56 @@ fn apply_fallback_logic(project_root: &Path) -> PathBuf {
     }
 }
 
+/// Skills found by discovery, plus the SKILL.md files that could not be loaded
+#[derive(Debug, Clone, Default)]
+pub struct Discovered {
+    /// Successfully parsed skills
+    pub skills: Vec<Skill>,
+    /// One result per skill that failed to load, holding the error
+    pub failures: Vec<ValidationResult>,
+}
+
+impl Discovered {
+    /// Whether nothing at all was found
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty() && self.failures.is_empty()
+    }
+}
+
 /// Discover all skills matching the configuration
-pub fn discover_skills(config: &DiscoveryConfig) -> CoreResult<Vec<Skill>> {
-    let mut skills = Vec::new();
+///
+/// SKILL.md files that fail to parse, and misnamed ones (`skill.md`), are
+/// reported in [`Discovered::failures`] instead of being dropped.
+pub fn discover_skills(config: &DiscoveryConfig) -> CoreResult<Discovered> {
+    let mut discovered = walk_skills(config, std::slice::from_ref(&config.skills_base_path))?;
+    let roots = [SkillsRoot::new(config.skills_base_path.clone())];
+    for skill in &mut discovered.skills {
+        skill.source = source_root(&skill.skill_md_path, &roots).cloned();
+    }
+    Ok(discovered)
+}
+
+/// Walk `config.root_path` for SKILL.md files under any of `bases` or
+/// matching the include patterns
+fn walk_skills(config: &DiscoveryConfig, bases: &[PathBuf]) -> CoreResult<Discovered> {
+    let mut discovered = Discovered::default();
+    let include = PathFilter::new(&config.root_path, &config.include_patterns)?;
+    let exclude = PathFilter::new(&config.root_path, &config.exclude_patterns)?;
+    let bases = bases
+        .iter()
+        .map(std::path::absolute)
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Use ignore crate for .gitignore-aware traversal
     let mut walker = ignore::WalkBuilder::new(&config.root_path);
     walker
         .standard_filters(true) // Respect .gitignore
-        .hidden(false); // Don't skip hidden files
+        .hidden(false) // Don't skip hidden files
+        .add_custom_ignore_filename(IGNORE_FILE_NAME)
+        // Walk in a stable order so results do not depend on the filesystem
+        .sort_by_file_name(|a, b| a.cmp(b))
+        // Never descend into excluded directories
+        .filter_entry(move |entry| !exclude.is_match(entry.path(), is_dir(entry)));
 
     let walker = walker.build();
 
@@ -122,79 +266,233 @@ pub fn discover_skills(config: &DiscoveryConfig) -> CoreResult<Vec<Skill>> {
         let path = entry.path();
 
         // Check if this is a SKILL.md file
-        if path.file_name() != Some(OsStr::new("SKILL.md")) {
+        let Some(file_name) = path.file_name().and_then(OsStr::to_str) else {
+            continue;
+        };
+        if !file_name.eq_ignore_ascii_case("SKILL.md") {
             continue;
         }
 
         // Check if this path matches our discovery patterns
-        if !should_include_path(path, config)? {
+        if !should_include_path(path, &bases, &include)? {
             continue;
         }
 
-        // Check against exclude patterns
-        if is_excluded(path, &config.exclude_patterns) {
+        // A misnamed skill file, unless the real one sits next to it
+        if file_name != "SKILL.md" {
+            if !path.with_file_name("SKILL.md").is_file() {
+                discovered.failures.push(misnamed_skill_md(path, file_name));
+            }
             continue;
         }
 
         // Parse the skill
         match parse_skill(path) {
-            Ok(skill) => skills.push(skill),
-            Err(e) => {
-                // Log parse errors but continue discovery
-                eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
-            }
+            Ok(skill) => discovered.skills.push(skill),
+            Err(e) => discovered.failures.push(parse_failure(path, &e)),
         }
     }
 
-    Ok(skills)
+    Ok(discovered)
 }
 
-/// Check if a path should be included based on discovery config
-fn should_include_path(path: &Path, config: &DiscoveryConfig) -> CoreResult<bool> {
-    let path_str = path
-        .to_str()
-        .ok_or_else(|| CoreError::DiscoveryFailed(format!("Non-UTF8 path: {}", path.display())))?;
-
-    let skills_base = config
-        .skills_base_path
-        .to_str()
-        .ok_or_else(|| CoreError::DiscoveryFailed("Non-UTF8 skills base path".into()))?;
-
-    // Check if path is under detected skills directory
-    if path_str.contains(skills_base) && path.file_name() == Some(OsStr::new("SKILL.md")) {
-        return Ok(true);
+/// Discover skills in several skills roots, listing each SKILL.md once
+///
+/// Roots inside `config.root_path` share a single walk of the project, where
+/// the include patterns apply; roots outside it (such as `~/.claude/skills`)
+/// are walked on their own. Each skill's source is the deepest root holding
+/// it, and skills are listed root by root.
+pub fn discover_skills_in_roots(
+    config: &DiscoveryConfig,
+    roots: &[SkillsRoot],
+) -> CoreResult<Discovered> {
+    let (inside, outside): (Vec<&SkillsRoot>, Vec<&SkillsRoot>) = roots
+        .iter()
+        .partition(|root| root.path.starts_with(&config.root_path));
+
+    let mut walks = Vec::new();
+    if !inside.is_empty() {
+        let bases: Vec<PathBuf> = inside.iter().map(|root| root.path.clone()).collect();
+        walks.push(walk_skills(config, &bases)?);
+    }
+    for root in outside {
+        let root_config = DiscoveryConfig {
+            root_path: root.path.clone(),
+            skills_base_path: root.path.clone(),
+            // Include patterns are relative to the project
+            include_patterns: Vec::new(),
+            ..config.clone()
+        };
+        walks.push(walk_skills(&root_config, std::slice::from_ref(&root.path))?);
     }
 
-    // Check additional include patterns
-    for pattern in &config.include_patterns {
-        if glob_matches(path_str, pattern) {
-            return Ok(true);
+    let mut discovered = Discovered::default();
+    let mut seen = HashSet::new();
+    let mut first_visit =
+        |path: &Path| seen.insert(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+    for found in walks {
+        for mut skill in found.skills {
+            if first_visit(&skill.skill_md_path) {
+                skill.source = source_root(&skill.skill_md_path, roots).cloned();
+                discovered.skills.push(skill);
+            }
+        }
+        for failure in found.failures {
+            let file = failure.errors.first().and_then(|e| e.location.as_ref());
+            if file.is_none_or(|location| first_visit(&location.file)) {
+                discovered.failures.push(failure);
+            }
         }
     }
 
-    Ok(false)
+    // Root by root, skills outside every root last
+    let root_index = |path: &Path| {
+        source_root(path, roots)
+            .and_then(|source| roots.iter().position(|root| root == source))
+            .unwrap_or(roots.len())
+    };
+    discovered
+        .skills
+        .sort_by_cached_key(|skill| root_index(&skill.skill_md_path));
+    discovered
+        .failures
+        .sort_by_cached_key(|failure| root_index(&failure.skill_path));
+
+    Ok(discovered)
+}
+
+/// The deepest of `roots` containing `path`
+fn source_root<'a>(path: &Path, roots: &'a [SkillsRoot]) -> Option<&'a SkillsRoot> {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(&root.path))
+        .max_by_key(|root| root.path.components().count())
 }
 
-/// Check if path matches any exclude patterns
-fn is_excluded(path: &Path, exclude_patterns: &[String]) -> bool {
-    let path_str = match path.to_str() {
-        Some(s) => s,
-        None => return false,
+/// A SKILL.md that failed to parse, as a lint result for its skill
+fn parse_failure(skill_md_path: &Path, err: &CoreError) -> ValidationResult {
+    let at = |line: usize, column: usize| SourceLocation {
+        file: skill_md_path.to_path_buf(),
+        line,
+        column,
+        end_line: None,
+        end_column: None,
     };
 
-    for pattern in exclude_patterns {
-        if glob_matches(path_str, pattern) {
-            return true;
+    let error = match err {
+        CoreError::YamlParse { source, .. } => {
+            let message = source.to_string();
+            match message.strip_prefix("missing field ") {
+                Some(field) => ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredField,
+                    message: format!("Missing required field {}", field),
+                    location: Some(at(1, 1)),
+                },
+                None => ValidationError {
+                    kind: ValidationErrorKind::FrontmatterParseError,
+                    message: format!("Invalid frontmatter YAML: {}", message),
+                    // The YAML starts on the line after the opening delimiter
+                    location: Some(match source.location() {
+                        Some(location) => at(location.line() + 1, location.column()),
+                        None => at(1, 1),
+                    }),
+                },
+            }
         }
-    }
+        CoreError::InvalidFrontmatter { message, .. } => ValidationError {
+            kind: ValidationErrorKind::FrontmatterParseError,
+            message: message.clone(),
+            location: Some(at(1, 1)),
+        },
+        other => ValidationError {
+            kind: ValidationErrorKind::FrontmatterParseError,
+            message: format!("Failed to read SKILL.md: {}", other),
+            location: None,
+        },
+    };
+
+    failure_result(skill_md_path, error)
+}
 
-    false
+/// A skill file named `skill.md` or similar instead of SKILL.md
+fn misnamed_skill_md(path: &Path, file_name: &str) -> ValidationResult {
+    let error = ValidationError {
+        kind: ValidationErrorKind::MissingSkillMd,
+        message: format!("Skill file must be named SKILL.md (found {})", file_name),
+        location: Some(SourceLocation {
+            file: path.to_path_buf(),
+            line: 1,
+            column: 1,
+            end_line: None,
+            end_column: None,
+        }),
+    };
+    failure_result(path, error)
 }
 
-/// Simple glob pattern matching
-fn glob_matches(path: &str, pattern: &str) -> bool {
-    // Simple implementation - in production could use globset crate
-    path.contains(pattern)
+fn failure_result(skill_file: &Path, error: ValidationError) -> ValidationResult {
+    let root = skill_file.parent().unwrap_or(skill_file);
+    let mut result = ValidationResult::new(root.to_path_buf());
+    result.errors.push(error);
+    result
+}
+
+/// Every markdown file belonging to a skill, SKILL.md first
+///
+/// See [`skill_files`] for which files belong to a skill.
+pub fn markdown_files(skill: &Skill, exclude: &PathFilter) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = skill_files(skill, exclude)
+        .into_iter()
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter(|p| *p != skill.skill_md_path)
+        .collect();
+    files.insert(0, skill.skill_md_path.clone());
+    files
+}
+
+/// Every file belonging to a skill, sorted
+///
+/// Walks the skill root respecting `.gitignore`, `.madskillsignore` and
+/// `exclude`, and leaves out directories holding a SKILL.md of their own
+/// (nested skills lint their own files).
+pub fn skill_files(skill: &Skill, exclude: &PathFilter) -> Vec<PathBuf> {
+    let root = skill.root.clone();
+    let exclude = exclude.clone();
+    let walker = ignore::WalkBuilder::new(&skill.root)
+        .standard_filters(true)
+        .add_custom_ignore_filename(IGNORE_FILE_NAME)
+        .filter_entry(move |entry| {
+            let path = entry.path();
+            let nested_skill = path != root && is_dir(entry) && path.join("SKILL.md").is_file();
+            !nested_skill && !exclude.is_match(path, is_dir(entry))
+        })
+        .build();
+
+    let mut files: Vec<PathBuf> = walker
+        .filter_map(Result::ok)
+        .map(ignore::DirEntry::into_path)
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn is_dir(entry: &ignore::DirEntry) -> bool {
+    entry.file_type().is_some_and(|t| t.is_dir())
+}
+
+/// Check if a path should be included based on discovery config
+///
+/// `bases` must be absolute.
+fn should_include_path(path: &Path, bases: &[PathBuf], include: &PathFilter) -> CoreResult<bool> {
+    // Check if path is under a detected skills directory
+    let absolute = std::path::absolute(path)?;
+    if bases.iter().any(|base| absolute.starts_with(base)) {
+        return Ok(true);
+    }
+
+    // Check additional include patterns
+    Ok(include.is_match(path, false))
 }
 
 /// Parse a single skill from a SKILL.md file
@@ -213,6 +511,7 @@ fn parse_skill(skill_md_path: &Path) -> CoreResult<Skill> {
         root,
         skill_md_path: skill_md_path.to_path_buf(),
         metadata,
+        source: None,
     })
 }
 
@@ -220,6 +519,7 @@ fn parse_skill(skill_md_path: &Path) -> CoreResult<Skill> {
 #[allow(unsafe_code)]
 mod tests {
     use super::*;
+    use crate::models::Agent;
     use std::fs;
     use tempfile::TempDir;
 
@@ -314,8 +614,296 @@ mod tests {
             exclude_patterns: vec![],
         };
 
-        let skills = discover_skills(&config).unwrap();
+        let skills = discover_skills(&config).unwrap().skills;
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].metadata.name, "test-skill");
     }
+
+    #[test]
+    fn test_markdown_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("demo");
+        for dir in ["examples", "drafts", "nested"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "SKILL.md",
+            "reference.md",
+            "script.py",
+            "examples/basic.md",
+            "drafts/wip.md",
+            "nested/SKILL.md",
+            "nested/inner.md",
+        ] {
+            fs::write(root.join(file), "# Doc\n").unwrap();
+        }
+        fs::write(
+            root.join("SKILL.md"),
+            "---\nname: demo\ndescription: Demo\n---\n# Demo\n",
+        )
+        .unwrap();
+
+        let skill = parse_skill(&root.join("SKILL.md")).unwrap();
+        let exclude = PathFilter::new(temp.path(), &["drafts/".to_string()]).unwrap();
+        let files = markdown_files(&skill, &exclude);
+        let relative: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(&root).unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(relative, ["SKILL.md", "examples/basic.md", "reference.md"]);
+    }
+
+    #[test]
+    fn test_path_filter() {
+        let root = Path::new("/repo");
+        let patterns = |list: &[&str]| {
+            let list: Vec<String> = list.iter().map(|s| s.to_string()).collect();
+            PathFilter::new(root, &list).unwrap()
+        };
+
+        let filter = patterns(&["**/vendor/**"]);
+        assert!(filter.is_match(Path::new("/repo/a/vendor/x/SKILL.md"), false));
+        assert!(filter.is_match(Path::new("/repo/vendor/SKILL.md"), false));
+        assert!(!filter.is_match(Path::new("/repo/vendors/SKILL.md"), false));
+
+        // Directory patterns match the directory and everything below it
+        let filter = patterns(&["drafts/"]);
+        assert!(filter.is_match(Path::new("/repo/drafts"), true));
+        assert!(filter.is_match(Path::new("/repo/skills/drafts/a/SKILL.md"), false));
+        assert!(!filter.is_match(Path::new("/repo/drafts"), false));
+
+        // Slashes anchor patterns to the root
+        let filter = patterns(&["skills/old", "!skills/old/keep"]);
+        assert!(filter.is_match(Path::new("/repo/skills/old/a/SKILL.md"), false));
+        assert!(!filter.is_match(Path::new("/repo/x/skills/old/SKILL.md"), false));
+        assert!(!filter.is_match(Path::new("/repo/skills/old/keep/SKILL.md"), false));
+
+        assert!(!filter.is_match(Path::new("/elsewhere/skills/old"), true));
+        assert!(!PathFilter::default().is_match(Path::new("./x"), false));
+    }
+
+    #[test]
+    fn test_discover_excludes_and_ignore_file() {
+        let temp = TempDir::new().unwrap();
+        for name in ["keep", "vendor/lib/dep", "wip"] {
+            let dir = temp.path().join(".github/skills").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            let skill_name = name.rsplit('/').next().unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", skill_name),
+            )
+            .unwrap();
+        }
+        fs::write(temp.path().join(IGNORE_FILE_NAME), "wip/\n").unwrap();
+
+        let config = DiscoveryConfig {
+            root_path: temp.path().to_path_buf(),
+            skills_base_path: temp.path().join(".github/skills"),
+            include_patterns: vec![],
+            exclude_patterns: vec!["**/vendor/**".to_string()],
+        };
+        let skills = discover_skills(&config).unwrap().skills;
+        let names: Vec<_> = skills.iter().map(|s| s.metadata.name.as_str()).collect();
+        assert_eq!(names, ["keep"]);
+    }
+
+    #[test]
+    fn test_discover_ignores_sibling_of_skills_base() {
+        let temp = TempDir::new().unwrap();
+        for dir in [".github/skills/keep", ".github/skills-archive/old"] {
+            let dir = temp.path().join(dir);
+            fs::create_dir_all(&dir).unwrap();
+            let name = dir.file_name().unwrap().to_str().unwrap().to_string();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let config = DiscoveryConfig {
+            root_path: temp.path().to_path_buf(),
+            skills_base_path: temp.path().join(".github/skills"),
+            include_patterns: vec![],
+            exclude_patterns: vec![],
+        };
+        let skills = discover_skills(&config).unwrap().skills;
+        let names: Vec<_> = skills.iter().map(|s| s.metadata.name.as_str()).collect();
+        assert_eq!(names, ["keep"]);
+    }
+
+    #[test]
+    fn test_detect_skills_roots() {
+        let temp = TempDir::new().unwrap();
+        for dir in [".github/skills", ".claude/skills", "docs/skills"] {
+            fs::create_dir_all(temp.path().join(dir)).unwrap();
+        }
+        fs::write(
+            temp.path().join("AGENTS.md"),
+            "Skills live in `./docs/skills/` and `./missing/skills/`, mirrored in `.claude/skills/`.",
+        )
+        .unwrap();
+
+        // Other tests may set AGENT_SKILLS_DIR, and ~/.claude/skills may exist
+        let roots: Vec<_> = detect_skills_roots(temp.path())
+            .into_iter()
+            .filter(|root| root.path.starts_with(temp.path()))
+            .map(|root| {
+                (
+                    root.path.strip_prefix(temp.path()).unwrap().to_path_buf(),
+                    root.agent,
+                )
+            })
+            .collect();
+        assert_eq!(
+            roots,
+            [
+                (PathBuf::from("docs/skills"), None),
+                (PathBuf::from(".claude/skills"), Some(Agent::Claude)),
+                (PathBuf::from(".github/skills"), Some(Agent::GitHub)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discover_skills_in_roots() {
+        let temp = TempDir::new().unwrap();
+        for (dir, name) in [
+            (".github/skills/review", "review"),
+            (".claude/skills/review", "review"),
+            (".claude/skills/deploy", "deploy"),
+        ] {
+            let skill_dir = temp.path().join(dir);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: Test\n---\n", name),
+            )
+            .unwrap();
+        }
+
+        let roots = vec![
+            SkillsRoot::new(temp.path().join(".github/skills")),
+            SkillsRoot::new(temp.path().join(".claude/skills")),
+            // Listed twice: still discovered once
+            SkillsRoot::new(temp.path().join(".claude/skills")),
+        ];
+        let config = DiscoveryConfig {
+            root_path: temp.path().to_path_buf(),
+            skills_base_path: roots[0].path.clone(),
+            include_patterns: vec![],
+            exclude_patterns: vec![],
+        };
+
+        let skills = discover_skills_in_roots(&config, &roots).unwrap().skills;
+        let mut found: Vec<_> = skills
+            .iter()
+            .map(|s| (s.metadata.name.as_str(), s.source.as_ref().unwrap().agent))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("deploy", Some(Agent::Claude)),
+                ("review", Some(Agent::Claude)),
+                ("review", Some(Agent::GitHub)),
+            ]
+        );
+        assert_eq!(crate::validator::validate_uniqueness(&skills).len(), 1);
+    }
+
+    #[test]
+    fn test_discover_in_roots_tags_included_skills_with_their_root() {
+        let temp = TempDir::new().unwrap();
+        for dir in [".github/skills/review", ".claude/skills/deploy"] {
+            let skill_dir = temp.path().join(dir);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("SKILL.md"),
+                "---\nname: test\ndescription: Test\n---\n",
+            )
+            .unwrap();
+        }
+
+        let roots = vec![
+            SkillsRoot::new(temp.path().join(".github/skills")),
+            SkillsRoot::new(temp.path().join(".claude/skills")),
+        ];
+        let config = DiscoveryConfig {
+            root_path: temp.path().to_path_buf(),
+            skills_base_path: roots[0].path.clone(),
+            // Also matches a skill in the second root
+            include_patterns: vec!["deploy/".into()],
+            exclude_patterns: vec![],
+        };
+
+        let skills = discover_skills_in_roots(&config, &roots).unwrap().skills;
+        let agents: Vec<_> = skills
+            .iter()
+            .map(|s| s.source.as_ref().and_then(|root| root.agent))
+            .collect();
+        assert_eq!(agents, [Some(Agent::GitHub), Some(Agent::Claude)]);
+    }
+
+    #[test]
+    fn test_discover_reports_parse_failures() {
+        let temp = TempDir::new().unwrap();
+        let base = temp.path().join(".github/skills");
+        for (dir, file, content) in [
+            ("ok", "SKILL.md", "---\nname: ok\ndescription: Fine\n---\n"),
+            (
+                "broken",
+                "SKILL.md",
+                "---\nname: broken\ndescription: a: b\n---\n",
+            ),
+            ("partial", "SKILL.md", "---\nname: partial\n---\n"),
+            ("plain", "SKILL.md", "# No frontmatter\n"),
+            (
+                "lowercase",
+                "skill.md",
+                "---\nname: lowercase\ndescription: Low\n---\n",
+            ),
+        ] {
+            fs::create_dir_all(base.join(dir)).unwrap();
+            fs::write(base.join(dir).join(file), content).unwrap();
+        }
+
+        let config = DiscoveryConfig {
+            root_path: temp.path().to_path_buf(),
+            skills_base_path: base.clone(),
+            include_patterns: vec![],
+            exclude_patterns: vec![],
+        };
+        let discovered = discover_skills(&config).unwrap();
+        assert_eq!(discovered.skills.len(), 1);
+
+        let failure = |dir: &str| {
+            let result = discovered
+                .failures
+                .iter()
+                .find(|r| r.skill_path == base.join(dir))
+                .unwrap();
+            assert_eq!(result.errors.len(), 1);
+            result.errors[0].clone()
+        };
+
+        let broken = failure("broken");
+        assert_eq!(broken.kind, ValidationErrorKind::FrontmatterParseError);
+        let location = broken.location.unwrap();
+        assert_eq!(location.file, base.join("broken/SKILL.md"));
+        assert_eq!((location.line, location.column), (3, 15));
+
+        let partial = failure("partial");
+        assert_eq!(partial.kind, ValidationErrorKind::MissingRequiredField);
+        assert!(partial.message.contains("description"));
+
+        let plain = failure("plain");
+        assert_eq!(plain.kind, ValidationErrorKind::FrontmatterParseError);
+        assert!(plain.message.contains("'---'"));
+
+        let lowercase = failure("lowercase");
+        assert_eq!(lowercase.kind, ValidationErrorKind::MissingSkillMd);
+        assert!(lowercase.message.contains("skill.md"));
+    }
 }
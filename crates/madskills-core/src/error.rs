@@ -16,6 +16,15 @@ pub enum CoreError {
     #[error("Invalid frontmatter in {path}: {message}")]
     InvalidFrontmatter { path: PathBuf, message: String },
 
+    #[error("Invalid config file {path}: {message}")]
+    Config { path: PathBuf, message: String },
+
+    #[error("Invalid baseline file {path}: {message}")]
+    Baseline { path: PathBuf, message: String },
+
+    #[error("Invalid rule: {0}")]
+    InvalidRule(String),
+
     #[error("Skill discovery failed: {0}")]
     DiscoveryFailed(String),
 
@@ -0,0 +1,274 @@
+//! Applying automatic fixes attached to best practice violations
+//!
+//! Fixes are grouped per file. A fix is applied whole or not at all: when its
+//! edits overlap an edit that was already accepted, it is left for the next
+//! pass, after the file has been re-linted. Passes run in memory (see
+//! [`fix_until_stable`]), so previewing fixes and applying them agree.
+
+use crate::error::CoreResult;
+use crate::models::{Applicability, Fix, TextEdit, ValidationResult};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The fixed content of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFix {
+    pub path: PathBuf,
+    pub original: String,
+    pub fixed: String,
+    /// Number of fixes applied
+    pub applied: usize,
+}
+
+impl FileFix {
+    /// Unified diff from the original to the fixed content
+    pub fn diff(&self) -> String {
+        unified_diff(&self.path, &self.original, &self.fixed)
+    }
+}
+
+/// Fixes from `results` that may be applied
+pub fn collect_fixes(results: &[ValidationResult], unsafe_fixes: bool) -> Vec<&Fix> {
+    results
+        .iter()
+        .flat_map(|r| &r.best_practice_violations)
+        .filter_map(|v| v.fix.as_ref())
+        .filter(|fix| unsafe_fixes || fix.applicability == Applicability::Safe)
+        .collect()
+}
+
+/// Compute the fixed content of every file with applicable fixes
+///
+/// Nothing is written; files whose content would not change are skipped.
+pub fn plan_fixes(results: &[ValidationResult], unsafe_fixes: bool) -> CoreResult<Vec<FileFix>> {
+    plan_fixes_with(results, unsafe_fixes, &HashMap::new())
+}
+
+/// [`plan_fixes`], reading `contents` in place of the files on disk
+fn plan_fixes_with(
+    results: &[ValidationResult],
+    unsafe_fixes: bool,
+    contents: &HashMap<PathBuf, String>,
+) -> CoreResult<Vec<FileFix>> {
+    let mut by_file: BTreeMap<&Path, Vec<&Fix>> = BTreeMap::new();
+    for fix in collect_fixes(results, unsafe_fixes) {
+        by_file.entry(fix.path.as_path()).or_default().push(fix);
+    }
+
+    let mut plans = Vec::new();
+    for (path, fixes) in by_file {
+        let original = match contents.get(path) {
+            Some(content) => content.clone(),
+            None => std::fs::read_to_string(path)?,
+        };
+        let (fixed, applied) = apply_fixes(&original, &fixes);
+        if fixed != original {
+            plans.push(FileFix {
+                path: path.to_path_buf(),
+                original,
+                fixed,
+                applied,
+            });
+        }
+    }
+    Ok(plans)
+}
+
+/// Fix files in memory until no more fixes apply
+///
+/// Each pass applies the fixes in `results`, then `relint` lints again with
+/// the fixed content in place of the files on disk, since fixes may uncover
+/// new findings or unblock overlapping ones. Stops after `max_passes`.
+/// Nothing is written; each returned plan goes from the file on disk to its
+/// final content.
+pub fn fix_until_stable(
+    mut results: Vec<ValidationResult>,
+    unsafe_fixes: bool,
+    max_passes: usize,
+    mut relint: impl FnMut(&HashMap<PathBuf, String>) -> Vec<ValidationResult>,
+) -> CoreResult<Vec<FileFix>> {
+    let mut plans: BTreeMap<PathBuf, FileFix> = BTreeMap::new();
+    let mut contents: HashMap<PathBuf, String> = HashMap::new();
+
+    for _ in 0..max_passes {
+        let pass = plan_fixes_with(&results, unsafe_fixes, &contents)?;
+        if pass.is_empty() {
+            break;
+        }
+        for fix in pass {
+            contents.insert(fix.path.clone(), fix.fixed.clone());
+            plans
+                .entry(fix.path.clone())
+                .and_modify(|plan| {
+                    plan.fixed = fix.fixed.clone();
+                    plan.applied += fix.applied;
+                })
+                .or_insert(fix);
+        }
+        results = relint(&contents);
+    }
+
+    Ok(plans
+        .into_values()
+        .filter(|plan| plan.fixed != plan.original)
+        .collect())
+}
+
+/// Apply non-overlapping fixes to `content`, returning the new content and the
+/// number of fixes applied
+pub fn apply_fixes(content: &str, fixes: &[&Fix]) -> (String, usize) {
+    let mut fixes = fixes.to_vec();
+    fixes.sort_by_key(|fix| fix.edits.iter().map(|e| e.range.start).min());
+
+    let mut accepted: Vec<&TextEdit> = Vec::new();
+    let mut applied = 0;
+    for fix in fixes {
+        let valid = fix.edits.iter().all(|edit| {
+            edit.range.start <= edit.range.end
+                && edit.range.end <= content.len()
+                && content.is_char_boundary(edit.range.start)
+                && content.is_char_boundary(edit.range.end)
+        });
+        let overlaps = fix.edits.iter().any(|edit| {
+            accepted
+                .iter()
+                .any(|other| overlaps(&edit.range, &other.range))
+        });
+        if valid && !overlaps {
+            accepted.extend(&fix.edits);
+            applied += 1;
+        }
+    }
+
+    // Apply back to front so earlier offsets stay valid
+    accepted.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    let mut fixed = content.to_string();
+    for edit in accepted.iter().rev() {
+        fixed.replace_range(edit.range.clone(), &edit.replacement);
+    }
+    (fixed, applied)
+}
+
+/// Whether two edits touch the same text (two insertions at one point do too)
+fn overlaps(a: &std::ops::Range<usize>, b: &std::ops::Range<usize>) -> bool {
+    (a.start < b.end && b.start < a.end) || a.start == b.start
+}
+
+/// Replace a file's content atomically (write a sibling temp file, then rename)
+pub fn write_atomic(path: &Path, content: &str) -> CoreResult<()> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+    temp.write_all(content.as_bytes())?;
+    if let Ok(metadata) = std::fs::metadata(path) {
+        temp.as_file().set_permissions(metadata.permissions())?;
+    }
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Unified diff with `a/` and `b/` headers
+pub fn unified_diff(path: &Path, original: &str, fixed: &str) -> String {
+    let name = path.display().to_string();
+    similar::TextDiff::from_lines(original, fixed)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", name), &format!("b/{}", name))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace(range: std::ops::Range<usize>, text: &str) -> Fix {
+        Fix::safe(
+            "SKILL.md",
+            vec![TextEdit {
+                range,
+                replacement: text.into(),
+            }],
+        )
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let content = "see a\\b and c\\d";
+        let first = replace(4..7, "a/b");
+        let second = replace(12..15, "c/d");
+
+        let (fixed, applied) = apply_fixes(content, &[&second, &first]);
+        assert_eq!(fixed, "see a/b and c/d");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn test_overlapping_fix_skipped() {
+        let content = "abcdef";
+        let first = replace(0..3, "X");
+        let second = replace(2..4, "Y");
+        let insert = Fix::safe("SKILL.md", vec![TextEdit::insert(6, "!")]);
+
+        let (fixed, applied) = apply_fixes(content, &[&first, &second, &insert]);
+        assert_eq!(fixed, "Xdef!");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn test_write_atomic_and_diff() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("SKILL.md");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+
+        write_atomic(&path, "one\n2\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "one\n2\n");
+
+        let diff = unified_diff(Path::new("SKILL.md"), "one\ntwo\n", "one\n2\n");
+        assert!(diff.starts_with("--- a/SKILL.md\n+++ b/SKILL.md\n"));
+        assert!(diff.contains("-two\n+2\n"));
+    }
+
+    #[test]
+    fn test_fix_until_stable_relints_fixed_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("SKILL.md");
+        std::fs::write(&path, "aaa").unwrap();
+
+        // Each pass finds the first remaining `a`, as a re-lint would
+        let findings = |content: &str| {
+            let mut result = ValidationResult::new(dir.path().to_path_buf());
+            if let Some(offset) = content.find('a') {
+                result
+                    .best_practice_violations
+                    .push(crate::models::BestPracticeViolation {
+                        code: crate::models::BestPracticeCode::AS005,
+                        severity: crate::models::Severity::Warning,
+                        message: "a".into(),
+                        location: None,
+                        fix: Some(Fix::safe(
+                            &path,
+                            vec![TextEdit {
+                                range: offset..offset + 1,
+                                replacement: "b".into(),
+                            }],
+                        )),
+                    });
+            }
+            vec![result]
+        };
+
+        let plans = fix_until_stable(findings("aaa"), false, 10, |contents| {
+            findings(&contents[&path])
+        })
+        .unwrap();
+
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].original, "aaa");
+        assert_eq!(plans[0].fixed, "bbb");
+        assert_eq!(plans[0].applied, 3);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "aaa");
+    }
+}
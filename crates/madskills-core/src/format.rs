@@ -0,0 +1,171 @@
+//! Formatting skill files in memory
+//!
+//! Formatting never touches the disk: [`Formatter::format_skill`] returns the
+//! original and formatted content of each file, and callers decide whether to
+//! check, diff or [write](FormattedFile::write) them.
+
+use crate::discovery::{PathFilter, markdown_files};
+use crate::error::CoreResult;
+use crate::fix::{unified_diff, write_atomic};
+use crate::frontmatter::format_frontmatter;
+use crate::markdown::{MarkdownConfig, MarkdownLinter};
+use crate::models::Skill;
+use crate::toc;
+use std::path::PathBuf;
+
+/// What to format
+#[derive(Debug, Clone)]
+pub struct FormatConfig {
+    /// Rewrite YAML frontmatter
+    pub frontmatter: bool,
+    /// Apply markdown lint fixes
+    pub markdown: bool,
+    /// Markdown lint settings
+    pub markdown_config: MarkdownConfig,
+    /// Insert or refresh tables of contents in markdown files other than
+    /// SKILL.md
+    pub toc: bool,
+    /// Files inside skills to leave alone
+    pub exclude: PathFilter,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            frontmatter: true,
+            markdown: true,
+            markdown_config: MarkdownConfig::default(),
+            toc: false,
+            exclude: PathFilter::default(),
+        }
+    }
+}
+
+/// A file's content before and after formatting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedFile {
+    pub path: PathBuf,
+    pub original: String,
+    pub formatted: String,
+}
+
+impl FormattedFile {
+    /// Whether formatting changed the content
+    pub fn is_changed(&self) -> bool {
+        self.original != self.formatted
+    }
+
+    /// Unified diff from the original to the formatted content
+    pub fn diff(&self) -> String {
+        unified_diff(&self.path, &self.original, &self.formatted)
+    }
+
+    /// Replace the file with its formatted content (atomically)
+    pub fn write(&self) -> CoreResult<()> {
+        write_atomic(&self.path, &self.formatted)
+    }
+}
+
+/// Every file formatting looked at for one skill, SKILL.md first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedSkill {
+    pub files: Vec<FormattedFile>,
+}
+
+impl FormattedSkill {
+    /// Files whose content would change
+    pub fn changed(&self) -> impl Iterator<Item = &FormattedFile> {
+        self.files.iter().filter(|f| f.is_changed())
+    }
+}
+
+/// Skill formatter
+pub struct Formatter {
+    pub config: FormatConfig,
+}
+
+impl Formatter {
+    /// Create a new formatter
+    pub fn new(config: FormatConfig) -> Self {
+        Self { config }
+    }
+
+    /// Format a skill's markdown files in memory
+    pub fn format_skill(&self, skill: &Skill) -> CoreResult<FormattedSkill> {
+        let markdown_config = &self.config.markdown_config;
+        let linter = self
+            .config
+            .markdown
+            .then(|| MarkdownLinter::new(markdown_config))
+            .transpose()?;
+        let mut files = Vec::new();
+
+        for path in markdown_files(skill, &self.config.exclude) {
+            let is_skill_md = path == skill.skill_md_path;
+            let original = std::fs::read_to_string(&path)?;
+            let mut formatted = original.clone();
+
+            if is_skill_md && self.config.frontmatter {
+                formatted = format_frontmatter(&formatted, &path)?;
+            }
+            if let Some(linter) = &linter {
+                formatted = linter.format(&formatted)?;
+            }
+            if !is_skill_md
+                && self.config.toc
+                && let Some(updated) = toc::update_toc(&formatted)
+            {
+                formatted = updated;
+            }
+
+            files.push(FormattedFile {
+                path,
+                original,
+                formatted,
+            });
+        }
+
+        Ok(FormattedSkill { files })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_frontmatter;
+
+    #[test]
+    fn test_format_skill_is_read_only() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().join("demo");
+        std::fs::create_dir(&root).unwrap();
+        let skill_md = root.join("SKILL.md");
+        let content = "---\ndescription: Demo\nname: demo\n---\n\n# Demo   \n";
+        std::fs::write(&skill_md, content).unwrap();
+
+        let skill = Skill {
+            root: root.clone(),
+            skill_md_path: skill_md.clone(),
+            metadata: parse_frontmatter(content, &skill_md).unwrap(),
+            source: None,
+        };
+        let formatted = Formatter::new(FormatConfig::default())
+            .format_skill(&skill)
+            .unwrap();
+
+        let changed: Vec<_> = formatted.changed().collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(
+            changed[0].formatted,
+            "---\nname: demo\ndescription: Demo\n---\n\n# Demo\n"
+        );
+        assert!(changed[0].diff().contains("+name: demo\n"));
+        assert_eq!(std::fs::read_to_string(&skill_md).unwrap(), content);
+
+        changed[0].write().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&skill_md).unwrap(),
+            changed[0].formatted
+        );
+    }
+}
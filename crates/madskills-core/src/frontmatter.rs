@@ -0,0 +1,422 @@
+//! Lossless formatting of SKILL.md frontmatter
+//!
+//! The YAML is rewritten line by line rather than re-serialized, so comments,
+//! quoting, block scalars and unknown keys all survive. Formatting:
+//!
+//! - orders top-level keys as in [`KEY_ORDER`], followed by any other keys in
+//!   their original order; comments above a key move with it
+//! - sorts the keys nested under `metadata` and indents them by two spaces
+//! - puts exactly one space after each key's colon and trims trailing
+//!   whitespace outside block scalars
+//! - drops blank lines at the start and end of the frontmatter
+//!
+//! The result is parsed again and compared with the original. If the data
+//! differs in any way the frontmatter is left untouched.
+
+use crate::error::{CoreError, CoreResult};
+use crate::parser::{extract_frontmatter, find_key_colon};
+use std::path::Path;
+
+/// Canonical order of the top-level frontmatter keys
+pub const KEY_ORDER: [&str; 6] = [
+    "name",
+    "description",
+    "license",
+    "compatibility",
+    "allowed-tools",
+    "metadata",
+];
+
+/// Indentation of keys nested under `metadata`
+const NESTED_INDENT: &str = "  ";
+
+/// A mapping key with the lines it owns
+struct Entry {
+    key: String,
+    /// Blank and comment lines directly above the key
+    leading: Vec<String>,
+    /// The key line, then its continuation lines
+    lines: Vec<String>,
+}
+
+impl Entry {
+    /// Text after the key's colon
+    fn value(&self) -> &str {
+        let line = &self.lines[0];
+        find_key_colon(line).map_or("", |colon| line[colon + 1..].trim())
+    }
+
+    /// Whether the value is a `|` or `>` block scalar
+    fn is_block_scalar(&self) -> bool {
+        self.value().starts_with(['|', '>'])
+    }
+}
+
+/// Format the frontmatter of a SKILL.md file, leaving the body untouched
+///
+/// Fails only when the frontmatter is missing or is not valid YAML.
+pub fn format_frontmatter(content: &str, path: &Path) -> CoreResult<String> {
+    let (yaml, _) = extract_frontmatter(content, path)?;
+    let value: serde_yaml::Value =
+        serde_yaml::from_str(yaml).map_err(|source| CoreError::YamlParse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    if !value.is_mapping() {
+        return Ok(content.to_string());
+    }
+
+    let Some(formatted) = format_yaml(yaml) else {
+        return Ok(content.to_string());
+    };
+    let eol = if content.starts_with("---\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut new_yaml: String = formatted
+        .iter()
+        .map(|line| format!("{}{}", line, eol))
+        .collect();
+    // The closing delimiter supplies the final newline
+    new_yaml.pop();
+
+    // Never trade data for style. Both sides are parsed with the newline that
+    // precedes the closing delimiter, which a final block scalar keeps.
+    let data = |yaml: &str| serde_yaml::from_str::<serde_yaml::Value>(&format!("{}\n", yaml)).ok();
+    if data(&new_yaml).is_none() || data(&new_yaml) != data(yaml) {
+        return Ok(content.to_string());
+    }
+
+    let start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+    let end = start + yaml.len();
+    Ok(format!(
+        "{}{}{}",
+        &content[..start],
+        new_yaml,
+        &content[end..]
+    ))
+}
+
+/// Formatted lines of a top-level mapping, or `None` when its layout is not
+/// one this formatter understands
+fn format_yaml(yaml: &str) -> Option<Vec<String>> {
+    let lines: Vec<String> = yaml
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+        .collect();
+    let (mut entries, trailer) = split_entries(&lines)?;
+
+    // A stable sort keeps unknown keys in their original order
+    entries.sort_by_key(|e| {
+        KEY_ORDER
+            .iter()
+            .position(|key| *key == e.key)
+            .unwrap_or(KEY_ORDER.len())
+    });
+
+    let mut out: Vec<String> = Vec::new();
+    for entry in entries {
+        out.extend(format_entry(entry, true)?);
+    }
+    out.extend(trailer.iter().map(|line| line.trim_end().to_string()));
+
+    let first = out.iter().position(|l| !l.is_empty()).unwrap_or(out.len());
+    let last = out.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+    Some(out[first..last.max(first)].to_vec())
+}
+
+/// Split mapping lines (keys at column one) into entries, plus the blank and
+/// comment lines after the last one
+fn split_entries(lines: &[String]) -> Option<(Vec<Entry>, Vec<String>)> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut pending = Vec::new();
+
+    for line in lines {
+        let body = line.trim_start();
+        let indented = body.len() < line.len();
+
+        if body.is_empty() || (!indented && body.starts_with('#')) {
+            pending.push(line.clone());
+        } else if !indented && !body.starts_with('-') {
+            let colon = find_key_colon(line)?;
+            entries.push(Entry {
+                key: line[..colon].trim().trim_matches(['"', '\'']).to_string(),
+                leading: std::mem::take(&mut pending),
+                lines: vec![line.clone()],
+            });
+        } else {
+            // Gaps inside an entry (e.g. blank lines in a block scalar) stay put
+            let entry = entries.last_mut()?;
+            entry.lines.append(&mut pending);
+            entry.lines.push(line.clone());
+        }
+    }
+
+    Some((entries, pending))
+}
+
+/// Formatted lines of one entry
+fn format_entry(entry: Entry, top_level: bool) -> Option<Vec<String>> {
+    let mut out: Vec<String> = entry
+        .leading
+        .iter()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    out.push(format_key_line(&entry.lines[0]));
+
+    let rest = &entry.lines[1..];
+    if top_level
+        && entry.key == "metadata"
+        && entry.value().is_empty()
+        && let Some(nested) = format_nested(rest)
+    {
+        out.extend(nested);
+        return Some(out);
+    }
+
+    if entry.is_block_scalar() {
+        out.extend(rest.iter().cloned());
+    } else {
+        out.extend(rest.iter().map(|line| line.trim_end().to_string()));
+    }
+    Some(out)
+}
+
+/// `key: value` with one space after the colon
+fn format_key_line(line: &str) -> String {
+    let Some(colon) = find_key_colon(line) else {
+        return line.trim_end().to_string();
+    };
+    let value = line[colon + 1..].trim();
+    let key = line[..colon].trim_end();
+    if value.is_empty() {
+        format!("{}:", key)
+    } else {
+        format!("{}: {}", key, value)
+    }
+}
+
+/// Nested mapping lines re-indented with sorted keys, or `None` when the
+/// block is not a plain mapping (a sequence, a flow collection, ...)
+fn format_nested(lines: &[String]) -> Option<Vec<String>> {
+    let is_content = |l: &&String| {
+        let body = l.trim_start();
+        !body.is_empty() && !body.starts_with('#')
+    };
+    let first = lines.iter().find(is_content)?;
+    let indent = first.len() - first.trim_start().len();
+    if first.trim_start().starts_with('-') {
+        return None;
+    }
+
+    let mut dedented = Vec::with_capacity(lines.len());
+    for line in lines {
+        let body = line.trim_start();
+        let line_indent = line.len() - body.len();
+        if body.is_empty() {
+            dedented.push(String::new());
+        } else if line_indent >= indent {
+            dedented.push(line[indent..].to_string());
+        } else if body.starts_with('#') {
+            dedented.push(body.to_string());
+        } else {
+            return None;
+        }
+    }
+
+    let (mut entries, trailer) = split_entries(&dedented)?;
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut out = Vec::new();
+    for entry in entries {
+        out.extend(format_entry(entry, false)?);
+    }
+    out.extend(trailer.iter().map(|line| line.trim_end().to_string()));
+
+    Some(
+        out.into_iter()
+            .map(|line| {
+                if line.is_empty() {
+                    line
+                } else {
+                    format!("{}{}", NESTED_INDENT, line)
+                }
+            })
+            .collect(),
+    )
+}
+
+/// YAML text for a string value, to follow `key: ` on a line indented by
+/// `indent` spaces
+///
+/// Plain scalars are used when they read back as the same string, block
+/// scalars for multi-line text, and double quotes otherwise.
+pub fn yaml_value(value: &str, indent: usize) -> String {
+    if !value.contains('\n') {
+        return if is_plain_safe(value) {
+            value.to_string()
+        } else {
+            double_quoted(value)
+        };
+    }
+
+    // Block scalars cannot start with indentation or carry trailing spaces
+    let lines: Vec<&str> = value.trim_end_matches('\n').split('\n').collect();
+    let blockable = !value.trim().is_empty()
+        && !lines[0].starts_with(' ')
+        && lines
+            .iter()
+            .all(|l| l.trim_end() == *l && !l.contains(['\t', '\r']));
+    if !blockable {
+        return double_quoted(value);
+    }
+
+    let chomp = match value.len() - value.trim_end_matches('\n').len() {
+        0 => "-",
+        1 => "",
+        _ => "+",
+    };
+    let pad = " ".repeat(indent + 2);
+    let mut out = format!("|{}", chomp);
+    for line in lines {
+        out.push('\n');
+        if !line.is_empty() {
+            out.push_str(&pad);
+            out.push_str(line);
+        }
+    }
+    for _ in 1..value.len() - value.trim_end_matches('\n').len() {
+        out.push('\n');
+    }
+    out
+}
+
+/// Whether `value` can be written unquoted
+fn is_plain_safe(value: &str) -> bool {
+    let Some(first) = value.chars().next() else {
+        return false;
+    };
+    let indicator = "-?:,[]{}#&*!|>'\"%@`".contains(first);
+    let ambiguous = value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.trim() != value
+        || value.chars().any(char::is_control);
+    !indicator
+        && !ambiguous
+        && serde_yaml::from_str::<serde_yaml::Value>(value).ok()
+            == Some(serde_yaml::Value::String(value.to_string()))
+}
+
+/// A double-quoted scalar; JSON string escapes are valid YAML escapes
+fn double_quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn format(content: &str) -> String {
+        format_frontmatter(content, Path::new("SKILL.md")).unwrap()
+    }
+
+    fn frontmatter_value(content: &str) -> serde_yaml::Value {
+        let (yaml, _) = extract_frontmatter(content, Path::new("SKILL.md")).unwrap();
+        serde_yaml::from_str(&format!("{}\n", yaml)).unwrap()
+    }
+
+    /// Every `<case>.md` in the corpus formats to `<case>.expected.md`,
+    /// keeps its data and is stable once formatted
+    #[test]
+    fn test_round_trip_corpus() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/frontmatter/corpus");
+        let mut cases = 0;
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            if name.ends_with(".expected.md") {
+                continue;
+            }
+            let input = std::fs::read_to_string(&path).unwrap();
+            let expected = std::fs::read_to_string(path.with_extension("expected.md"))
+                .unwrap_or_else(|_| {
+                    panic!("{} has no .expected.md", name);
+                });
+
+            let formatted = format(&input);
+            assert_eq!(formatted, expected, "{}", name);
+            assert_eq!(
+                frontmatter_value(&formatted),
+                frontmatter_value(&input),
+                "{}",
+                name
+            );
+            assert_eq!(format(&formatted), formatted, "{} is not stable", name);
+            cases += 1;
+        }
+        assert!(cases >= 5);
+    }
+
+    #[test]
+    fn test_reorders_keys() {
+        let content = "---\ndescription: Test\nname: test-skill\n---\n# Content\n";
+        assert_eq!(
+            format(content),
+            "---\nname: test-skill\ndescription: Test\n---\n# Content\n"
+        );
+    }
+
+    #[test]
+    fn test_preserves_crlf() {
+        let content = "---\r\ndescription: Test\r\nname: test-skill\r\n---\r\n# Content\r\n";
+        assert_eq!(
+            format(content),
+            "---\r\nname: test-skill\r\ndescription: Test\r\n---\r\n# Content\r\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_body() {
+        let content = "---\nname: empty\ndescription:   Nothing here\n---\n";
+        assert_eq!(
+            format(content),
+            "---\nname: empty\ndescription: Nothing here\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_invalid_yaml_is_an_error() {
+        let content = "---\nname: bad\ndescription: Use when: things happen\n---\n";
+        assert!(format_frontmatter(content, Path::new("SKILL.md")).is_err());
+    }
+
+    #[test]
+    fn test_yaml_value() {
+        assert_eq!(yaml_value("Process PDF files", 0), "Process PDF files");
+        assert_eq!(yaml_value("Use when: asked", 0), "\"Use when: asked\"");
+        assert_eq!(yaml_value("C# #tips", 0), "\"C# #tips\"");
+        assert_eq!(yaml_value("true", 0), "\"true\"");
+        assert_eq!(yaml_value("1.0", 0), "\"1.0\"");
+        assert_eq!(yaml_value("", 0), "\"\"");
+        assert_eq!(yaml_value("- item", 0), "\"- item\"");
+        assert_eq!(yaml_value("one\ntwo", 2), "|-\n    one\n    two");
+        assert_eq!(yaml_value("one\n\ntwo\n", 0), "|\n  one\n\n  two");
+        assert_eq!(yaml_value(" indented\ntext", 0), "\" indented\\ntext\"");
+
+        for value in [
+            "a: b",
+            "x\ny\n\n",
+            "quote \"it\"",
+            "tab\there",
+            "#tag",
+            "ok",
+        ] {
+            let yaml = format!("key: {}\n", yaml_value(value, 0));
+            let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+            assert_eq!(parsed["key"].as_str(), Some(value), "{:?}", yaml);
+        }
+    }
+}
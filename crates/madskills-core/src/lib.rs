@@ -1,12 +1,20 @@
 //! Core library for madskills - skill discovery, parsing, and validation
 #![deny(unsafe_code)]
 
+pub mod baseline;
+pub mod config;
 pub mod discovery;
 pub mod error;
+pub mod fix;
+pub mod format;
+pub mod frontmatter;
 pub mod markdown;
 pub mod models;
 pub mod output;
 pub mod parser;
+pub mod rules;
+pub mod suppression;
+pub mod toc;
 pub mod validator;
 
 pub use error::{CoreError, CoreResult};
@@ -1,7 +1,166 @@
 //! Markdown linting integration (powered by rumdl library)
 
-use crate::error::CoreResult;
-use std::path::Path;
+use crate::error::{CoreError, CoreResult};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Markdown lint config files picked up automatically, in lookup order within
+/// a single directory
+pub const MARKDOWN_CONFIG_FILES: &[&str] = &[
+    ".rumdl.toml",
+    "rumdl.toml",
+    ".markdownlint.json",
+    ".markdownlint.jsonc",
+    ".markdownlint.yaml",
+    ".markdownlint.yml",
+];
+
+/// Markdown dialect the linter parses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkdownFlavor {
+    #[default]
+    Standard,
+    Mkdocs,
+    Mdx,
+    Quarto,
+}
+
+impl fmt::Display for MarkdownFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Standard => "standard",
+            Self::Mkdocs => "mkdocs",
+            Self::Mdx => "mdx",
+            Self::Quarto => "quarto",
+        })
+    }
+}
+
+impl From<MarkdownFlavor> for rumdl_lib::config::MarkdownFlavor {
+    fn from(flavor: MarkdownFlavor) -> Self {
+        match flavor {
+            MarkdownFlavor::Standard => Self::Standard,
+            MarkdownFlavor::Mkdocs => Self::MkDocs,
+            MarkdownFlavor::Mdx => Self::MDX,
+            MarkdownFlavor::Quarto => Self::Quarto,
+        }
+    }
+}
+
+/// Markdown lint settings, shared by linting and formatting
+///
+/// `enable`, `disable` and `flavor` are layered over whatever the config file
+/// sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownConfig {
+    /// rumdl (`.rumdl.toml`) or markdownlint (`.markdownlint.json`, `.yaml`)
+    /// config file
+    pub config_file: Option<PathBuf>,
+    /// Only run these rules (all rules when empty)
+    pub enable: Vec<String>,
+    /// Never run these rules
+    pub disable: Vec<String>,
+    /// Markdown flavor, overriding the config file's
+    pub flavor: Option<MarkdownFlavor>,
+}
+
+impl MarkdownConfig {
+    /// Use the first markdown lint config file found in `skills_base` or one
+    /// of its parents up to `root`, unless a config file is already set
+    pub fn with_discovered_file(mut self, skills_base: &Path, root: &Path) -> Self {
+        if self.config_file.is_none() {
+            self.config_file = find_markdown_config(skills_base, root);
+        }
+        self
+    }
+
+    /// Check that the config file loads, so errors surface before linting
+    pub fn check(&self) -> CoreResult<()> {
+        self.load().map(|_| ())
+    }
+
+    /// rumdl configuration: the config file, then our overrides
+    fn load(&self) -> CoreResult<rumdl_lib::config::Config> {
+        let mut config = match &self.config_file {
+            Some(path) => load_rumdl_config(path)?,
+            None => rumdl_lib::config::Config::default(),
+        };
+
+        let canonical = |names: &[String]| -> Vec<String> {
+            names
+                .iter()
+                .map(|n| rumdl_lib::config::resolve_rule_name(n))
+                .collect()
+        };
+        if !self.enable.is_empty() {
+            config.global.enable = canonical(&self.enable);
+        }
+        config.global.disable.extend(canonical(&self.disable));
+        if let Some(flavor) = self.flavor {
+            config.global.flavor = flavor.into();
+        }
+        Ok(config)
+    }
+}
+
+/// Canonical rule code for a markdown rule name or alias (`line-length` is
+/// `MD013`)
+pub fn parse_markdown_rule(name: &str) -> Result<String, String> {
+    rumdl_lib::config::resolve_rule_name_alias(name)
+        .map(str::to_string)
+        .ok_or_else(|| format!("unknown markdown rule '{}'", name))
+}
+
+/// First markdown lint config file in `start` or its parents, stopping at
+/// `root` when `start` lies inside it
+fn find_markdown_config(start: &Path, root: &Path) -> Option<PathBuf> {
+    let root = std::path::absolute(root).ok()?;
+    let start = std::path::absolute(start).ok()?;
+    let dirs: Vec<&Path> = if start.starts_with(&root) {
+        start
+            .ancestors()
+            .take_while(|d| d.starts_with(&root))
+            .collect()
+    } else {
+        vec![start.as_path(), root.as_path()]
+    };
+
+    dirs.into_iter().find_map(|dir| {
+        MARKDOWN_CONFIG_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Load a rumdl or markdownlint config file
+fn load_rumdl_config(path: &Path) -> CoreResult<rumdl_lib::config::Config> {
+    let error = |message: String| CoreError::Config {
+        path: path.to_path_buf(),
+        message,
+    };
+    if !path.is_file() {
+        return Err(error("file not found".into()));
+    }
+
+    let path_str = path.to_string_lossy();
+    let loaded = rumdl_lib::config::SourcedConfig::load_with_discovery(Some(&path_str), None, true)
+        .map_err(|e| error(e.to_string()))?;
+    let registry = rumdl_lib::config::RuleRegistry::from_rules(&rumdl_lib::rules::all_rules(
+        &rumdl_lib::config::Config::default(),
+    ));
+    let (config, _warnings) = loaded
+        .validate_into(&registry)
+        .map_err(|e| error(e.to_string()))?;
+    Ok(config)
+}
+
+/// Rules enabled by `config`
+fn enabled_rules(config: &rumdl_lib::config::Config) -> Vec<Box<dyn rumdl_lib::rule::Rule>> {
+    let rules = rumdl_lib::rules::all_rules(config);
+    rumdl_lib::rules::filter_rules(&rules, &config.global)
+}
 
 /// Severity level of a markdown violation
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,126 +178,133 @@ pub struct MarkdownViolation {
     pub message: String,
     pub line: usize,
     pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
     pub severity: Severity,
+    /// Whether rumdl can fix this automatically
+    pub fixable: bool,
 }
 
-/// Lint markdown file using rumdl library
-pub fn lint_markdown(path: &Path, _config: Option<&Path>) -> CoreResult<Vec<MarkdownViolation>> {
-    // Read the file content
-    let content = std::fs::read_to_string(path)?;
-
-    // Create default config
-    // TODO: Support custom config file from _config parameter
-    let config = rumdl_lib::config::Config::default();
-
-    // Get all rules
-    let rules = rumdl_lib::rules::all_rules(&config);
-
-    // Run the linter with standard markdown flavor
-    let result = rumdl_lib::lint(
-        &content,
-        &rules,
-        false, // verbose
-        rumdl_lib::config::MarkdownFlavor::Standard,
-        Some(&config),
-    );
-
-    // Convert rumdl violations to our MarkdownViolation type
-    match result {
-        Ok(warnings) => {
-            let violations = warnings
-                .iter()
-                .map(|w| MarkdownViolation {
-                    file: path.display().to_string(),
-                    rule: w.rule_name.clone().unwrap_or_else(|| "unknown".to_string()),
-                    message: w.message.clone(),
-                    line: w.line,
-                    column: w.column,
-                    severity: match w.severity {
-                        rumdl_lib::rule::Severity::Error => Severity::Error,
-                        rumdl_lib::rule::Severity::Warning => Severity::Warning,
-                        rumdl_lib::rule::Severity::Info => Severity::Info,
-                    },
-                })
-                .collect();
-            Ok(violations)
-        }
-        Err(e) => Err(crate::error::CoreError::ValidationFailed(format!(
-            "Markdown linting failed: {}",
-            e
-        ))),
-    }
+/// rumdl configuration and rules, loaded once and reused for every file
+pub struct MarkdownLinter {
+    config: rumdl_lib::config::Config,
+    rules: Vec<Box<dyn rumdl_lib::rule::Rule>>,
 }
 
-/// Format markdown file using rumdl library
-pub fn format_markdown(path: &Path, check_only: bool, _config: Option<&Path>) -> CoreResult<bool> {
-    // Read the file content
-    let content = std::fs::read_to_string(path)?;
-
-    // Create default config
-    // TODO: Support custom config file from _config parameter
-    let config = rumdl_lib::config::Config::default();
-
-    // Get all rules
-    let rules = rumdl_lib::rules::all_rules(&config);
-
-    // Lint to get violations with fixes
-    let result = rumdl_lib::lint(
-        &content,
-        &rules,
-        false, // verbose
-        rumdl_lib::config::MarkdownFlavor::Standard,
-        Some(&config),
-    );
-
-    match result {
-        Ok(warnings) => {
-            // Check if any warnings have fixes
-            let has_fixes = warnings.iter().any(|w| w.fix.is_some());
-
-            if !has_fixes {
-                return Ok(false);
-            }
+impl MarkdownLinter {
+    /// Load the config file and build the enabled rules
+    pub fn new(config: &MarkdownConfig) -> CoreResult<Self> {
+        let config = config.load()?;
+        let rules = enabled_rules(&config);
+        Ok(Self { config, rules })
+    }
 
-            if check_only {
-                // Just return that changes are needed
-                return Ok(true);
+    /// Lint markdown content read from `path`
+    pub fn lint(&self, path: &Path, content: &str) -> CoreResult<Vec<MarkdownViolation>> {
+        // Run the linter with the configured markdown flavor
+        let result = rumdl_lib::lint(
+            content,
+            &self.rules,
+            false, // verbose
+            self.config.markdown_flavor(),
+            Some(&self.config),
+        );
+
+        // Convert rumdl violations to our MarkdownViolation type
+        match result {
+            Ok(warnings) => {
+                let violations = warnings
+                    .iter()
+                    .map(|w| MarkdownViolation {
+                        file: path.display().to_string(),
+                        rule: w.rule_name.clone().unwrap_or_else(|| "unknown".to_string()),
+                        message: w.message.clone(),
+                        line: w.line,
+                        column: w.column,
+                        end_line: w.end_line,
+                        end_column: w.end_column,
+                        fixable: w.fix.is_some(),
+                        severity: match w.severity {
+                            rumdl_lib::rule::Severity::Error => Severity::Error,
+                            rumdl_lib::rule::Severity::Warning => Severity::Warning,
+                            rumdl_lib::rule::Severity::Info => Severity::Info,
+                        },
+                    })
+                    .collect();
+                Ok(violations)
             }
+            Err(e) => Err(crate::error::CoreError::ValidationFailed(format!(
+                "Markdown linting failed: {}",
+                e
+            ))),
+        }
+    }
 
-            // Apply fixes using rumdl's fix coordinator
-            let coordinator = rumdl_lib::fix_coordinator::FixCoordinator::new();
-            let mut fixed_content = content.clone();
+    /// Apply rumdl's fixes to markdown content, returning the fixed content
+    pub fn format(&self, content: &str) -> CoreResult<String> {
+        // Lint to get violations with fixes
+        let warnings = rumdl_lib::lint(
+            content,
+            &self.rules,
+            false, // verbose
+            self.config.markdown_flavor(),
+            Some(&self.config),
+        )
+        .map_err(|e| {
+            crate::error::CoreError::ValidationFailed(format!("Markdown linting failed: {}", e))
+        })?;
+
+        if !warnings.iter().any(|w| w.fix.is_some()) {
+            return Ok(content.to_string());
+        }
 
-            match coordinator.apply_fixes_iterative(
-                &rules,
+        // Apply fixes using rumdl's fix coordinator
+        let coordinator = rumdl_lib::fix_coordinator::FixCoordinator::new();
+        let mut fixed_content = content.to_string();
+        coordinator
+            .apply_fixes_iterative(
+                &self.rules,
                 &warnings,
                 &mut fixed_content,
-                &config,
+                &self.config,
                 100, // max iterations
-            ) {
-                Ok(_result) => {
-                    // Check if content actually changed
-                    let changed = fixed_content != content;
-
-                    if changed {
-                        // Write the fixed content back to the file
-                        std::fs::write(path, &fixed_content)?;
-                    }
-
-                    Ok(changed)
-                }
-                Err(e) => Err(crate::error::CoreError::ValidationFailed(format!(
+            )
+            .map_err(|e| {
+                crate::error::CoreError::ValidationFailed(format!(
                     "Failed to apply markdown fixes: {}",
                     e
-                ))),
-            }
-        }
-        Err(e) => Err(crate::error::CoreError::ValidationFailed(format!(
-            "Markdown linting failed: {}",
-            e
-        ))),
+                ))
+            })?;
+
+        Ok(fixed_content)
     }
 }
+
+/// Lint markdown file using rumdl library
+pub fn lint_markdown(path: &Path, config: &MarkdownConfig) -> CoreResult<Vec<MarkdownViolation>> {
+    let content = std::fs::read_to_string(path)?;
+    MarkdownLinter::new(config)?.lint(path, &content)
+}
+
+/// Format markdown file using rumdl library
+///
+/// Returns whether the content changed; with `check_only` the file is never
+/// written.
+pub fn format_markdown(path: &Path, check_only: bool, config: &MarkdownConfig) -> CoreResult<bool> {
+    let content = std::fs::read_to_string(path)?;
+    let formatted = format_markdown_str(&content, config)?;
+    let changed = formatted != content;
+    if changed && !check_only {
+        crate::fix::write_atomic(path, &formatted)?;
+    }
+    Ok(changed)
+}
+
+/// Apply rumdl's fixes to markdown content, returning the fixed content
+pub fn format_markdown_str(content: &str, config: &MarkdownConfig) -> CoreResult<String> {
+    MarkdownLinter::new(config)?.format(content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,7 +331,10 @@ mod tests {
             message: "Header levels should increment by one".to_string(),
             line: 5,
             column: 1,
+            end_line: 5,
+            end_column: 10,
             severity: Severity::Warning,
+            fixable: false,
         };
 
         assert_eq!(violation.file, "test.md");
@@ -183,7 +352,7 @@ mod tests {
         writeln!(file)?;
         writeln!(file, "This is a valid markdown file.")?;
 
-        let violations = lint_markdown(file.path(), None)?;
+        let violations = lint_markdown(file.path(), &MarkdownConfig::default())?;
         // Should not error - violations may or may not be present
         let _ = violations.len();
 
@@ -198,7 +367,7 @@ mod tests {
         writeln!(file, "###  Skipped Level")?; // MD001: skipped level
         writeln!(file, "Line with    trailing spaces  ")?; // MD009: trailing spaces
 
-        let violations = lint_markdown(file.path(), None)?;
+        let violations = lint_markdown(file.path(), &MarkdownConfig::default())?;
         // Should detect violations (exact count depends on rumdl rules)
         let _ = violations.len();
 
@@ -207,7 +376,10 @@ mod tests {
 
     #[test]
     fn test_lint_markdown_nonexistent_file() {
-        let result = lint_markdown(Path::new("/nonexistent/file.md"), None);
+        let result = lint_markdown(
+            Path::new("/nonexistent/file.md"),
+            &MarkdownConfig::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -218,7 +390,7 @@ mod tests {
         writeln!(file)?;
         writeln!(file, "No formatting issues here.")?;
 
-        let changed = format_markdown(file.path(), false, None)?;
+        let changed = format_markdown(file.path(), false, &MarkdownConfig::default())?;
         // Always returns a boolean (just exercise the code path)
         let _ = changed;
 
@@ -233,7 +405,7 @@ mod tests {
 
         // In check-only mode, file should not be modified
         let original_content = std::fs::read_to_string(file.path())?;
-        let _changed = format_markdown(file.path(), true, None)?;
+        let _changed = format_markdown(file.path(), true, &MarkdownConfig::default())?;
         let after_content = std::fs::read_to_string(file.path())?;
 
         assert_eq!(original_content, after_content);
@@ -241,9 +413,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_format_markdown_str() -> CoreResult<()> {
+        let content = "# Header\n\nTrailing spaces   \n";
+        let formatted = format_markdown_str(content, &MarkdownConfig::default())?;
+        assert_eq!(formatted, "# Header\n\nTrailing spaces\n");
+        assert_eq!(
+            format_markdown_str(&formatted, &MarkdownConfig::default())?,
+            formatted
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_format_markdown_nonexistent_file() {
-        let result = format_markdown(Path::new("/nonexistent/file.md"), false, None);
+        let result = format_markdown(
+            Path::new("/nonexistent/file.md"),
+            false,
+            &MarkdownConfig::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -264,7 +452,10 @@ mod tests {
             message: "Test message".to_string(),
             line: 1,
             column: 1,
+            end_line: 1,
+            end_column: 1,
             severity: Severity::Error,
+            fixable: true,
         };
 
         let cloned = violation.clone();
@@ -272,4 +463,61 @@ mod tests {
         assert_eq!(cloned.rule, violation.rule);
         assert_eq!(cloned.line, violation.line);
     }
+
+    #[test]
+    fn test_config_file_disables_rules() -> CoreResult<()> {
+        let dir = tempfile::TempDir::new()?;
+        let md = dir.path().join("doc.md");
+        std::fs::write(&md, "# Header\n\nTrailing spaces   \n")?;
+        let has_md009 = |config: &MarkdownConfig| -> CoreResult<bool> {
+            Ok(lint_markdown(&md, config)?
+                .iter()
+                .any(|v| v.rule == "MD009"))
+        };
+        assert!(has_md009(&MarkdownConfig::default())?);
+
+        let json = dir.path().join(".markdownlint.json");
+        std::fs::write(&json, r#"{"MD009": false}"#)?;
+        let config = MarkdownConfig::default().with_discovered_file(dir.path(), dir.path());
+        assert_eq!(config.config_file, Some(json));
+        assert!(!has_md009(&config)?);
+        assert_eq!(
+            format_markdown_str("Trailing   \n", &config)?,
+            "Trailing   \n"
+        );
+
+        let config = MarkdownConfig {
+            disable: vec!["no-trailing-spaces".to_string()],
+            ..Default::default()
+        };
+        assert!(!has_md009(&config)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_file_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let missing = MarkdownConfig {
+            config_file: Some(dir.path().join("missing.toml")),
+            ..Default::default()
+        };
+        let err = missing.check().unwrap_err();
+        assert!(matches!(err, CoreError::Config { .. }));
+        assert!(err.to_string().contains("file not found"));
+
+        let path = dir.path().join(".rumdl.toml");
+        std::fs::write(&path, "[global\n").unwrap();
+        let invalid = MarkdownConfig {
+            config_file: Some(path),
+            ..Default::default()
+        };
+        assert!(invalid.check().is_err());
+    }
+
+    #[test]
+    fn test_parse_markdown_rule() {
+        assert_eq!(parse_markdown_rule("md013"), Ok("MD013".to_string()));
+        assert_eq!(parse_markdown_rule("line-length"), Ok("MD013".to_string()));
+        assert!(parse_markdown_rule("MD999").is_err());
+    }
 }
@@ -1,7 +1,9 @@
 //! Core data models for Agent Skills
 
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Allowed frontmatter fields per AgentSkills spec
 pub const ALLOWED_FRONTMATTER_FIELDS: &[&str] = &[
@@ -22,6 +24,67 @@ pub struct Skill {
     pub skill_md_path: PathBuf,
     /// Parsed metadata from frontmatter
     pub metadata: SkillMetadata,
+    /// Skills directory the skill was found in (`None` when only an include
+    /// pattern matched it)
+    pub source: Option<SkillsRoot>,
+}
+
+/// Agent a skills directory belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Agent {
+    /// `.claude/skills`
+    Claude,
+    /// `.github/skills` (GitHub Copilot)
+    GitHub,
+    /// `.codex/skills`
+    Codex,
+}
+
+impl Agent {
+    /// Agent owning a skills directory, judged by the nearest `.claude`,
+    /// `.github` or `.codex` component of its path
+    pub fn from_skills_dir(path: &Path) -> Option<Self> {
+        path.components()
+            .rev()
+            .find_map(|component| match component.as_os_str().to_str()? {
+                ".claude" => Some(Self::Claude),
+                ".github" => Some(Self::GitHub),
+                ".codex" => Some(Self::Codex),
+                _ => None,
+            })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::GitHub => "github",
+            Self::Codex => "codex",
+        }
+    }
+}
+
+impl std::fmt::Display for Agent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A directory skills are discovered in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillsRoot {
+    /// The skills directory
+    pub path: PathBuf,
+    /// Agent the directory belongs to, when recognizable
+    pub agent: Option<Agent>,
+}
+
+impl SkillsRoot {
+    /// A skills root, with its agent inferred from the path
+    pub fn new(path: PathBuf) -> Self {
+        let agent = Agent::from_skills_dir(&path);
+        Self { path, agent }
+    }
 }
 
 /// Parsed YAML frontmatter from SKILL.md
@@ -50,6 +113,76 @@ pub struct SkillMetadata {
     /// Track all fields that were present in the frontmatter (for validation)
     #[serde(skip)]
     pub all_fields: HashSet<String>,
+    /// Where each field appears in SKILL.md (for diagnostics)
+    #[serde(skip)]
+    pub spans: FrontmatterSpans,
+}
+
+/// Position of a frontmatter key and its value in SKILL.md
+///
+/// Lines and columns are 1-indexed and counted from the top of the file;
+/// columns count characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSpan {
+    /// Line of the key
+    pub line: usize,
+    /// Column of the key
+    pub column: usize,
+    /// Line where the value starts
+    pub value_line: usize,
+    /// Column where the value starts
+    pub value_column: usize,
+    /// Last line of the value (block scalars and nested maps span several)
+    pub end_line: usize,
+    /// Column just past the end of the value
+    pub end_column: usize,
+    /// Byte range of the key and value within the file
+    pub range: Range<usize>,
+}
+
+impl FieldSpan {
+    /// Location of the value, for diagnostics about its content
+    pub fn value_location(&self, file: &Path) -> SourceLocation {
+        SourceLocation {
+            file: file.to_path_buf(),
+            line: self.value_line,
+            column: self.value_column,
+            end_line: Some(self.end_line),
+            end_column: Some(self.end_column),
+        }
+    }
+
+    /// Location of the key, for diagnostics about the field itself
+    pub fn key_location(&self, file: &Path) -> SourceLocation {
+        SourceLocation {
+            file: file.to_path_buf(),
+            line: self.line,
+            column: self.column,
+            end_line: Some(self.end_line),
+            end_column: Some(self.end_column),
+        }
+    }
+}
+
+/// Spans of the frontmatter fields of a SKILL.md
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrontmatterSpans {
+    /// Top-level keys
+    pub fields: HashMap<String, FieldSpan>,
+    /// Keys nested under `metadata`
+    pub metadata: HashMap<String, FieldSpan>,
+}
+
+impl FrontmatterSpans {
+    /// Span of a top-level field
+    pub fn field(&self, name: &str) -> Option<&FieldSpan> {
+        self.fields.get(name)
+    }
+
+    /// Span of a key nested under `metadata`
+    pub fn metadata_key(&self, key: &str) -> Option<&FieldSpan> {
+        self.metadata.get(key)
+    }
 }
 
 /// Configuration for skill discovery
@@ -98,6 +231,8 @@ pub struct ValidationWarning {
     pub message: String,
     /// Optional source location
     pub location: Option<SourceLocation>,
+    /// Whether `madskills fmt` can fix this automatically
+    pub fixable: bool,
 }
 
 /// Location in a source file
@@ -109,6 +244,10 @@ pub struct SourceLocation {
     pub line: usize,
     /// Column number (1-indexed)
     pub column: usize,
+    /// Last line of the span, when known
+    pub end_line: Option<usize>,
+    /// Column just past the end of the span, when known
+    pub end_column: Option<usize>,
 }
 
 /// Types of validation errors
@@ -130,6 +269,45 @@ pub enum ValidationErrorKind {
     MarkdownLintError,
 }
 
+impl ValidationErrorKind {
+    /// All error kinds
+    pub const ALL: [Self; 7] = [
+        Self::MissingSkillMd,
+        Self::FrontmatterParseError,
+        Self::MissingRequiredField,
+        Self::InvalidFieldValue,
+        Self::NameDirectoryMismatch,
+        Self::DuplicateSkillName,
+        Self::MarkdownLintError,
+    ];
+
+    /// Stable identifier for this kind of error
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingSkillMd => "missing-skill-md",
+            Self::FrontmatterParseError => "frontmatter-parse-error",
+            Self::MissingRequiredField => "missing-required-field",
+            Self::InvalidFieldValue => "invalid-field-value",
+            Self::NameDirectoryMismatch => "name-directory-mismatch",
+            Self::DuplicateSkillName => "duplicate-skill-name",
+            Self::MarkdownLintError => "markdown-lint-error",
+        }
+    }
+
+    /// Short description of the check
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::MissingSkillMd => "Skill directory must contain a SKILL.md file",
+            Self::FrontmatterParseError => "SKILL.md frontmatter must be valid YAML",
+            Self::MissingRequiredField => "Frontmatter must include the required fields",
+            Self::InvalidFieldValue => "Frontmatter fields must satisfy the AgentSkills spec",
+            Self::NameDirectoryMismatch => "Skill name must match its directory name",
+            Self::DuplicateSkillName => "Skill names must be unique",
+            Self::MarkdownLintError => "Markdown must pass linting",
+        }
+    }
+}
+
 /// Types of validation warnings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValidationWarningKind {
@@ -139,6 +317,38 @@ pub enum ValidationWarningKind {
     MissingOptionalFile,
     /// Deprecated field used
     DeprecatedField,
+    /// Suppression comment that silenced nothing
+    UnusedSuppression,
+}
+
+impl ValidationWarningKind {
+    /// All warning kinds
+    pub const ALL: [Self; 4] = [
+        Self::MarkdownLintWarning,
+        Self::MissingOptionalFile,
+        Self::DeprecatedField,
+        Self::UnusedSuppression,
+    ];
+
+    /// Stable identifier for this kind of warning
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MarkdownLintWarning => "markdown-lint-warning",
+            Self::MissingOptionalFile => "missing-optional-file",
+            Self::DeprecatedField => "deprecated-field",
+            Self::UnusedSuppression => "unused-suppression",
+        }
+    }
+
+    /// Short description of the check
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::MarkdownLintWarning => "Markdown style issue",
+            Self::MissingOptionalFile => "Referenced optional file is missing",
+            Self::DeprecatedField => "Frontmatter field is deprecated",
+            Self::UnusedSuppression => "Suppression comment silences no findings",
+        }
+    }
 }
 
 impl ValidationResult {
@@ -182,8 +392,41 @@ impl ValidationResult {
     }
 }
 
-/// Best practice rule codes (AS001-AS020)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+/// Base URL of the best practice rule documentation
+pub const RULE_DOCS_URL: &str =
+    "https://github.com/madskilling/madskills/blob/main/crates/madskills-core/docs/rules";
+
+/// Documentation of the built-in rules, in code order
+const RULE_DOCS: [&str; 24] = [
+    include_str!("../docs/rules/AS001.md"),
+    include_str!("../docs/rules/AS002.md"),
+    include_str!("../docs/rules/AS003.md"),
+    include_str!("../docs/rules/AS004.md"),
+    include_str!("../docs/rules/AS005.md"),
+    include_str!("../docs/rules/AS006.md"),
+    include_str!("../docs/rules/AS007.md"),
+    include_str!("../docs/rules/AS008.md"),
+    include_str!("../docs/rules/AS009.md"),
+    include_str!("../docs/rules/AS010.md"),
+    include_str!("../docs/rules/AS011.md"),
+    include_str!("../docs/rules/AS012.md"),
+    include_str!("../docs/rules/AS013.md"),
+    include_str!("../docs/rules/AS014.md"),
+    include_str!("../docs/rules/AS015.md"),
+    include_str!("../docs/rules/AS016.md"),
+    include_str!("../docs/rules/AS017.md"),
+    include_str!("../docs/rules/AS018.md"),
+    include_str!("../docs/rules/AS019.md"),
+    include_str!("../docs/rules/AS020.md"),
+    include_str!("../docs/rules/AS021.md"),
+    include_str!("../docs/rules/AS022.md"),
+    include_str!("../docs/rules/AS023.md"),
+    include_str!("../docs/rules/AS024.md"),
+];
+
+/// Best practice rule codes: the built-in AS001-AS024, plus codes of rules
+/// registered from outside madskills
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BestPracticeCode {
     AS001,
     AS002,
@@ -205,10 +448,88 @@ pub enum BestPracticeCode {
     AS018,
     AS019,
     AS020,
+    AS021,
+    AS022,
+    AS023,
+    AS024,
+    /// A rule defined outside madskills (see [`BestPracticeCode::custom`])
+    Custom(Arc<CustomCode>),
+}
+
+/// Identity and documentation of a rule defined outside madskills
+///
+/// Codes compare by `id` alone.
+#[derive(Debug, Clone, Default)]
+pub struct CustomCode {
+    /// Code findings are reported under, e.g. `ACME001`
+    pub id: String,
+    /// One-line summary of what the rule checks
+    pub description: String,
+    /// One-line suggestion for fixing a violation (may be empty)
+    pub help: String,
+    /// Link to the rule's documentation
+    pub docs_url: Option<String>,
+}
+
+impl PartialEq for CustomCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomCode {}
+
+impl std::hash::Hash for CustomCode {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
 }
 
 impl BestPracticeCode {
-    pub fn as_str(&self) -> &'static str {
+    /// All best practice rule codes, in order
+    pub const ALL: [Self; 24] = [
+        Self::AS001,
+        Self::AS002,
+        Self::AS003,
+        Self::AS004,
+        Self::AS005,
+        Self::AS006,
+        Self::AS007,
+        Self::AS008,
+        Self::AS009,
+        Self::AS010,
+        Self::AS011,
+        Self::AS012,
+        Self::AS013,
+        Self::AS014,
+        Self::AS015,
+        Self::AS016,
+        Self::AS017,
+        Self::AS018,
+        Self::AS019,
+        Self::AS020,
+        Self::AS021,
+        Self::AS022,
+        Self::AS023,
+        Self::AS024,
+    ];
+
+    /// Code for a rule defined outside madskills
+    ///
+    /// Custom codes are only known to the
+    /// [`RuleRegistry`](crate::rules::RuleRegistry) the rule is registered
+    /// in; look them up there with
+    /// [`RuleRegistry::code`](crate::rules::RuleRegistry::code).
+    pub fn custom(code: CustomCode) -> Self {
+        Self::Custom(Arc::new(code))
+    }
+
+    /// Whether this is one of the built-in AS rules
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, Self::Custom(_))
+    }
+
+    pub fn as_str(&self) -> &str {
         match self {
             Self::AS001 => "AS001",
             Self::AS002 => "AS002",
@@ -230,10 +551,35 @@ impl BestPracticeCode {
             Self::AS018 => "AS018",
             Self::AS019 => "AS019",
             Self::AS020 => "AS020",
+            Self::AS021 => "AS021",
+            Self::AS022 => "AS022",
+            Self::AS023 => "AS023",
+            Self::AS024 => "AS024",
+            Self::Custom(code) => &code.id,
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    /// Link to the rule's documentation, if it has any
+    pub fn help_uri(&self) -> Option<String> {
+        match self {
+            Self::Custom(code) => code.docs_url.clone(),
+            _ => Some(format!("{}/{}.md", RULE_DOCS_URL, self.as_str())),
+        }
+    }
+
+    /// The rule's documentation (rationale, examples, remediation) as
+    /// markdown; built into the binary for the AS rules
+    pub fn docs(&self) -> Option<&'static str> {
+        let index = Self::ALL.iter().position(|code| code == self)?;
+        Some(RULE_DOCS[index])
+    }
+
+    /// Whether the rule can attach automatic fixes to its findings
+    pub fn fixable(&self) -> bool {
+        matches!(self, Self::AS005 | Self::AS008 | Self::AS017 | Self::AS020)
+    }
+
+    pub fn description(&self) -> &str {
         match self {
             Self::AS001 => "Name must be max 64 chars, lowercase/numbers/hyphens only",
             Self::AS002 => "Description must be non-empty, max 1024 chars, no XML tags",
@@ -255,10 +601,74 @@ impl BestPracticeCode {
             Self::AS018 => "Avoid undocumented magic constants",
             Self::AS019 => "Workflows should use numbered steps/checkboxes",
             Self::AS020 => "Table of contents must be complete (match headers)",
+            Self::AS021 => "Relative links must point to existing files",
+            Self::AS022 => "Link anchors must match a heading in the target document",
+            Self::AS023 => "Images must point to existing files",
+            Self::AS024 => "Reference-style links must have a definition",
+            Self::Custom(code) => &code.description,
+        }
+    }
+
+    /// One-line suggestion for fixing a violation
+    pub fn help(&self) -> &str {
+        match self {
+            Self::AS001 => "use only lowercase letters, numbers and hyphens, up to 64 characters",
+            Self::AS002 => "write a plain-text description of at most 1024 characters",
+            Self::AS003 => "describe what the skill does, e.g. 'Extracts tables from PDFs'",
+            Self::AS004 => "move detailed material into reference files linked from SKILL.md",
+            Self::AS005 => "replace backslashes with forward slashes, e.g. 'scripts/helper.py'",
+            Self::AS006 => "link the nested file directly from SKILL.md instead",
+            Self::AS007 => "rename the file after its content, e.g. 'form-fields.md'",
+            Self::AS008 => "add a '## Contents' section linking to each heading",
+            Self::AS009 => "prefix the tool with its server name, e.g. 'GitHub:create_issue'",
+            Self::AS010 => {
+                "describe the change without a date, or move it to an 'old patterns' section"
+            }
+            Self::AS011 => "add a '## Template' or '## Example Output' section",
+            Self::AS012 => "pick one term and use it throughout",
+            Self::AS013 => "add a '## Dependencies' section listing required packages",
+            Self::AS014 => "say when to use the skill, e.g. 'Use when working with PDF files'",
+            Self::AS015 => "name the skill after its activity, e.g. 'processing-pdfs'",
+            Self::AS016 => "remove 'anthropic' and 'claude' from the name",
+            Self::AS017 => "handle failures explicitly and exit with a non-zero status",
+            Self::AS018 => "add a comment explaining why the value was chosen",
+            Self::AS019 => "number the steps (1. 2. 3.) or use checkboxes (- [ ])",
+            Self::AS020 => "add a table of contents entry for each '##' heading",
+            Self::AS021 => "fix the path, or restore the file the link points to",
+            Self::AS022 => "link to the slug of an existing heading, e.g. '#quick-start'",
+            Self::AS023 => "fix the image path, or add the image to the skill",
+            Self::AS024 => "add a '[label]: target' definition, or fix the label",
+            Self::Custom(code) => &code.help,
         }
     }
 }
 
+/// Built-in codes parse case-insensitively; custom codes are resolved by
+/// the registry that holds them
+impl std::str::FromStr for BestPracticeCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|code| code.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown rule code '{}'", s))
+    }
+}
+
+impl serde::Serialize for BestPracticeCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BestPracticeCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        id.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// Severity level for violations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Severity {
@@ -267,13 +677,91 @@ pub enum Severity {
     Error,
 }
 
+/// Configured level for a best practice rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// Rule is disabled
+    Off,
+    /// Report as informational (never fails the run)
+    Info,
+    /// Report as a warning (an error under `--strict`)
+    #[serde(alias = "warn")]
+    Warning,
+    /// Report as an error
+    Error,
+}
+
+impl RuleLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    /// Effective severity for this level, or `None` when the rule is off
+    ///
+    /// Strict mode promotes warnings to errors; info stays info.
+    pub fn severity(&self, strict: bool) -> Option<Severity> {
+        match self {
+            Self::Off => None,
+            Self::Info => Some(Severity::Info),
+            Self::Warning if strict => Some(Severity::Error),
+            Self::Warning => Some(Severity::Warning),
+            Self::Error => Some(Severity::Error),
+        }
+    }
+}
+
 /// Location of a best practice violation
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum ViolationLocation {
-    Frontmatter { field: String },
-    File { path: PathBuf, line: Option<usize> },
-    Script { path: PathBuf, line: Option<usize> },
-    SkillBody { line: usize },
+    Frontmatter {
+        field: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        column: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        end_line: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        end_column: Option<usize>,
+    },
+    File {
+        path: PathBuf,
+        line: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        column: Option<usize>,
+    },
+    Script {
+        path: PathBuf,
+        line: Option<usize>,
+    },
+    SkillBody {
+        line: usize,
+    },
+}
+
+impl ViolationLocation {
+    /// Location of a frontmatter field's value, using its recorded span when known
+    ///
+    /// `metadata.<key>` names a key nested under `metadata`.
+    pub fn frontmatter(metadata: &SkillMetadata, field: &str) -> Self {
+        let span = match field.strip_prefix("metadata.") {
+            Some(key) => metadata.spans.metadata_key(key),
+            None => metadata.spans.field(field),
+        };
+        Self::Frontmatter {
+            field: field.to_string(),
+            line: span.map(|s| s.value_line),
+            column: span.map(|s| s.value_column),
+            end_line: span.map(|s| s.end_line),
+            end_column: span.map(|s| s.end_column),
+        }
+    }
 }
 
 /// A best practice violation
@@ -283,6 +771,72 @@ pub struct BestPracticeViolation {
     pub severity: Severity,
     pub message: String,
     pub location: Option<ViolationLocation>,
+    /// Automatic fix, when the problem is mechanical
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+impl BestPracticeViolation {
+    /// Attach an automatic fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Replace a byte range of a file with new text
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// Insert text at a byte offset
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        Self {
+            range: at..at,
+            replacement: text.into(),
+        }
+    }
+}
+
+/// Whether a fix is guaranteed to keep the skill's meaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Applicability {
+    /// Applied by `lint --fix`
+    Safe,
+    /// May change behavior; only applied with `--unsafe-fixes`
+    Unsafe,
+}
+
+/// An automatic fix: edits to one file that are applied together
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Fix {
+    pub path: PathBuf,
+    pub edits: Vec<TextEdit>,
+    pub applicability: Applicability,
+}
+
+impl Fix {
+    /// A fix that is always safe to apply
+    pub fn safe(path: impl Into<PathBuf>, edits: Vec<TextEdit>) -> Self {
+        Self {
+            path: path.into(),
+            edits,
+            applicability: Applicability::Safe,
+        }
+    }
+
+    /// A fix that may change behavior
+    pub fn unsafe_edit(path: impl Into<PathBuf>, edits: Vec<TextEdit>) -> Self {
+        Self {
+            path: path.into(),
+            edits,
+            applicability: Applicability::Unsafe,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -321,6 +875,7 @@ mod tests {
                 severity: Severity::Error,
                 message: "test".to_string(),
                 location: None,
+                fix: None,
             });
         assert!(!result_with_bp_error.is_valid());
     }
@@ -335,6 +890,7 @@ mod tests {
             kind: ValidationWarningKind::MissingOptionalFile,
             message: "test warning".to_string(),
             location: None,
+            fixable: false,
         });
         assert!(result_with_warning.has_warnings());
     }
@@ -349,6 +905,7 @@ mod tests {
             severity: Severity::Error,
             message: "error violation".to_string(),
             location: None,
+            fix: None,
         });
         assert!(result.has_bp_errors());
 
@@ -357,6 +914,7 @@ mod tests {
             severity: Severity::Warning,
             message: "warning violation".to_string(),
             location: None,
+            fix: None,
         });
         assert!(result.has_bp_errors());
     }
@@ -371,6 +929,7 @@ mod tests {
             severity: Severity::Warning,
             message: "warning violation".to_string(),
             location: None,
+            fix: None,
         });
         assert!(result.has_bp_warnings());
 
@@ -379,6 +938,7 @@ mod tests {
             severity: Severity::Error,
             message: "error violation".to_string(),
             location: None,
+            fix: None,
         });
         assert!(result.has_bp_warnings());
     }
@@ -396,6 +956,7 @@ mod tests {
                 severity: Severity::Info,
                 message: "info violation".to_string(),
                 location: None,
+                fix: None,
             });
         assert!(result_with_violation.has_bp_violations());
     }
@@ -442,6 +1003,10 @@ mod tests {
             BestPracticeCode::AS018,
             BestPracticeCode::AS019,
             BestPracticeCode::AS020,
+            BestPracticeCode::AS021,
+            BestPracticeCode::AS022,
+            BestPracticeCode::AS023,
+            BestPracticeCode::AS024,
         ];
 
         for code in codes {
@@ -456,6 +1021,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_best_practice_code_help() {
+        for code in BestPracticeCode::ALL {
+            assert!(
+                !code.help().is_empty(),
+                "{} should have help",
+                code.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_best_practice_code_docs() {
+        for code in BestPracticeCode::ALL {
+            let docs = code.docs().unwrap();
+            assert!(
+                docs.starts_with(&format!("# {}:", code.as_str())),
+                "{} docs should start with its title",
+                code.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn test_best_practice_code_from_str() {
+        assert_eq!(
+            "AS015".parse::<BestPracticeCode>(),
+            Ok(BestPracticeCode::AS015)
+        );
+        assert_eq!(
+            "as004".parse::<BestPracticeCode>(),
+            Ok(BestPracticeCode::AS004)
+        );
+        assert!("AS999".parse::<BestPracticeCode>().is_err());
+        assert!(serde_json::from_str::<BestPracticeCode>("\"AS0O5\"").is_err());
+
+        for code in BestPracticeCode::ALL {
+            assert_eq!(code.as_str().parse::<BestPracticeCode>(), Ok(code));
+        }
+    }
+
+    #[test]
+    fn test_rule_level_severity() {
+        assert_eq!(RuleLevel::Off.severity(false), None);
+        assert_eq!(RuleLevel::Info.severity(true), Some(Severity::Info));
+        assert_eq!(RuleLevel::Warning.severity(false), Some(Severity::Warning));
+        assert_eq!(RuleLevel::Warning.severity(true), Some(Severity::Error));
+        assert_eq!(RuleLevel::Error.severity(false), Some(Severity::Error));
+    }
+
     #[test]
     fn test_skill_metadata_deserialization() {
         let yaml = r#"
@@ -499,6 +1114,8 @@ description: Minimal test skill
                 file: PathBuf::from("/test/SKILL.md"),
                 line: 1,
                 column: 1,
+                end_line: None,
+                end_column: None,
             }),
         };
 
@@ -516,7 +1133,10 @@ description: Minimal test skill
                 file: PathBuf::from("/test/SKILL.md"),
                 line: 42,
                 column: 100,
+                end_line: None,
+                end_column: None,
             }),
+            fixable: true,
         };
 
         assert_eq!(warning.kind, ValidationWarningKind::MarkdownLintWarning);
@@ -528,6 +1148,16 @@ description: Minimal test skill
         }
     }
 
+    #[test]
+    fn test_agent_from_skills_dir() {
+        let agent = |p: &str| Agent::from_skills_dir(Path::new(p));
+        assert_eq!(agent("/repo/.claude/skills"), Some(Agent::Claude));
+        assert_eq!(agent(".github/skills/"), Some(Agent::GitHub));
+        assert_eq!(agent("/home/me/.codex/skills"), Some(Agent::Codex));
+        assert_eq!(agent("/repo/.github/.claude/skills"), Some(Agent::Claude));
+        assert_eq!(agent("./skills"), None);
+    }
+
     #[test]
     fn test_discovery_config_construction() {
         let config = DiscoveryConfig {
@@ -546,10 +1176,41 @@ description: Minimal test skill
         assert_eq!(config.exclude_patterns.len(), 1);
     }
 
+    #[test]
+    fn test_frontmatter_location_uses_spans() {
+        let content =
+            "---\nname: demo\ndescription: Does things\nmetadata:\n  owner: team-a\n---\n";
+        let metadata = crate::parser::parse_frontmatter(content, Path::new("SKILL.md")).unwrap();
+
+        let ViolationLocation::Frontmatter {
+            line,
+            column,
+            end_line,
+            end_column,
+            ..
+        } = ViolationLocation::frontmatter(&metadata, "description")
+        else {
+            panic!("expected a frontmatter location");
+        };
+        assert_eq!((line, column), (Some(3), Some(14)));
+        assert_eq!((end_line, end_column), (Some(3), Some(25)));
+
+        let ViolationLocation::Frontmatter { line, column, .. } =
+            ViolationLocation::frontmatter(&metadata, "metadata.owner")
+        else {
+            panic!("expected a frontmatter location");
+        };
+        assert_eq!((line, column), (Some(5), Some(10)));
+    }
+
     #[test]
     fn test_violation_location_variants() {
         let frontmatter_loc = ViolationLocation::Frontmatter {
             field: "name".to_string(),
+            line: Some(2),
+            column: Some(7),
+            end_line: Some(2),
+            end_column: Some(12),
         };
         assert!(matches!(
             frontmatter_loc,
@@ -559,6 +1220,7 @@ description: Minimal test skill
         let file_loc = ViolationLocation::File {
             path: PathBuf::from("/test.md"),
             line: Some(10),
+            column: Some(4),
         };
         assert!(matches!(file_loc, ViolationLocation::File { .. }));
 
@@ -603,6 +1265,7 @@ description: Minimal test skill
                     assert_eq!(kind1, kind2);
                 } else {
                     assert_ne!(kind1, kind2);
+                    assert_ne!(kind1.as_str(), kind2.as_str());
                 }
             }
         }
@@ -614,6 +1277,7 @@ description: Minimal test skill
             ValidationWarningKind::MarkdownLintWarning,
             ValidationWarningKind::MissingOptionalFile,
             ValidationWarningKind::DeprecatedField,
+            ValidationWarningKind::UnusedSuppression,
         ];
 
         // Test that they're all different
@@ -623,6 +1287,7 @@ description: Minimal test skill
                     assert_eq!(kind1, kind2);
                 } else {
                     assert_ne!(kind1, kind2);
+                    assert_ne!(kind1.as_str(), kind2.as_str());
                 }
             }
         }
@@ -636,7 +1301,12 @@ description: Minimal test skill
             message: "Name contains uppercase".to_string(),
             location: Some(ViolationLocation::Frontmatter {
                 field: "name".to_string(),
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
             }),
+            fix: None,
         };
 
         // Test serialization/deserialization round-trip
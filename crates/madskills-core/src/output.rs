@@ -1,7 +1,24 @@
 //! Output formatting for validation results
 
-use crate::models::ValidationResult;
-use serde::Serialize;
+mod checkstyle;
+mod github;
+mod json;
+mod junit;
+mod sarif;
+mod text;
+
+pub use json::{
+    DiagnosticKind, DiagnosticSeverity, JSON_SCHEMA_VERSION, JsonDiagnostic, JsonReport, JsonSkill,
+    JsonSummary, JsonTool, json_schema,
+};
+
+use crate::models::{
+    BestPracticeCode, RuleLevel, Severity, ValidationErrorKind, ValidationResult,
+    ValidationWarningKind, ViolationLocation,
+};
+use crate::rules::RuleRegistry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Output format options
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,6 +27,14 @@ pub enum OutputFormat {
     Text,
     /// Machine-readable JSON output
     Json,
+    /// SARIF 2.1.0 log for code scanning tools
+    Sarif,
+    /// GitHub Actions workflow commands (inline annotations)
+    Github,
+    /// JUnit XML (one test suite per skill)
+    Junit,
+    /// Checkstyle XML (one entry per affected file)
+    Checkstyle,
 }
 
 /// Output formatter
@@ -18,155 +43,235 @@ pub struct OutputFormatter {
     pub format: OutputFormat,
     /// Use color in output
     pub use_color: bool,
+    /// Root that report paths are made relative to
+    pub root: Option<PathBuf>,
+    /// List skills without findings in the text summary
+    pub verbose: bool,
+    /// Default level of each rule, for reports that describe the rules
+    pub default_levels: HashMap<BestPracticeCode, RuleLevel>,
 }
 
 impl OutputFormatter {
     /// Create a new output formatter
     pub fn new(format: OutputFormat, use_color: bool) -> Self {
-        Self { format, use_color }
+        Self {
+            format,
+            use_color,
+            root: None,
+            verbose: false,
+            default_levels: HashMap::new(),
+        }
+    }
+
+    /// Include skills without findings in the text summary
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Report file paths relative to `root`
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Describe rules with the default levels of the rules in `registry`
+    ///
+    /// Rules missing from the registry are described as warnings.
+    pub fn with_registry(mut self, registry: &RuleRegistry) -> Self {
+        self.default_levels = registry
+            .iter()
+            .map(|(code, rule)| (code.clone(), rule.default_level()))
+            .collect();
+        self
     }
 
     /// Format validation results
     pub fn format_validation_results(&self, results: &[ValidationResult]) -> String {
         match self.format {
-            OutputFormat::Text => self.format_text(results),
+            OutputFormat::Text => text::format_text(results, self.use_color, self.verbose),
             OutputFormat::Json => self.format_json(results),
+            OutputFormat::Sarif => {
+                sarif::format_sarif(results, self.root.as_deref(), &self.default_levels)
+            }
+            OutputFormat::Github => github::format_github(results),
+            OutputFormat::Junit => junit::format_junit(results),
+            OutputFormat::Checkstyle => checkstyle::format_checkstyle(results),
         }
     }
 
-    /// Format as human-readable text
-    fn format_text(&self, results: &[ValidationResult]) -> String {
-        let mut output = String::new();
-        let mut total_errors = 0;
-        let mut total_warnings = 0;
-        let mut total_bp_violations = 0;
-
-        for result in results {
-            if result.errors.is_empty()
-                && result.warnings.is_empty()
-                && result.best_practice_violations.is_empty()
-            {
-                continue;
-            }
+    /// Format as a versioned JSON report
+    fn format_json(&self, results: &[ValidationResult]) -> String {
+        let report = JsonReport::new(results, self.root.as_deref());
+        serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".into())
+    }
+}
 
-            output.push_str(&format!("\n{}\n", result.skill_path.display()));
+/// A single finding flattened out of a validation result
+pub(crate) struct Finding<'a> {
+    /// Rule code (`AS010`) or spec check kind (`invalid-field-value`)
+    pub rule_id: &'a str,
+    /// Best practice rule, for findings that come from one
+    pub code: Option<&'a BestPracticeCode>,
+    pub kind: DiagnosticKind,
+    pub severity: Severity,
+    pub message: &'a str,
+    /// File the finding points at, when known
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub end_line: Option<usize>,
+    pub end_column: Option<usize>,
+    pub fixable: bool,
+}
 
-            // Spec errors
-            for error in &result.errors {
-                output.push_str(&format!("  [ERROR] {}\n", error.message));
-                total_errors += 1;
-            }
+/// Flatten spec errors, warnings and best practice violations into findings
+pub(crate) fn findings(results: &[ValidationResult]) -> Vec<Finding<'_>> {
+    let mut findings = Vec::new();
 
-            // Spec warnings
-            for warning in &result.warnings {
-                output.push_str(&format!("  [WARN]  {}\n", warning.message));
-                total_warnings += 1;
-            }
+    for result in results {
+        let skill_path = result.skill_path.as_path();
+        let skill_md = || {
+            let path = skill_path.join("SKILL.md");
+            path.is_file().then_some(path)
+        };
 
-            // Best practice violations
-            for violation in &result.best_practice_violations {
-                let icon = match violation.severity {
-                    crate::models::Severity::Error => "[BP-ERROR]",
-                    crate::models::Severity::Warning => "[BP-WARN] ",
-                    crate::models::Severity::Info => "[BP-INFO] ",
-                };
-
-                let location = self.format_violation_location(&violation.location);
-                output.push_str(&format!(
-                    "  {} [{}]{} {}\n",
-                    icon,
-                    violation.code.as_str(),
-                    location,
-                    violation.message
-                ));
-                total_bp_violations += 1;
-
-                if violation.severity == crate::models::Severity::Error {
-                    total_errors += 1;
-                } else if violation.severity == crate::models::Severity::Warning {
-                    total_warnings += 1;
-                }
-            }
+        for error in &result.errors {
+            let location = error.location.as_ref();
+            findings.push(Finding {
+                rule_id: error.kind.as_str(),
+                code: None,
+                kind: match error.kind {
+                    ValidationErrorKind::MarkdownLintError => DiagnosticKind::Markdown,
+                    _ => DiagnosticKind::Spec,
+                },
+                severity: Severity::Error,
+                message: &error.message,
+                file: location.map(|l| l.file.clone()).or_else(skill_md),
+                line: location.map(|l| l.line),
+                column: location.map(|l| l.column),
+                end_line: location.and_then(|l| l.end_line),
+                end_column: location.and_then(|l| l.end_column),
+                fixable: false,
+            });
         }
 
-        if total_errors > 0 || total_warnings > 0 {
-            output.push('\n');
-            output.push_str(&format!(
-                "Found {} error(s), {} warning(s)",
-                total_errors, total_warnings
-            ));
-            if total_bp_violations > 0 {
-                output.push_str(&format!(" ({} best practice)", total_bp_violations));
-            }
-            output.push('\n');
+        for warning in &result.warnings {
+            let location = warning.location.as_ref();
+            findings.push(Finding {
+                rule_id: warning.kind.as_str(),
+                code: None,
+                kind: match warning.kind {
+                    ValidationWarningKind::MarkdownLintWarning => DiagnosticKind::Markdown,
+                    ValidationWarningKind::UnusedSuppression => DiagnosticKind::Suppression,
+                    _ => DiagnosticKind::Spec,
+                },
+                severity: Severity::Warning,
+                message: &warning.message,
+                file: location.map(|l| l.file.clone()).or_else(skill_md),
+                line: location.map(|l| l.line),
+                column: location.map(|l| l.column),
+                end_line: location.and_then(|l| l.end_line),
+                end_column: location.and_then(|l| l.end_column),
+                fixable: warning.fixable,
+            });
         }
 
-        output
-    }
-
-    /// Format violation location for display
-    fn format_violation_location(
-        &self,
-        location: &Option<crate::models::ViolationLocation>,
-    ) -> String {
-        use crate::models::ViolationLocation;
-
-        match location {
-            Some(ViolationLocation::Frontmatter { field }) => format!(" [{}]", field),
-            Some(ViolationLocation::File { path, line }) => {
-                if let Some(l) = line {
-                    format!(" [{}:{}]", path.display(), l)
-                } else {
-                    format!(" [{}]", path.display())
+        for violation in &result.best_practice_violations {
+            let (file, line, column, end_line, end_column) = match &violation.location {
+                Some(ViolationLocation::File { path, line, column }) => {
+                    (Some(path.clone()), *line, *column, None, None)
                 }
-            }
-            Some(ViolationLocation::SkillBody { line }) => format!(" [line {}]", line),
-            Some(ViolationLocation::Script { path, line }) => {
-                if let Some(l) = line {
-                    format!(" [{}:{}]", path.display(), l)
-                } else {
-                    format!(" [{}]", path.display())
+                Some(ViolationLocation::Script { path, line }) => {
+                    (Some(path.clone()), *line, None, None, None)
                 }
-            }
-            None => String::new(),
+                Some(ViolationLocation::SkillBody { line }) => {
+                    (skill_md(), Some(*line), None, None, None)
+                }
+                Some(ViolationLocation::Frontmatter {
+                    line,
+                    column,
+                    end_line,
+                    end_column,
+                    ..
+                }) => (skill_md(), *line, *column, *end_line, *end_column),
+                None => (skill_md(), None, None, None, None),
+            };
+            findings.push(Finding {
+                rule_id: violation.code.as_str(),
+                code: Some(&violation.code),
+                kind: DiagnosticKind::BestPractice,
+                severity: violation.severity,
+                message: &violation.message,
+                file,
+                line,
+                column,
+                end_line,
+                end_column,
+                fixable: violation.fix.is_some(),
+            });
         }
     }
 
-    /// Format as JSON
-    fn format_json(&self, results: &[ValidationResult]) -> String {
-        let json_output = JsonOutput {
-            results: results
-                .iter()
-                .map(|r| JsonValidationResult {
-                    skill_path: r.skill_path.display().to_string(),
-                    errors: r.errors.iter().map(|e| e.message.clone()).collect(),
-                    warnings: r.warnings.iter().map(|w| w.message.clone()).collect(),
-                    best_practice_violations: r.best_practice_violations.clone(),
-                })
-                .collect(),
-        };
+    findings
+}
+
+/// Skill name for a result path (the skill directory name)
+pub(crate) fn skill_name(skill_path: &Path) -> String {
+    skill_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| skill_path.display().to_string())
+}
 
-        serde_json::to_string_pretty(&json_output).unwrap_or_else(|_| "{}".into())
+/// Express `path` relative to `root` when it lies inside it
+pub(crate) fn relative_path(root: Option<&Path>, path: &Path) -> PathBuf {
+    let Some(root) = root else {
+        return path.to_path_buf();
+    };
+    if let Ok(relative) = path.strip_prefix(root) {
+        return relative.to_path_buf();
+    }
+    match (std::path::absolute(root), std::path::absolute(path)) {
+        (Ok(root), Ok(abs)) => abs
+            .strip_prefix(&root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
     }
 }
 
-#[derive(Serialize)]
-struct JsonOutput {
-    results: Vec<JsonValidationResult>,
+/// Path as shown to CI tooling: relative to the current directory when inside it
+pub(crate) fn workspace_path(path: &Path) -> String {
+    let cwd = std::env::current_dir().ok();
+    relative_path(cwd.as_deref(), path)
+        .to_string_lossy()
+        .replace('\\', "/")
 }
 
-#[derive(Serialize)]
-struct JsonValidationResult {
-    skill_path: String,
-    errors: Vec<String>,
-    warnings: Vec<String>,
-    best_practice_violations: Vec<crate::models::BestPracticeViolation>,
+/// Escape text for XML attributes and content, dropping disallowed control characters
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\t' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{ValidationError, ValidationErrorKind};
+    use crate::models::{BestPracticeViolation, ValidationError, ValidationErrorKind};
     use std::path::PathBuf;
 
     #[test]
@@ -189,11 +294,42 @@ mod tests {
         });
 
         let output = formatter.format_validation_results(&[result]);
-        assert!(output.contains("[ERROR]"));
+        assert!(output.contains("error[invalid-field-value]:"));
         assert!(output.contains("Name must be lowercase"));
         assert!(output.contains("Found 1 error(s)"));
     }
 
+    #[test]
+    fn test_findings_keep_frontmatter_end_positions() {
+        let mut result = ValidationResult::new(PathBuf::from("test-skill"));
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS002,
+            severity: Severity::Warning,
+            message: "Vague description".into(),
+            location: Some(ViolationLocation::Frontmatter {
+                field: "description".into(),
+                line: Some(3),
+                column: Some(14),
+                end_line: Some(4),
+                end_column: Some(9),
+            }),
+            fix: None,
+        });
+
+        let results = [result];
+        let found = findings(&results);
+        assert_eq!((found[0].line, found[0].column), (Some(3), Some(14)));
+        assert_eq!((found[0].end_line, found[0].end_column), (Some(4), Some(9)));
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">'&'</a>\u{1}"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+
     #[test]
     fn test_format_json() {
         let formatter = OutputFormatter::new(OutputFormat::Json, false);
@@ -205,8 +341,10 @@ mod tests {
         });
 
         let output = formatter.format_validation_results(&[result]);
-        assert!(output.contains("\"skill_path\""));
-        assert!(output.contains("\"errors\""));
-        assert!(output.contains("Test error"));
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(json["summary"]["errors"], 1);
+        assert_eq!(json["diagnostics"][0]["code"], "invalid-field-value");
+        assert_eq!(json["diagnostics"][0]["message"], "Test error");
     }
 }
@@ -0,0 +1,137 @@
+//! Checkstyle XML reports: one `<file>` per affected file
+
+use super::{escape_xml, findings, workspace_path};
+use crate::models::{Severity, ValidationResult};
+
+/// Render results as Checkstyle XML
+///
+/// Findings that are not tied to a file are reported against their skill
+/// directory.
+pub(super) fn format_checkstyle(results: &[ValidationResult]) -> String {
+    // Files in order of first appearance, each with its rendered errors
+    let mut files: Vec<(String, Vec<String>)> = Vec::new();
+
+    for result in results {
+        for finding in findings(std::slice::from_ref(result)) {
+            let name = workspace_path(finding.file.as_deref().unwrap_or(&result.skill_path));
+            let severity = match finding.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info",
+            };
+
+            let mut error = String::from("    <error");
+            if let Some(line) = finding.line {
+                error.push_str(&format!(" line=\"{}\"", line));
+            }
+            if let Some(column) = finding.column {
+                error.push_str(&format!(" column=\"{}\"", column));
+            }
+            error.push_str(&format!(
+                " severity=\"{}\" message=\"{}\" source=\"madskills.{}\"/>\n",
+                severity,
+                escape_xml(finding.message),
+                escape_xml(finding.rule_id)
+            ));
+
+            match files.iter_mut().find(|(file, _)| *file == name) {
+                Some((_, errors)) => errors.push(error),
+                None => files.push((name, vec![error])),
+            }
+        }
+    }
+
+    let mut output =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    for (file, errors) in files {
+        output.push_str(&format!("  <file name=\"{}\">\n", escape_xml(&file)));
+        for error in errors {
+            output.push_str(&error);
+        }
+        output.push_str("  </file>\n");
+    }
+    output.push_str("</checkstyle>\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BestPracticeCode, BestPracticeViolation, SourceLocation, ValidationError,
+        ValidationErrorKind, ViolationLocation,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_checkstyle() {
+        let mut result = ValidationResult::new(PathBuf::from("skills/demo"));
+        result.errors.push(ValidationError {
+            kind: ValidationErrorKind::MarkdownLintError,
+            message: "[MD001] \"Heading\" levels".into(),
+            location: Some(SourceLocation {
+                file: PathBuf::from("skills/demo/SKILL.md"),
+                line: 7,
+                column: 2,
+                end_line: None,
+                end_column: None,
+            }),
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Info,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("skills/demo/run.py"),
+                line: Some(3),
+            }),
+            fix: None,
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS017,
+            severity: Severity::Warning,
+            message: "No error handling".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("skills/demo/run.py"),
+                line: None,
+            }),
+            fix: None,
+        });
+
+        let output = format_checkstyle(&[result]);
+
+        assert!(output.contains("<file name=\"skills/demo/SKILL.md\">"));
+        assert!(output.contains(
+            "<error line=\"7\" column=\"2\" severity=\"error\" message=\"[MD001] &quot;Heading&quot; levels\" source=\"madskills.markdown-lint-error\"/>"
+        ));
+        assert_eq!(
+            output.matches("<file name=\"skills/demo/run.py\">").count(),
+            1
+        );
+        assert!(output.contains(
+            "<error line=\"3\" severity=\"info\" message=\"Undocumented constant\" source=\"madskills.AS018\"/>"
+        ));
+        assert!(output.contains(
+            "<error severity=\"warning\" message=\"No error handling\" source=\"madskills.AS017\"/>"
+        ));
+    }
+
+    #[test]
+    fn test_custom_rule_ids_are_escaped() {
+        let code = BestPracticeCode::custom(crate::models::CustomCode {
+            id: "ACME&<\"1\">".into(),
+            ..Default::default()
+        });
+        let mut result = ValidationResult::new(PathBuf::from("skills/demo"));
+        result.best_practice_violations.push(BestPracticeViolation {
+            code,
+            severity: Severity::Error,
+            message: "Bad".into(),
+            location: None,
+            fix: None,
+        });
+
+        let output = format_checkstyle(&[result]);
+        assert!(output.contains("source=\"madskills.ACME&amp;&lt;&quot;1&quot;&gt;\""));
+    }
+}
@@ -0,0 +1,108 @@
+//! GitHub Actions workflow commands for inline pull request annotations
+
+use super::{findings, workspace_path};
+use crate::models::{Severity, ValidationResult};
+
+/// Render one `::error`/`::warning`/`::notice` command per finding
+///
+/// GitHub resolves annotation paths against the workspace, so files are made
+/// relative to the current directory rather than the scanned root.
+pub(super) fn format_github(results: &[ValidationResult]) -> String {
+    let mut output = String::new();
+
+    for finding in findings(results) {
+        let command = match finding.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+
+        let mut properties = Vec::new();
+        if let Some(file) = &finding.file {
+            properties.push(format!("file={}", escape_property(&workspace_path(file))));
+            if let Some(line) = finding.line {
+                properties.push(format!("line={}", line));
+            }
+            if let Some(column) = finding.column {
+                properties.push(format!("col={}", column));
+            }
+        }
+        properties.push(format!("title={}", escape_property(finding.rule_id)));
+
+        output.push_str(&format!(
+            "::{} {}::{}\n",
+            command,
+            properties.join(","),
+            escape_data(finding.message)
+        ));
+    }
+
+    output
+}
+
+/// Escape a workflow command message
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BestPracticeCode, BestPracticeViolation, SourceLocation, ValidationError,
+        ValidationErrorKind, ViolationLocation,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_github_commands() {
+        let mut result = ValidationResult::new(PathBuf::from("skills/demo"));
+        result.errors.push(ValidationError {
+            kind: ValidationErrorKind::MarkdownLintError,
+            message: "[MD001] Heading levels should increment by one".into(),
+            location: Some(SourceLocation {
+                file: PathBuf::from("skills/demo/SKILL.md"),
+                line: 7,
+                column: 1,
+                end_line: None,
+                end_column: None,
+            }),
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Info,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("skills/demo/run.py"),
+                line: Some(3),
+            }),
+            fix: None,
+        });
+
+        let output = format_github(&[result]);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "::error file=skills/demo/SKILL.md,line=7,col=1,title=markdown-lint-error::[MD001] Heading levels should increment by one"
+        );
+        assert_eq!(
+            lines[1],
+            "::notice file=skills/demo/run.py,line=3,title=AS018::Undocumented constant"
+        );
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(escape_data("50% done\nnext"), "50%25 done%0Anext");
+        assert_eq!(escape_property("a,b:c"), "a%2Cb%3Ac");
+    }
+}
@@ -0,0 +1,281 @@
+//! Versioned JSON report for lint results
+//!
+//! The document shape is described by [`JsonReport`]; its JSON Schema is
+//! published at `docs/schema/lint-report.schema.json` (regenerate with
+//! `cargo xtask schema`). Breaking changes bump [`JSON_SCHEMA_VERSION`].
+
+use super::{findings, relative_path, skill_name};
+use crate::models::{Severity, ValidationResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current version of the JSON report shape
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Lint results as a single JSON document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonReport {
+    /// Version of the document shape
+    pub schema_version: u32,
+    /// Tool that produced the report
+    pub tool: JsonTool,
+    /// Totals across all skills
+    pub summary: JsonSummary,
+    /// Every linted skill, including ones without findings
+    pub skills: Vec<JsonSkill>,
+    /// Every finding, in report order
+    pub diagnostics: Vec<JsonDiagnostic>,
+}
+
+/// Tool information
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonTool {
+    pub name: String,
+    pub version: String,
+}
+
+/// Finding counts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonSummary {
+    /// Number of skills linted
+    pub skills: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    /// Findings that can be fixed automatically
+    pub fixable: usize,
+}
+
+/// A linted skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonSkill {
+    /// Skill name (its directory name)
+    pub name: String,
+    /// Skill directory, relative to the scanned root
+    pub path: String,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+/// A single finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonDiagnostic {
+    /// Rule code (`AS010`) or spec check identifier (`invalid-field-value`)
+    pub code: String,
+    /// Which family of checks produced the finding
+    pub kind: DiagnosticKind,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Name of the skill the finding belongs to
+    pub skill: String,
+    /// File relative to the scanned root, when the finding points at one
+    pub file: Option<String>,
+    /// 1-indexed start line
+    pub line: Option<usize>,
+    /// 1-indexed start column
+    pub column: Option<usize>,
+    /// 1-indexed end line
+    pub end_line: Option<usize>,
+    /// 1-indexed column just past the end
+    pub end_column: Option<usize>,
+    /// Whether an automatic fix is available
+    pub fixable: bool,
+}
+
+/// Family of checks a diagnostic comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticKind {
+    /// AgentSkills specification checks
+    Spec,
+    /// Markdown linting
+    Markdown,
+    /// Best practice rules (ASxxx)
+    BestPractice,
+    /// Inline suppression bookkeeping
+    Suppression,
+}
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl From<Severity> for DiagnosticSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => Self::Error,
+            Severity::Warning => Self::Warning,
+            Severity::Info => Self::Info,
+        }
+    }
+}
+
+impl JsonReport {
+    /// Build the report, making paths relative to `root`
+    pub fn new(results: &[ValidationResult], root: Option<&Path>) -> Self {
+        let display = |path: &Path| {
+            relative_path(root, path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+
+        let mut summary = JsonSummary {
+            skills: results.len(),
+            ..Default::default()
+        };
+        let mut skills = Vec::new();
+        let mut diagnostics = Vec::new();
+
+        for result in results {
+            let name = skill_name(&result.skill_path);
+            let mut skill = JsonSkill {
+                name: name.clone(),
+                path: display(&result.skill_path),
+                errors: 0,
+                warnings: 0,
+                infos: 0,
+            };
+
+            for finding in findings(std::slice::from_ref(result)) {
+                let severity = DiagnosticSeverity::from(finding.severity);
+                match severity {
+                    DiagnosticSeverity::Error => skill.errors += 1,
+                    DiagnosticSeverity::Warning => skill.warnings += 1,
+                    DiagnosticSeverity::Info => skill.infos += 1,
+                }
+                if finding.fixable {
+                    summary.fixable += 1;
+                }
+
+                diagnostics.push(JsonDiagnostic {
+                    code: finding.rule_id.to_string(),
+                    kind: finding.kind,
+                    severity,
+                    message: finding.message.to_string(),
+                    skill: name.clone(),
+                    file: finding.file.as_deref().map(display),
+                    line: finding.line,
+                    column: finding.column,
+                    end_line: finding.end_line,
+                    end_column: finding.end_column,
+                    fixable: finding.fixable,
+                });
+            }
+
+            summary.errors += skill.errors;
+            summary.warnings += skill.warnings;
+            summary.infos += skill.infos;
+            skills.push(skill);
+        }
+
+        Self {
+            schema_version: JSON_SCHEMA_VERSION,
+            tool: JsonTool {
+                name: "madskills".into(),
+                version: env!("CARGO_PKG_VERSION").into(),
+            },
+            summary,
+            skills,
+            diagnostics,
+        }
+    }
+}
+
+/// JSON Schema describing [`JsonReport`]
+pub fn json_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(JsonReport)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BestPracticeCode, BestPracticeViolation, SourceLocation, ValidationWarning,
+        ValidationWarningKind, ViolationLocation,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_json_report_shape() {
+        let mut result = ValidationResult::new(PathBuf::from("repo/skills/demo"));
+        result.warnings.push(ValidationWarning {
+            kind: ValidationWarningKind::MarkdownLintWarning,
+            message: "[MD013] Line length".into(),
+            location: Some(SourceLocation {
+                file: PathBuf::from("repo/skills/demo/SKILL.md"),
+                line: 4,
+                column: 81,
+                end_line: Some(4),
+                end_column: Some(120),
+            }),
+            fixable: true,
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Info,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("repo/skills/demo/run.py"),
+                line: Some(3),
+            }),
+            fix: None,
+        });
+        let clean = ValidationResult::new(PathBuf::from("repo/skills/clean"));
+
+        let report = JsonReport::new(&[result, clean], Some(Path::new("repo")));
+
+        assert_eq!(report.schema_version, JSON_SCHEMA_VERSION);
+        assert_eq!(
+            report.summary,
+            JsonSummary {
+                skills: 2,
+                errors: 0,
+                warnings: 1,
+                infos: 1,
+                fixable: 1,
+            }
+        );
+        assert_eq!(report.skills[1].name, "clean");
+        assert_eq!(report.skills[0].path, "skills/demo");
+
+        let markdown = &report.diagnostics[0];
+        assert_eq!(markdown.code, "markdown-lint-warning");
+        assert_eq!(markdown.kind, DiagnosticKind::Markdown);
+        assert_eq!(markdown.file.as_deref(), Some("skills/demo/SKILL.md"));
+        assert_eq!(markdown.end_column, Some(120));
+        assert!(markdown.fixable);
+
+        let json = serde_json::to_value(&report.diagnostics[1]).unwrap();
+        assert_eq!(json["code"], "AS018");
+        assert_eq!(json["kind"], "best-practice");
+        assert_eq!(json["severity"], "info");
+        assert_eq!(json["skill"], "demo");
+        assert_eq!(json["line"], 3);
+        assert!(json["column"].is_null());
+    }
+
+    #[test]
+    fn test_published_schema_is_current() {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../docs/schema/lint-report.schema.json");
+        // The published schema lives in the workspace, not the packaged crate
+        if !path.exists() {
+            return;
+        }
+        let published: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        assert_eq!(
+            published,
+            json_schema(),
+            "run `cargo xtask schema` to regenerate the published schema"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! JUnit XML reports: one test suite per skill, one test case per check
+
+use super::{Finding, escape_xml, findings, skill_name, workspace_path};
+use crate::models::{
+    BestPracticeCode, Severity, ValidationErrorKind, ValidationResult, ValidationWarningKind,
+};
+
+/// Render results as JUnit XML
+///
+/// Every check becomes a test case so passing checks are counted too. Error
+/// findings fail their test case; warnings and infos are attached as output.
+pub(super) fn format_junit(results: &[ValidationResult]) -> String {
+    let checks = check_ids(&findings(results));
+    let mut suites = String::new();
+    let mut total_tests = 0;
+    let mut total_failures = 0;
+
+    for result in results {
+        let skill = skill_name(&result.skill_path);
+        let skill_findings = findings(std::slice::from_ref(result));
+
+        let mut cases = String::new();
+        let mut failures = 0;
+        for check in &checks {
+            let matching: Vec<&Finding<'_>> = skill_findings
+                .iter()
+                .filter(|f| f.rule_id == *check)
+                .collect();
+            let (errors, others): (Vec<&Finding<'_>>, Vec<&Finding<'_>>) = matching
+                .into_iter()
+                .partition(|f| f.severity == Severity::Error);
+
+            cases.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"madskills.{}\"",
+                escape_xml(check),
+                escape_xml(&skill)
+            ));
+            if errors.is_empty() && others.is_empty() {
+                cases.push_str("/>\n");
+                continue;
+            }
+            cases.push_str(">\n");
+
+            if !errors.is_empty() {
+                failures += 1;
+                cases.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                    escape_xml(errors[0].message),
+                    escape_xml(check),
+                    escape_xml(&describe(&errors))
+                ));
+            }
+            if !others.is_empty() {
+                cases.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    escape_xml(&describe(&others))
+                ));
+            }
+            cases.push_str("    </testcase>\n");
+        }
+
+        total_tests += checks.len();
+        total_failures += failures;
+        suites.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n{}  </testsuite>\n",
+            escape_xml(&skill),
+            checks.len(),
+            failures,
+            cases
+        ));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"madskills\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n{}</testsuites>\n",
+        total_tests, total_failures, suites
+    )
+}
+
+/// Identifiers of every check, in report order, plus custom rules that
+/// reported findings
+fn check_ids<'a>(findings: &[Finding<'a>]) -> Vec<&'a str> {
+    let mut ids: Vec<&'a str> = ValidationErrorKind::ALL
+        .iter()
+        .map(|k| k.as_str())
+        .chain(ValidationWarningKind::ALL.iter().map(|k| k.as_str()))
+        .chain(BestPracticeCode::ALL.iter().map(|c| c.as_str()))
+        .collect();
+    for code in findings.iter().filter_map(|f| f.code) {
+        if !ids.contains(&code.as_str()) {
+            ids.push(code.as_str());
+        }
+    }
+    ids
+}
+
+/// One `severity: file:line: message` line per finding
+fn describe(findings: &[&Finding<'_>]) -> String {
+    findings
+        .iter()
+        .map(|f| {
+            let severity = match f.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info",
+            };
+            match (&f.file, f.line) {
+                (Some(file), Some(line)) => format!(
+                    "{}: {}:{}: {}",
+                    severity,
+                    workspace_path(file),
+                    line,
+                    f.message
+                ),
+                (Some(file), None) => {
+                    format!("{}: {}: {}", severity, workspace_path(file), f.message)
+                }
+                (None, _) => format!("{}: {}", severity, f.message),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BestPracticeViolation, ValidationError, ViolationLocation};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_format_junit() {
+        let mut result = ValidationResult::new(PathBuf::from("skills/demo"));
+        result.errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidFieldValue,
+            message: "Name must be <lowercase>".into(),
+            location: None,
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Warning,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("skills/demo/run.py"),
+                line: Some(3),
+            }),
+            fix: None,
+        });
+
+        let output = format_junit(&[result]);
+        let checks = check_ids(&[]).len();
+
+        assert!(output.starts_with("<?xml"));
+        assert!(output.contains(&format!(
+            "<testsuite name=\"demo\" tests=\"{}\" failures=\"1\"",
+            checks
+        )));
+        assert!(output.contains(
+            "<failure message=\"Name must be &lt;lowercase&gt;\" type=\"invalid-field-value\">"
+        ));
+        assert!(output.contains(
+            "<system-out>warning: skills/demo/run.py:3: Undocumented constant</system-out>"
+        ));
+        assert!(output.contains("<testcase name=\"AS001\" classname=\"madskills.demo\"/>"));
+    }
+
+    #[test]
+    fn test_custom_rule_ids_are_escaped() {
+        let code = BestPracticeCode::custom(crate::models::CustomCode {
+            id: "ACME<\"1\">".into(),
+            ..Default::default()
+        });
+        let mut result = ValidationResult::new(PathBuf::from("skills/demo"));
+        result.best_practice_violations.push(BestPracticeViolation {
+            code,
+            severity: Severity::Error,
+            message: "Bad".into(),
+            location: None,
+            fix: None,
+        });
+
+        let output = format_junit(&[result]);
+        assert!(output.contains("<testcase name=\"ACME&lt;&quot;1&quot;&gt;\""));
+        assert!(output.contains("type=\"ACME&lt;&quot;1&quot;&gt;\""));
+    }
+}
@@ -0,0 +1,493 @@
+//! SARIF 2.1.0 output for code scanning dashboards
+
+use super::{Finding, findings};
+use crate::models::{
+    BestPracticeCode, RuleLevel, Severity, ValidationErrorKind, ValidationResult,
+    ValidationWarningKind,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SRCROOT: &str = "SRCROOT";
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_uri_base_ids: Option<serde_json::Value>,
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor {
+    id: String,
+    name: String,
+    short_description: Message,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+    default_configuration: Configuration,
+}
+
+#[derive(Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+/// Render results as a SARIF log with paths relative to `root`
+///
+/// Rules are described with their level in `default_levels`, or as
+/// warnings when missing from it.
+pub(super) fn format_sarif(
+    results: &[ValidationResult],
+    root: Option<&Path>,
+    default_levels: &HashMap<BestPracticeCode, RuleLevel>,
+) -> String {
+    let findings = findings(results);
+    let rules = reporting_descriptors(&findings, default_levels);
+    let results = findings
+        .iter()
+        .map(|finding| sarif_result(finding, &rules, root))
+        .collect();
+
+    let original_uri_base_ids = root
+        .and_then(|root| std::path::absolute(root).ok())
+        .map(|root| {
+            serde_json::json!({
+                SRCROOT: { "uri": directory_uri(&root) }
+            })
+        });
+
+    let log = SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "madskills",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules,
+                },
+            },
+            original_uri_base_ids,
+            column_kind: "unicodeCodePoints",
+            results,
+        }],
+    };
+
+    let mut output = serde_json::to_string_pretty(&log).unwrap_or_else(|_| "{}".into());
+    output.push('\n');
+    output
+}
+
+/// One descriptor per best practice rule and spec check, plus custom rules
+/// that reported findings
+fn reporting_descriptors(
+    findings: &[Finding<'_>],
+    default_levels: &HashMap<BestPracticeCode, RuleLevel>,
+) -> Vec<ReportingDescriptor> {
+    let mut custom: Vec<BestPracticeCode> = Vec::new();
+    for code in findings.iter().filter_map(|f| f.code) {
+        if !code.is_builtin() && !custom.contains(code) {
+            custom.push(code.clone());
+        }
+    }
+    let best_practices =
+        BestPracticeCode::ALL
+            .into_iter()
+            .chain(custom)
+            .map(|code| ReportingDescriptor {
+                id: code.as_str().to_string(),
+                name: code.as_str().to_string(),
+                short_description: Message {
+                    text: code.description().to_string(),
+                },
+                help_uri: code.help_uri(),
+                default_configuration: Configuration {
+                    level: rule_level(default_levels.get(&code).copied()),
+                },
+            });
+    let errors = ValidationErrorKind::ALL
+        .iter()
+        .map(|kind| ReportingDescriptor {
+            id: kind.as_str().to_string(),
+            name: pascal_case(kind.as_str()),
+            short_description: Message {
+                text: kind.description().to_string(),
+            },
+            help_uri: None,
+            default_configuration: Configuration { level: "error" },
+        });
+    let warnings = ValidationWarningKind::ALL
+        .iter()
+        .map(|kind| ReportingDescriptor {
+            id: kind.as_str().to_string(),
+            name: pascal_case(kind.as_str()),
+            short_description: Message {
+                text: kind.description().to_string(),
+            },
+            help_uri: None,
+            default_configuration: Configuration { level: "warning" },
+        });
+
+    best_practices.chain(errors).chain(warnings).collect()
+}
+
+fn sarif_result(
+    finding: &Finding<'_>,
+    rules: &[ReportingDescriptor],
+    root: Option<&Path>,
+) -> SarifResult {
+    let locations = finding
+        .file
+        .as_ref()
+        .map(|file| Location {
+            physical_location: PhysicalLocation {
+                artifact_location: artifact_location(file, root),
+                region: finding.line.map(|start_line| Region {
+                    start_line,
+                    start_column: finding.column,
+                    end_line: finding.end_line,
+                    end_column: finding.end_column,
+                }),
+            },
+        })
+        .into_iter()
+        .collect();
+
+    SarifResult {
+        rule_id: finding.rule_id.to_string(),
+        rule_index: rules.iter().position(|rule| rule.id == finding.rule_id),
+        level: level(finding.severity),
+        message: Message {
+            text: finding.message.to_string(),
+        },
+        locations,
+    }
+}
+
+/// Reference to `file`, relative to SRCROOT when it lies inside `root`
+///
+/// Files elsewhere (such as `~/.claude/skills`) get an absolute `file://` URI.
+fn artifact_location(file: &Path, root: Option<&Path>) -> ArtifactLocation {
+    match root.and_then(|root| inside_root(file, root)) {
+        Some(relative) => ArtifactLocation {
+            uri: path_uri(&relative),
+            uri_base_id: Some(SRCROOT),
+        },
+        // Without a root there is nothing to resolve against but the reader's cwd
+        None if root.is_none() && file.is_relative() => ArtifactLocation {
+            uri: path_uri(file),
+            uri_base_id: None,
+        },
+        None => ArtifactLocation {
+            uri: file_uri(&std::path::absolute(file).unwrap_or_else(|_| file.to_path_buf())),
+            uri_base_id: None,
+        },
+    }
+}
+
+/// `file` relative to `root`, or `None` when it lies outside it
+fn inside_root(file: &Path, root: &Path) -> Option<PathBuf> {
+    if let Ok(relative) = file.strip_prefix(root) {
+        return Some(relative.to_path_buf());
+    }
+    let root = std::path::absolute(root).ok()?;
+    let file = std::path::absolute(file).ok()?;
+    file.strip_prefix(&root).ok().map(Path::to_path_buf)
+}
+
+/// SARIF level for a rule's default level
+fn rule_level(level: Option<RuleLevel>) -> &'static str {
+    match level {
+        Some(RuleLevel::Off) => "none",
+        Some(RuleLevel::Info) => "note",
+        Some(RuleLevel::Warning) | None => "warning",
+        Some(RuleLevel::Error) => "error",
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// `missing-skill-md` -> `MissingSkillMd`
+fn pascal_case(id: &str) -> String {
+    id.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Percent-encode a relative path as a URI reference with `/` separators
+fn path_uri(path: &Path) -> String {
+    let mut uri = String::new();
+    for (i, component) in path
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .enumerate()
+    {
+        if i > 0 {
+            uri.push('/');
+        }
+        for byte in component.as_os_str().to_string_lossy().bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+                uri.push(byte as char);
+            } else {
+                uri.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    uri
+}
+
+/// `file://` URI for an absolute path
+fn file_uri(path: &Path) -> String {
+    format!(
+        "file:///{}",
+        path_uri(path.strip_prefix("/").unwrap_or(path))
+    )
+}
+
+/// `file://` URI for a directory, ending in `/` as SARIF requires
+fn directory_uri(path: &Path) -> String {
+    let uri = file_uri(path);
+    if uri.ends_with('/') { uri } else { uri + "/" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BestPracticeViolation, ValidationError, ViolationLocation};
+    use std::path::PathBuf;
+
+    fn sample_results() -> Vec<ValidationResult> {
+        let mut result = ValidationResult::new(PathBuf::from("repo/.github/skills/demo"));
+        result.errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidFieldValue,
+            message: "Name must be lowercase".into(),
+            location: None,
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Info,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("repo/.github/skills/demo/scripts dir/run.py"),
+                line: Some(4),
+            }),
+            fix: None,
+        });
+        vec![result]
+    }
+
+    #[test]
+    fn test_sarif_log_shape() {
+        let output = format_sarif(&sample_results(), Some(Path::new("repo")), &HashMap::new());
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "madskills");
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(
+            rules.len(),
+            BestPracticeCode::ALL.len()
+                + ValidationErrorKind::ALL.len()
+                + ValidationWarningKind::ALL.len()
+        );
+        assert_eq!(rules[0]["id"], "AS001");
+        assert!(
+            rules[0]["helpUri"]
+                .as_str()
+                .unwrap()
+                .ends_with("docs/rules/AS001.md")
+        );
+        assert!(
+            run["originalUriBaseIds"]["SRCROOT"]["uri"]
+                .as_str()
+                .unwrap()
+                .ends_with("/repo/")
+        );
+    }
+
+    #[test]
+    fn test_sarif_results_use_relative_locations() {
+        let output = format_sarif(&sample_results(), Some(Path::new("repo")), &HashMap::new());
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let results = log["runs"][0]["results"].as_array().unwrap();
+
+        // Spec error without a location and no SKILL.md on disk
+        assert_eq!(results[0]["ruleId"], "invalid-field-value");
+        assert_eq!(results[0]["level"], "error");
+        assert!(results[0].get("locations").is_none());
+
+        let violation = &results[1];
+        assert_eq!(violation["ruleId"], "AS018");
+        assert_eq!(violation["level"], "note");
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        let index = violation["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(rules[index]["id"], "AS018");
+
+        let physical = &violation["locations"][0]["physicalLocation"];
+        assert_eq!(
+            physical["artifactLocation"]["uri"],
+            ".github/skills/demo/scripts%20dir/run.py"
+        );
+        assert_eq!(physical["artifactLocation"]["uriBaseId"], "SRCROOT");
+        assert_eq!(physical["region"]["startLine"], 4);
+    }
+
+    #[test]
+    fn test_sarif_result_without_descriptor_omits_rule_index() {
+        let results = sample_results();
+        let findings = findings(&results);
+        let result = serde_json::to_value(sarif_result(&findings[1], &[], None)).unwrap();
+
+        assert_eq!(result["ruleId"], "AS018");
+        assert!(result.get("ruleIndex").is_none());
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("missing-skill-md"), "MissingSkillMd");
+    }
+
+    #[test]
+    fn test_sarif_locations_outside_root_are_absolute() {
+        let mut result = ValidationResult::new(PathBuf::from("/home/dev/.claude/skills/demo"));
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS018,
+            severity: Severity::Warning,
+            message: "Undocumented constant".into(),
+            location: Some(ViolationLocation::Script {
+                path: PathBuf::from("/home/dev/.claude/skills/demo/run.py"),
+                line: Some(4),
+            }),
+            fix: None,
+        });
+
+        let output = format_sarif(&[result], Some(Path::new("/work/repo")), &HashMap::new());
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let artifact =
+            &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+
+        assert_eq!(
+            artifact["uri"],
+            "file:///home/dev/.claude/skills/demo/run.py"
+        );
+        assert!(artifact.get("uriBaseId").is_none());
+    }
+
+    #[test]
+    fn test_sarif_rules_use_default_levels() {
+        let levels = HashMap::from([
+            (BestPracticeCode::AS003, RuleLevel::Info),
+            (BestPracticeCode::AS010, RuleLevel::Error),
+        ]);
+        let output = format_sarif(&sample_results(), Some(Path::new("repo")), &levels);
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        let level = |id: &str| {
+            rules.iter().find(|rule| rule["id"] == id).unwrap()["defaultConfiguration"]["level"]
+                .clone()
+        };
+
+        assert_eq!(level("AS003"), "note");
+        assert_eq!(level("AS010"), "error");
+        assert_eq!(level("AS001"), "warning");
+    }
+}
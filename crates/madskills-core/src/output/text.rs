@@ -0,0 +1,388 @@
+//! Human-readable terminal output with source snippets
+//!
+//! Each finding is rendered rustc-style: a `severity[code]: message` header,
+//! a `--> file:line:col` pointer, the offending source line with a caret
+//! underline and an optional `= help:` line. Findings are grouped per skill
+//! and followed by a per-skill summary.
+
+use super::{Finding, findings, skill_name, workspace_path};
+use crate::models::{Severity, ValidationResult};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const RED: &str = "1;31";
+const YELLOW: &str = "1;33";
+const CYAN: &str = "1;36";
+const BLUE: &str = "1;34";
+const BOLD: &str = "1";
+
+/// Wraps text in ANSI styles and hyperlinks when color is enabled
+struct Painter {
+    color: bool,
+}
+
+impl Painter {
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", style, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// OSC 8 hyperlink; terminals without support show the plain text
+    fn link(&self, url: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Per-skill finding counts
+#[derive(Default)]
+struct Counts {
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+}
+
+impl Counts {
+    fn add(&mut self, severity: Severity) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Info => self.infos += 1,
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        for (count, noun) in [
+            (self.errors, "error"),
+            (self.warnings, "warning"),
+            (self.infos, "info"),
+        ] {
+            if count > 0 {
+                let plural = if count == 1 || noun == "info" {
+                    ""
+                } else {
+                    "s"
+                };
+                parts.push(format!("{} {}{}", count, noun, plural));
+            }
+        }
+        if parts.is_empty() {
+            "no issues".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Render results for a terminal
+///
+/// With `verbose`, skills without findings are listed in the summary too.
+pub(super) fn format_text(results: &[ValidationResult], color: bool, verbose: bool) -> String {
+    let painter = Painter { color };
+    let mut sources = SourceCache::default();
+    let mut output = String::new();
+    let mut summary = Vec::new();
+    let mut total_errors = 0;
+    let mut total_warnings = 0;
+    let mut total_bp_violations = 0;
+
+    for result in results {
+        let mut skill_findings = findings(std::slice::from_ref(result));
+        let name = skill_name(&result.skill_path);
+        let mut counts = Counts::default();
+
+        if skill_findings.is_empty() {
+            if verbose {
+                summary.push((name, counts));
+            }
+            continue;
+        }
+
+        // Read each file top to bottom
+        skill_findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        output.push_str(&format!(
+            "\n{}\n",
+            painter.paint(BOLD, &workspace_path(&result.skill_path))
+        ));
+        for finding in &skill_findings {
+            output.push('\n');
+            output.push_str(&render(finding, &painter, &mut sources));
+            counts.add(finding.severity);
+            if finding.code.is_some() {
+                total_bp_violations += 1;
+            }
+        }
+
+        total_errors += counts.errors;
+        total_warnings += counts.warnings;
+        summary.push((name, counts));
+    }
+
+    if summary.is_empty() {
+        return output;
+    }
+
+    let width = summary
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    output.push_str(&format!("\n{}\n", painter.paint(BOLD, "Summary:")));
+    for (name, counts) in &summary {
+        output.push_str(&format!(
+            "  {:width$}  {}\n",
+            name,
+            counts.describe(),
+            width = width
+        ));
+    }
+
+    if total_errors > 0 || total_warnings > 0 {
+        output.push('\n');
+        output.push_str(&format!(
+            "Found {} error(s), {} warning(s)",
+            total_errors, total_warnings
+        ));
+        if total_bp_violations > 0 {
+            output.push_str(&format!(" ({} best practice)", total_bp_violations));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Render one finding
+fn render(finding: &Finding<'_>, painter: &Painter, sources: &mut SourceCache) -> String {
+    let (label, style) = match finding.severity {
+        Severity::Error => ("error", RED),
+        Severity::Warning => ("warning", YELLOW),
+        Severity::Info => ("info", CYAN),
+    };
+
+    let code = match finding.code.and_then(|code| code.help_uri()) {
+        Some(uri) => painter.link(&uri, finding.rule_id),
+        None => finding.rule_id.to_string(),
+    };
+    let mut out = format!(
+        "{} {}\n",
+        painter.paint(style, &format!("{}[{}]:", label, code)),
+        painter.paint(BOLD, finding.message)
+    );
+
+    // Gutter wide enough for the line number
+    let gutter = finding.line.map_or(1, |l| l.to_string().len());
+    let pad = " ".repeat(gutter);
+    let bar = painter.paint(BLUE, "|");
+
+    if let Some(file) = &finding.file {
+        let mut position = workspace_path(file);
+        if let Some(line) = finding.line {
+            position.push_str(&format!(":{}", line));
+            if let Some(column) = finding.column {
+                position.push_str(&format!(":{}", column));
+            }
+        }
+        out.push_str(&format!(
+            "{}{} {}\n",
+            pad,
+            painter.paint(BLUE, "-->"),
+            position
+        ));
+
+        if let Some(line) = finding.line
+            && let Some(text) = sources.line(file, line)
+        {
+            let (shown, underline) = underline(&text, finding);
+            out.push_str(&format!("{} {}\n", pad, bar));
+            out.push_str(&format!(
+                "{} {} {}\n",
+                painter.paint(BLUE, &line.to_string()),
+                bar,
+                shown
+            ));
+            if let Some((start, len)) = underline {
+                out.push_str(&format!(
+                    "{} {} {}{}\n",
+                    pad,
+                    bar,
+                    " ".repeat(start),
+                    painter.paint(style, &"^".repeat(len))
+                ));
+            }
+        }
+    }
+
+    let help = match finding.code {
+        Some(code) if code.help().is_empty() => None,
+        Some(code) => Some(code.help().to_string()),
+        None if finding.fixable => Some("run `madskills fmt` to fix this automatically".into()),
+        None => None,
+    };
+    if let Some(help) = help {
+        out.push_str(&format!(
+            "{} {} {} {}\n",
+            pad,
+            painter.paint(BLUE, "="),
+            painter.paint(BOLD, "help:"),
+            help
+        ));
+    }
+
+    out
+}
+
+/// The source line with tabs expanded, and the display start and width of
+/// the caret underline when the finding has a column
+fn underline(text: &str, finding: &Finding<'_>) -> (String, Option<(usize, usize)>) {
+    let shown = text.replace('\t', "    ");
+    let Some(column) = finding.column else {
+        return (shown, None);
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let width =
+        |chars: &[char]| -> usize { chars.iter().map(|&c| if c == '\t' { 4 } else { 1 }).sum() };
+    let start_idx = column.saturating_sub(1).min(chars.len());
+    let end_idx = match (finding.end_line, finding.end_column) {
+        (Some(end_line), Some(end_column)) if Some(end_line) == finding.line => {
+            end_column.saturating_sub(1).clamp(start_idx, chars.len())
+        }
+        // Multi-line spans are underlined to the end of their first line
+        (Some(end_line), _) if Some(end_line) != finding.line => chars.len(),
+        _ => start_idx,
+    };
+
+    let start = width(&chars[..start_idx]);
+    let len = width(&chars[start_idx..end_idx]).max(1);
+    (shown, Some((start, len)))
+}
+
+/// Lazily read source files, once each
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    fn line(&mut self, path: &Path, line: usize) -> Option<String> {
+        let lines = self.files.entry(path.to_path_buf()).or_insert_with(|| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|content| content.lines().map(str::to_string).collect())
+        });
+        lines.as_ref()?.get(line.checked_sub(1)?).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        BestPracticeCode, BestPracticeViolation, ValidationError, ValidationErrorKind,
+        ViolationLocation,
+    };
+
+    #[test]
+    fn test_format_text_snippet() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let skill = dir.path().join("demo");
+        std::fs::create_dir(&skill).unwrap();
+        let skill_md = skill.join("SKILL.md");
+        std::fs::write(&skill_md, "---\nname: demo\n---\n\nSee docs\\guide.md\n").unwrap();
+
+        let mut result = ValidationResult::new(skill.clone());
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS005,
+            severity: Severity::Warning,
+            message: "Use forward slashes".into(),
+            location: Some(ViolationLocation::File {
+                path: skill_md,
+                line: Some(5),
+                column: Some(5),
+            }),
+            fix: None,
+        });
+
+        let output = format_text(&[result], false, false);
+
+        assert!(output.contains("warning[AS005]: Use forward slashes\n"));
+        assert!(output.contains("SKILL.md:5:5\n"));
+        assert!(output.contains("5 | See docs\\guide.md\n  |     ^\n"));
+        assert!(output.contains(&format!("  = help: {}\n", BestPracticeCode::AS005.help())));
+        assert!(output.contains("Summary:\n  demo  1 warning\n"));
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_format_text_color_and_links() {
+        let mut result = ValidationResult::new(PathBuf::from("demo"));
+        result.errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidFieldValue,
+            message: "Bad name".into(),
+            location: None,
+        });
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS016,
+            severity: Severity::Error,
+            message: "Reserved word".into(),
+            location: None,
+            fix: None,
+        });
+
+        let output = format_text(&[result], true, false);
+
+        assert!(output.contains("\x1b[1;31merror[invalid-field-value]:\x1b[0m"));
+        assert!(output.contains(&format!(
+            "\x1b]8;;{}\x1b\\AS016\x1b]8;;\x1b\\",
+            BestPracticeCode::AS016.help_uri().unwrap()
+        )));
+        assert!(output.contains("invalid-field-value"));
+    }
+
+    #[test]
+    fn test_underline_span() {
+        let finding = Finding {
+            rule_id: "invalid-field-value",
+            code: None,
+            kind: super::super::DiagnosticKind::Spec,
+            severity: Severity::Error,
+            message: "",
+            file: None,
+            line: Some(2),
+            column: Some(7),
+            end_line: Some(2),
+            end_column: Some(15),
+            fixable: false,
+        };
+
+        let (shown, underline) = underline("name:\tBad_Name", &finding);
+        assert_eq!(shown, "name:    Bad_Name");
+        assert_eq!(underline, Some((9, 8)));
+    }
+
+    #[test]
+    fn test_verbose_lists_clean_skills() {
+        let mut noisy = ValidationResult::new(PathBuf::from("noisy"));
+        noisy.errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidFieldValue,
+            message: "Bad".into(),
+            location: None,
+        });
+        let clean = ValidationResult::new(PathBuf::from("clean"));
+
+        let quiet = format_text(&[noisy.clone(), clean.clone()], false, false);
+        let verbose = format_text(&[noisy, clean], false, true);
+
+        assert!(!quiet.contains("clean  no issues"));
+        assert!(verbose.contains("clean  no issues"));
+    }
+}
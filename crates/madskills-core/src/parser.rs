@@ -1,7 +1,7 @@
 //! YAML frontmatter parsing for SKILL.md files
 
 use crate::error::{CoreError, CoreResult};
-use crate::models::SkillMetadata;
+use crate::models::{FieldSpan, FrontmatterSpans, SkillMetadata};
 use std::collections::HashSet;
 use std::path::Path;
 
@@ -41,11 +41,18 @@ pub fn parse_frontmatter(content: &str, path: &Path) -> CoreResult<SkillMetadata
     // Set the all_fields
     metadata.all_fields = all_fields;
 
+    // The YAML starts right after the opening delimiter line
+    let offset = yaml_str.as_ptr() as usize - content.as_ptr() as usize;
+    metadata.spans = scan_spans(yaml_str, offset);
+
     Ok(metadata)
 }
 
 /// Extract frontmatter from content, returning (yaml_str, markdown_content)
-fn extract_frontmatter<'a>(content: &'a str, path: &Path) -> CoreResult<(&'a str, &'a str)> {
+pub(crate) fn extract_frontmatter<'a>(
+    content: &'a str,
+    path: &Path,
+) -> CoreResult<(&'a str, &'a str)> {
     // Must start with ---
     if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
         return Err(CoreError::InvalidFrontmatter {
@@ -80,6 +87,149 @@ fn extract_frontmatter<'a>(content: &'a str, path: &Path) -> CoreResult<(&'a str
     Ok((yaml_str, markdown))
 }
 
+/// Record where each top-level key (and each key nested under `metadata`)
+/// appears, so diagnostics can point at the offending line
+///
+/// This is a line scanner rather than a YAML parser: a key starts at column
+/// one and owns every following indented, blank or sequence line. That covers
+/// the block-style frontmatter skills use; flow-style values still parse fine
+/// but are attributed to their key line only.
+fn scan_spans(yaml: &str, offset: usize) -> FrontmatterSpans {
+    let mut spans = FrontmatterSpans::default();
+    let mut current: Option<(String, FieldSpan)> = None;
+    let mut nested: Option<(String, FieldSpan)> = None;
+    let mut nested_indent = None;
+    let mut line_start = offset;
+
+    for (idx, raw) in yaml.split_inclusive('\n').enumerate() {
+        // The opening `---` is line 1
+        let line_no = idx + 2;
+        let start = line_start;
+        line_start += raw.len();
+
+        let text = raw.trim_end();
+        let body = text.trim_start();
+        if body.is_empty() || body.starts_with('#') {
+            continue;
+        }
+        let indent = text.len() - body.len();
+        let end_column = text.chars().count() + 1;
+        let end = start + text.len();
+
+        if indent == 0 && !body.starts_with('-') {
+            if let Some((key, span)) = current.take() {
+                spans.fields.insert(key, span);
+            }
+            if let Some((key, span)) = nested.take() {
+                spans.metadata.insert(key, span);
+            }
+            nested_indent = None;
+            current = key_span(text, line_no, 0, start, end);
+            continue;
+        }
+
+        let Some((key, span)) = current.as_mut() else {
+            continue;
+        };
+        extend(span, text, indent, line_no, end_column, end);
+
+        if key == "metadata" {
+            let nested_indent = *nested_indent.get_or_insert(indent);
+            if indent == nested_indent {
+                if let Some((key, span)) = nested.take() {
+                    spans.metadata.insert(key, span);
+                }
+                nested = key_span(text, line_no, indent, start + indent, end);
+            } else if let Some((_, span)) = nested.as_mut() {
+                extend(span, text, indent, line_no, end_column, end);
+            }
+        }
+    }
+
+    if let Some((key, span)) = current {
+        spans.fields.insert(key, span);
+    }
+    if let Some((key, span)) = nested {
+        spans.metadata.insert(key, span);
+    }
+    spans
+}
+
+/// Span of a `key: value` line whose key starts at byte `indent`
+fn key_span(
+    text: &str,
+    line: usize,
+    indent: usize,
+    start: usize,
+    end: usize,
+) -> Option<(String, FieldSpan)> {
+    let rest = &text[indent..];
+    let colon = find_key_colon(rest)?;
+    let key = rest[..colon].trim().trim_matches(['"', '\'']).to_string();
+
+    let after = &rest[colon + 1..];
+    let value = after.trim_start();
+    let value_start = indent + colon + 1 + (after.len() - value.len());
+    let column = text[..indent].chars().count() + 1;
+    let end_column = text.chars().count() + 1;
+
+    // An empty value continues on the following lines
+    let (value_line, value_column) = if value.is_empty() || value.starts_with('#') {
+        (0, 0)
+    } else {
+        (line, text[..value_start].chars().count() + 1)
+    };
+
+    Some((
+        key,
+        FieldSpan {
+            line,
+            column,
+            value_line,
+            value_column,
+            end_line: line,
+            end_column,
+            range: start..end,
+        },
+    ))
+}
+
+/// Grow a span over a continuation line
+fn extend(
+    span: &mut FieldSpan,
+    text: &str,
+    indent: usize,
+    line: usize,
+    end_column: usize,
+    end: usize,
+) {
+    if span.value_line == 0 {
+        span.value_line = line;
+        span.value_column = text[..indent].chars().count() + 1;
+    }
+    span.end_line = line;
+    span.end_column = end_column;
+    span.range.end = end;
+}
+
+/// Byte index of the colon ending a mapping key, skipping quoted keys
+pub(crate) fn find_key_colon(text: &str) -> Option<usize> {
+    let search_from = match text.chars().next() {
+        Some(quote @ ('"' | '\'')) => text[1..].find(quote)? + 2,
+        _ => 0,
+    };
+    text[search_from..]
+        .char_indices()
+        .find(|&(i, c)| {
+            c == ':'
+                && text[search_from + i + 1..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+        })
+        .map(|(i, _)| search_from + i)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +303,42 @@ name: test-skill
         let result = parse_frontmatter(content, &path);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_field_spans() {
+        let content = "---\nname: test-skill\ndescription: >\n  Folded text\n  over two lines\nmetadata:\n  author: me\n  tags: \"a, b\"\n---\n# Content\n";
+        let meta = parse_frontmatter(content, Path::new("SKILL.md")).unwrap();
+
+        let name = meta.spans.field("name").unwrap();
+        assert_eq!((name.line, name.column), (2, 1));
+        assert_eq!((name.value_line, name.value_column), (2, 7));
+        assert_eq!(name.end_column, 17);
+        assert_eq!(&content[name.range.clone()], "name: test-skill");
+
+        let description = meta.spans.field("description").unwrap();
+        assert_eq!((description.value_line, description.value_column), (3, 14));
+        assert_eq!((description.end_line, description.end_column), (5, 17));
+
+        let metadata = meta.spans.field("metadata").unwrap();
+        assert_eq!((metadata.value_line, metadata.value_column), (7, 3));
+        assert_eq!(metadata.end_line, 8);
+
+        let tags = meta.spans.metadata_key("tags").unwrap();
+        assert_eq!((tags.line, tags.column), (8, 3));
+        assert_eq!(&content[tags.range.clone()], "tags: \"a, b\"");
+        assert!(meta.spans.metadata_key("author").is_some());
+    }
+
+    #[test]
+    fn test_field_spans_crlf() {
+        let content = "---\r\nname: test-skill\r\ndescription: Something\r\n---\r\n";
+        let meta = parse_frontmatter(content, Path::new("SKILL.md")).unwrap();
+
+        let description = meta.spans.field("description").unwrap();
+        assert_eq!((description.line, description.value_column), (3, 14));
+        assert_eq!(
+            &content[description.range.clone()],
+            "description: Something"
+        );
+    }
 }
@@ -0,0 +1,384 @@
+//! Pluggable best practice rules
+//!
+//! Every best practice check is a [`Rule`]. The built-in AS rules register
+//! into [`RuleRegistry::builtin`]; downstream crates register their own rules
+//! next to them and hand the registry to
+//! [`Validator::with_registry`](crate::validator::Validator::with_registry).
+//! Custom rules then share rule selection, `[rules]` levels, suppression
+//! comments, baselines and every output format with the built-in ones; build
+//! the rule settings with
+//! [`ResolvedConfig::rule_settings`](crate::config::ResolvedConfig::rule_settings)
+//! against the same registry so the config file can name them.
+//!
+//! ```
+//! use madskills_core::rules::{Diagnostic, Rule, RuleRegistry};
+//! use madskills_core::validator::{SkillContext, ValidationConfig, Validator};
+//!
+//! struct NoRmRf;
+//!
+//! impl Rule for NoRmRf {
+//!     fn id(&self) -> &str {
+//!         "ACME001"
+//!     }
+//!
+//!     fn description(&self) -> &str {
+//!         "Scripts must not call rm -rf"
+//!     }
+//!
+//!     fn check(&self, ctx: &SkillContext) -> Vec<Diagnostic> {
+//!         ctx.scripts
+//!             .iter()
+//!             .filter_map(|path| ctx.read(path))
+//!             .filter_map(|file| {
+//!                 let offset = file.content.find("rm -rf")?;
+//!                 Some(Diagnostic::new("Avoid rm -rf", file.location(offset)))
+//!             })
+//!             .collect()
+//!     }
+//! }
+//!
+//! let mut registry = RuleRegistry::builtin();
+//! registry.register(NoRmRf).unwrap();
+//! let validator = Validator::new(ValidationConfig::default()).with_registry(registry);
+//! ```
+//!
+//! Simple pattern checks can also be declared in the config file instead; see
+//! [`RuleDefinition`].
+
+use crate::error::{CoreError, CoreResult};
+use crate::models::{
+    BestPracticeCode, BestPracticeViolation, CustomCode, Fix, RuleLevel, ViolationLocation,
+};
+use crate::validator::{RuleSettings, SkillContext};
+
+mod declarative;
+
+pub use declarative::{DeclarativeRule, RuleDefinition, RuleTarget};
+
+/// A best practice check
+pub trait Rule: Send + Sync {
+    /// Code findings are reported under, e.g. `ACME001`
+    fn id(&self) -> &str;
+
+    /// One-line summary of what the rule checks
+    fn description(&self) -> &str;
+
+    /// One-line suggestion for fixing a violation
+    fn help(&self) -> &str {
+        ""
+    }
+
+    /// Level used when the configuration does not set one
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Warning
+    }
+
+    /// Link to the rule's documentation
+    fn docs_url(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether findings may carry an automatic fix
+    fn fixable(&self) -> bool {
+        false
+    }
+
+    /// Findings for one skill
+    fn check(&self, ctx: &SkillContext) -> Vec<Diagnostic>;
+}
+
+/// A finding reported by a [`Rule`]
+///
+/// The validator adds the rule's code and the configured severity.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub message: String,
+    pub location: Option<ViolationLocation>,
+    /// Automatic fix, when the problem is mechanical
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>, location: Option<ViolationLocation>) -> Self {
+        Self {
+            message: message.into(),
+            location,
+            fix: None,
+        }
+    }
+
+    /// Attach an automatic fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Code a rule's findings are reported under
+///
+/// Ids naming a built-in rule map to it; any other id gets a custom code
+/// carrying the rule's documentation.
+pub(crate) fn code_for(rule: &impl Rule) -> BestPracticeCode {
+    let id = rule.id();
+    match BestPracticeCode::ALL
+        .into_iter()
+        .find(|code| code.as_str() == id)
+    {
+        Some(code) => code,
+        None => BestPracticeCode::custom(CustomCode {
+            id: id.to_string(),
+            description: rule.description().to_string(),
+            help: rule.help().to_string(),
+            docs_url: rule.docs_url(),
+        }),
+    }
+}
+
+/// A registered rule and the code its findings are reported under
+struct Entry {
+    code: BestPracticeCode,
+    rule: Box<dyn Rule>,
+}
+
+/// The rules a validator runs, in registration order
+#[derive(Default)]
+pub struct RuleRegistry {
+    entries: Vec<Entry>,
+}
+
+impl RuleRegistry {
+    /// A registry without any rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry holding the built-in rules (AS001-AS024)
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for rule in crate::validator::builtin_rules() {
+            registry
+                .register(rule)
+                .expect("built-in rule codes are unique");
+        }
+        registry
+    }
+
+    /// Add a rule, returning the code its findings are reported under
+    ///
+    /// Ids naming a built-in rule (`AS005`) report under that code; any
+    /// other id becomes a [custom code](BestPracticeCode::custom). Fails
+    /// when a rule with the same id is already registered.
+    pub fn register(&mut self, rule: impl Rule + 'static) -> CoreResult<BestPracticeCode> {
+        let id = rule.id();
+        if id.is_empty() {
+            return Err(CoreError::InvalidRule("rule id cannot be empty".into()));
+        }
+        let code = code_for(&rule);
+        if self.get(&code).is_some() {
+            return Err(CoreError::InvalidRule(format!(
+                "rule {} is already registered",
+                id
+            )));
+        }
+
+        self.entries.push(Entry {
+            code: code.clone(),
+            rule: Box::new(rule),
+        });
+        Ok(code)
+    }
+
+    /// Codes of the registered rules, in registration order
+    pub fn codes(&self) -> impl Iterator<Item = &BestPracticeCode> + '_ {
+        self.entries.iter().map(|entry| &entry.code)
+    }
+
+    /// The registered rules with their codes, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = (&BestPracticeCode, &dyn Rule)> + '_ {
+        self.entries
+            .iter()
+            .map(|entry| (&entry.code, entry.rule.as_ref()))
+    }
+
+    /// The rule reporting under `code`
+    pub fn get(&self, code: &BestPracticeCode) -> Option<&dyn Rule> {
+        self.entries
+            .iter()
+            .find(|entry| entry.code == *code)
+            .map(|entry| entry.rule.as_ref())
+    }
+
+    /// The registered code with id `id` (case-insensitive)
+    pub fn code(&self, id: &str) -> Option<&BestPracticeCode> {
+        self.codes()
+            .find(|code| code.as_str().eq_ignore_ascii_case(id))
+    }
+
+    /// Resolve a rule code against the registered rules
+    pub fn parse_code(&self, id: &str) -> Result<BestPracticeCode, String> {
+        self.code(id)
+            .cloned()
+            .ok_or_else(|| format!("unknown rule code '{}'", id))
+    }
+
+    /// Run every enabled rule on a skill
+    ///
+    /// Rules turned off by `settings` are skipped; findings get the rule's
+    /// configured severity (its default level when none is set).
+    pub fn check(
+        &self,
+        ctx: &SkillContext,
+        settings: &RuleSettings,
+        strict: bool,
+    ) -> Vec<BestPracticeViolation> {
+        let mut violations = Vec::new();
+
+        for entry in &self.entries {
+            let default = entry.rule.default_level();
+            let Some(severity) = settings.severity_with_default(&entry.code, default, strict)
+            else {
+                continue;
+            };
+            violations.extend(entry.rule.check(ctx).into_iter().map(|diagnostic| {
+                BestPracticeViolation {
+                    code: entry.code.clone(),
+                    severity,
+                    message: diagnostic.message,
+                    location: diagnostic.location,
+                    fix: diagnostic.fix,
+                }
+            }));
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Severity, Skill, SkillMetadata};
+    use crate::output::{OutputFormat, OutputFormatter};
+    use crate::validator::{ValidationConfig, Validator};
+    use std::collections::HashMap;
+
+    /// Flags every "TODO" in SKILL.md
+    struct NoTodo;
+
+    impl Rule for NoTodo {
+        fn id(&self) -> &str {
+            "TEST001"
+        }
+
+        fn description(&self) -> &str {
+            "SKILL.md must not contain TODOs"
+        }
+
+        fn help(&self) -> &str {
+            "Finish the section or remove the TODO"
+        }
+
+        fn check(&self, ctx: &SkillContext) -> Vec<Diagnostic> {
+            let Some(file) = &ctx.skill_md else {
+                return Vec::new();
+            };
+            file.content
+                .match_indices("TODO")
+                .map(|(offset, _)| Diagnostic::new("Unfinished TODO", file.location(offset)))
+                .collect()
+        }
+    }
+
+    fn skill_with_body(dir: &std::path::Path, body: &str) -> Skill {
+        let root = dir.join("processing-data");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(
+            root.join("SKILL.md"),
+            format!(
+                "---\nname: processing-data\ndescription: Processes data\n---\n\n{}",
+                body
+            ),
+        )
+        .unwrap();
+        Skill {
+            skill_md_path: root.join("SKILL.md"),
+            root,
+            metadata: SkillMetadata {
+                name: "processing-data".to_string(),
+                description: "Processes data".to_string(),
+                license: None,
+                compatibility: None,
+                allowed_tools: None,
+                metadata: HashMap::new(),
+                all_fields: Default::default(),
+                spans: Default::default(),
+            },
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates() {
+        let mut registry = RuleRegistry::builtin();
+        assert_eq!(registry.codes().count(), BestPracticeCode::ALL.len());
+        let code = registry.register(NoTodo).unwrap();
+        assert!(!code.is_builtin());
+        assert_eq!(code.as_str(), "TEST001");
+        assert_eq!(registry.parse_code("test001"), Ok(code));
+        assert!("TEST001".parse::<BestPracticeCode>().is_err());
+        assert!(matches!(
+            registry.register(NoTodo),
+            Err(CoreError::InvalidRule(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_rule_runs_through_validator() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let skill = skill_with_body(
+            dir.path(),
+            "# Processing\n\nTODO write steps\n<!-- madskills-disable-next-line TEST001 -->\nTODO later\n",
+        );
+        let mut registry = RuleRegistry::new();
+        let code = registry.register(NoTodo).unwrap();
+        let validator = Validator::new(ValidationConfig {
+            rules: RuleSettings {
+                levels: HashMap::from([(code.clone(), RuleLevel::Error)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_registry(registry);
+
+        let result = validator.validate_skill(&skill);
+
+        // The suppressed TODO is dropped, the other one is an error
+        assert_eq!(result.best_practice_violations.len(), 1);
+        let violation = &result.best_practice_violations[0];
+        assert_eq!(violation.code.as_str(), "TEST001");
+        assert_eq!(violation.severity, Severity::Error);
+        assert!(result.warnings.is_empty());
+
+        let output = OutputFormatter::new(OutputFormat::Text, false)
+            .format_validation_results(std::slice::from_ref(&result));
+        assert!(output.contains("error[TEST001]: Unfinished TODO\n"));
+        assert!(output.contains("  = help: Finish the section or remove the TODO\n"));
+    }
+
+    #[test]
+    fn test_disabled_custom_rule_is_skipped() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let skill = skill_with_body(dir.path(), "# Processing\n\nTODO write steps\n");
+        let mut registry = RuleRegistry::new();
+        let code = registry.register(NoTodo).unwrap();
+        let settings = RuleSettings {
+            ignore: vec!["TEST".to_string()],
+            ..Default::default()
+        };
+
+        let violations = registry.check(&SkillContext::new(&skill), &settings, false);
+
+        assert!(violations.is_empty());
+        assert!(!settings.is_selected(&code));
+    }
+}
@@ -0,0 +1,421 @@
+//! Rules declared in configuration (`[[custom-rules]]`)
+//!
+//! Each declaration names a target (a frontmatter field, the SKILL.md body,
+//! reference files or scripts) and one matcher:
+//!
+//! - `forbidden-pattern` reports every match of a regex
+//! - `required-pattern` reports targets the regex does not match
+//! - `required-heading` reports markdown targets without the heading
+
+use super::{Diagnostic, Rule};
+use crate::models::{BestPracticeCode, RuleLevel, SkillMetadata, ViolationLocation};
+use crate::toc;
+use crate::validator::{SkillContext, SourceFile};
+use regex::Regex;
+use std::path::Path;
+
+/// What a declared rule looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleTarget {
+    /// One frontmatter field, named by `field`
+    Frontmatter,
+    /// SKILL.md after the frontmatter
+    Body,
+    /// Markdown files other than SKILL.md
+    References,
+    /// Files with one of `extensions` (default: sh, py, js, ts)
+    Scripts,
+}
+
+impl RuleTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Frontmatter => "frontmatter",
+            Self::Body => "body",
+            Self::References => "references",
+            Self::Scripts => "scripts",
+        }
+    }
+}
+
+/// A `[[custom-rules]]` entry as written in a config file
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RuleDefinition {
+    /// Code findings are reported under, e.g. `ACME001`
+    pub id: String,
+    /// Finding message
+    pub message: String,
+    /// One-line summary of the rule (defaults to the message)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// One-line suggestion for fixing a violation
+    #[serde(default)]
+    pub help: Option<String>,
+    /// Level used when `[rules]` does not set one
+    #[serde(default = "default_severity")]
+    pub severity: RuleLevel,
+    pub target: RuleTarget,
+    /// Frontmatter field, for `target = "frontmatter"`
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Script extensions without the dot, for `target = "scripts"`
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Regex that must not match
+    #[serde(default)]
+    pub forbidden_pattern: Option<String>,
+    /// Regex that must match at least once
+    #[serde(default)]
+    pub required_pattern: Option<String>,
+    /// Heading text that must be present, optionally with its `#` level
+    #[serde(default)]
+    pub required_heading: Option<String>,
+}
+
+const fn default_severity() -> RuleLevel {
+    RuleLevel::Warning
+}
+
+/// Script extensions checked when a rule does not list any
+const DEFAULT_SCRIPT_EXTENSIONS: &[&str] = &["sh", "py", "js", "ts"];
+
+/// How a declared rule decides that a target is in violation
+#[derive(Debug)]
+enum Matcher {
+    Forbidden(Regex),
+    Required(Regex),
+    Heading { level: Option<usize>, text: String },
+}
+
+/// A [`Rule`] built from a [`RuleDefinition`]
+#[derive(Debug)]
+pub struct DeclarativeRule {
+    definition: RuleDefinition,
+    matcher: Matcher,
+}
+
+impl DeclarativeRule {
+    /// Check a definition and compile its matcher
+    pub fn new(definition: RuleDefinition) -> Result<Self, String> {
+        let id = &definition.id;
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "custom rule id '{}' must be non-empty and only contain letters, digits, '-' and '_'",
+                id
+            ));
+        }
+        if BestPracticeCode::ALL
+            .iter()
+            .any(|code| code.as_str().eq_ignore_ascii_case(id))
+        {
+            return Err(format!(
+                "custom rule id '{}' is taken by a built-in rule",
+                id
+            ));
+        }
+
+        let matcher = match (
+            &definition.forbidden_pattern,
+            &definition.required_pattern,
+            &definition.required_heading,
+        ) {
+            (Some(pattern), None, None) => Matcher::Forbidden(compile(id, pattern)?),
+            (None, Some(pattern), None) => Matcher::Required(compile(id, pattern)?),
+            (None, None, Some(heading)) => parse_heading(heading),
+            _ => {
+                return Err(format!(
+                    "custom rule {} needs exactly one of forbidden-pattern, required-pattern or required-heading",
+                    id
+                ));
+            }
+        };
+
+        let target = definition.target;
+        match (target, &definition.field) {
+            (RuleTarget::Frontmatter, None) => {
+                return Err(format!(
+                    "custom rule {} targets frontmatter but names no field",
+                    id
+                ));
+            }
+            (RuleTarget::Frontmatter, Some(_)) | (_, None) => {}
+            (_, Some(_)) => {
+                return Err(format!(
+                    "custom rule {}: field only applies to target = \"frontmatter\"",
+                    id
+                ));
+            }
+        }
+        if target != RuleTarget::Scripts && !definition.extensions.is_empty() {
+            return Err(format!(
+                "custom rule {}: extensions only apply to target = \"scripts\"",
+                id
+            ));
+        }
+        if matches!(matcher, Matcher::Heading { .. })
+            && !matches!(target, RuleTarget::Body | RuleTarget::References)
+        {
+            return Err(format!(
+                "custom rule {}: required-heading only applies to body and references",
+                id
+            ));
+        }
+
+        Ok(Self {
+            definition,
+            matcher,
+        })
+    }
+
+    /// The definition this rule was built from
+    pub fn definition(&self) -> &RuleDefinition {
+        &self.definition
+    }
+
+    fn check_frontmatter(&self, metadata: &SkillMetadata, field: &str) -> Vec<Diagnostic> {
+        let value = frontmatter_value(metadata, field);
+        let violated = match (&self.matcher, value) {
+            (Matcher::Forbidden(regex), Some(value)) => regex.is_match(value),
+            (Matcher::Required(regex), value) => !value.is_some_and(|v| regex.is_match(v)),
+            _ => false,
+        };
+        if violated {
+            vec![self.diagnostic(Some(ViolationLocation::frontmatter(metadata, field)))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Findings for `file`, looking only at its content from byte `start`
+    fn check_file(&self, file: &SourceFile, start: usize) -> Vec<Diagnostic> {
+        let text = &file.content[start..];
+        match &self.matcher {
+            Matcher::Forbidden(regex) => regex
+                .find_iter(text)
+                .map(|m| self.diagnostic(file.location(start + m.start())))
+                .collect(),
+            Matcher::Required(regex) if !regex.is_match(text) => {
+                vec![self.diagnostic(file.location(start))]
+            }
+            Matcher::Heading {
+                level,
+                text: wanted,
+            } => {
+                let found = toc::headings(&file.content).iter().any(|heading| {
+                    heading.offset >= start
+                        && level.is_none_or(|level| heading.level == level)
+                        && heading.text.eq_ignore_ascii_case(wanted)
+                });
+                if found {
+                    Vec::new()
+                } else {
+                    vec![self.diagnostic(file.location(start))]
+                }
+            }
+            Matcher::Required(_) => Vec::new(),
+        }
+    }
+
+    fn diagnostic(&self, location: Option<ViolationLocation>) -> Diagnostic {
+        Diagnostic::new(self.definition.message.clone(), location)
+    }
+
+    fn is_script(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+        if self.definition.extensions.is_empty() {
+            DEFAULT_SCRIPT_EXTENSIONS.contains(&extension)
+        } else {
+            self.definition
+                .extensions
+                .iter()
+                .any(|e| e.trim_start_matches('.') == extension)
+        }
+    }
+}
+
+impl Rule for DeclarativeRule {
+    fn id(&self) -> &str {
+        &self.definition.id
+    }
+
+    fn description(&self) -> &str {
+        self.definition
+            .description
+            .as_deref()
+            .unwrap_or(&self.definition.message)
+    }
+
+    fn help(&self) -> &str {
+        self.definition.help.as_deref().unwrap_or_default()
+    }
+
+    fn default_level(&self) -> RuleLevel {
+        self.definition.severity
+    }
+
+    fn check(&self, ctx: &SkillContext) -> Vec<Diagnostic> {
+        match self.definition.target {
+            RuleTarget::Frontmatter => {
+                let field = self.definition.field.as_deref().unwrap_or_default();
+                self.check_frontmatter(&ctx.skill.metadata, field)
+            }
+            RuleTarget::Body => {
+                let Some(file) = &ctx.skill_md else {
+                    return Vec::new();
+                };
+                let start = ctx
+                    .body_offsets
+                    .first()
+                    .copied()
+                    .unwrap_or(file.content.len());
+                self.check_file(file, start)
+            }
+            RuleTarget::References => ctx
+                .all_files()
+                .iter()
+                .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+                .filter(|p| **p != ctx.skill.skill_md_path)
+                .filter_map(|p| ctx.read(p))
+                .flat_map(|file| self.check_file(&file, 0))
+                .collect(),
+            RuleTarget::Scripts => ctx
+                .all_files()
+                .iter()
+                .filter(|p| self.is_script(p))
+                .filter_map(|p| ctx.read(p))
+                .flat_map(|file| self.check_file(&file, 0))
+                .collect(),
+        }
+    }
+}
+
+fn compile(id: &str, pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("custom rule {}: invalid pattern: {}", id, e))
+}
+
+/// `## Safety` requires a level-2 heading; `Safety` accepts any level
+fn parse_heading(heading: &str) -> Matcher {
+    let trimmed = heading.trim();
+    let hashes = trimmed.len() - trimmed.trim_start_matches('#').len();
+    Matcher::Heading {
+        level: (hashes > 0).then_some(hashes),
+        text: trimmed[hashes..].trim().to_string(),
+    }
+}
+
+/// A frontmatter field's value; `metadata.<key>` reads the metadata map
+fn frontmatter_value<'a>(metadata: &'a SkillMetadata, field: &str) -> Option<&'a str> {
+    match field {
+        "name" => Some(&metadata.name),
+        "description" => Some(&metadata.description),
+        "license" => metadata.license.as_deref(),
+        "compatibility" => metadata.compatibility.as_deref(),
+        "allowed-tools" => metadata.allowed_tools.as_deref(),
+        _ => field
+            .strip_prefix("metadata.")
+            .and_then(|key| metadata.metadata.get(key))
+            .map(String::as_str),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(toml: &str) -> RuleDefinition {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_definition_errors() {
+        let error = |toml: &str| DeclarativeRule::new(definition(toml)).unwrap_err();
+
+        assert!(
+            error("id = \"AS005\"\nmessage = \"m\"\ntarget = \"body\"\nforbidden-pattern = \"x\"")
+                .contains("built-in")
+        );
+        assert!(
+            error("id = \"X1\"\nmessage = \"m\"\ntarget = \"body\"").contains("exactly one of")
+        );
+        assert!(
+            error(
+                "id = \"X1\"\nmessage = \"m\"\ntarget = \"frontmatter\"\nrequired-pattern = \"x\""
+            )
+            .contains("names no field")
+        );
+        assert!(
+            error(
+                "id = \"X1\"\nmessage = \"m\"\ntarget = \"scripts\"\nrequired-heading = \"Usage\""
+            )
+            .contains("required-heading")
+        );
+        assert!(
+            error("id = \"X1\"\nmessage = \"m\"\ntarget = \"body\"\nforbidden-pattern = \"(\"")
+                .contains("invalid pattern")
+        );
+    }
+
+    #[test]
+    fn test_parse_heading() {
+        match parse_heading("## Safety ") {
+            Matcher::Heading { level, text } => {
+                assert_eq!(level, Some(2));
+                assert_eq!(text, "Safety");
+            }
+            _ => unreachable!(),
+        }
+        match parse_heading("Safety") {
+            Matcher::Heading { level, .. } => assert_eq!(level, None),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_heading_matcher_ignores_frontmatter() {
+        let rule = DeclarativeRule::new(definition(
+            "id = \"X1\"\nmessage = \"Add a Safety section\"\ntarget = \"body\"\nrequired-heading = \"## Safety\"",
+        ))
+        .unwrap();
+        let content = "---\nname: x\n---\n\n# Title\n\n### Safety\n";
+        let file = SourceFile::new("SKILL.md".into(), content.to_string());
+        let start = content.find("# Title").unwrap();
+
+        let diagnostics = rule.check_file(&file, start);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Add a Safety section");
+    }
+
+    #[test]
+    fn test_metadata_field_findings_have_locations() {
+        let rule = DeclarativeRule::new(definition(
+            "id = \"X1\"\nmessage = \"Owner must be a team\"\ntarget = \"frontmatter\"\nfield = \"metadata.owner\"\nrequired-pattern = \"^team-\"",
+        ))
+        .unwrap();
+        let content = "---\nname: demo\ndescription: Does things\nmetadata:\n  owner: alice\n---\n";
+        let metadata = crate::parser::parse_frontmatter(content, Path::new("SKILL.md")).unwrap();
+
+        let diagnostics = rule.check_frontmatter(&metadata, "metadata.owner");
+
+        assert_eq!(diagnostics.len(), 1);
+        let Some(ViolationLocation::Frontmatter {
+            field,
+            line,
+            column,
+            ..
+        }) = &diagnostics[0].location
+        else {
+            panic!("expected a frontmatter location");
+        };
+        assert_eq!(field, "metadata.owner");
+        assert_eq!((*line, *column), (Some(5), Some(10)));
+    }
+}
@@ -0,0 +1,627 @@
+//! Inline suppression comments for best practice violations
+//!
+//! Markdown files accept HTML comments:
+//!
+//! - `<!-- madskills-disable-next-line AS010 -->` silences the following line
+//! - `<!-- madskills-disable AS012 -->` ... `<!-- madskills-enable -->` silences
+//!   a region (to the end of the file when never re-enabled); naming codes in
+//!   `madskills-enable` re-enables only those codes
+//!
+//! Directives in fenced code or inline code spans are ignored.
+//!
+//! Scripts, and other files custom rules report on, accept
+//! `# madskills: ignore[AS018]` or `// madskills: ignore[AS018]`,
+//! applying to their own line, or to the next line when the comment stands alone.
+//! SKILL.md frontmatter accepts the `#` form as a YAML comment.
+//!
+//! Omitting the codes silences every rule. Findings reported for a whole file
+//! (without a line) are silenced by any directive in that file naming their code.
+//! Directives that silence nothing are reported as warnings.
+
+use crate::models::{
+    BestPracticeCode, BestPracticeViolation, RuleLevel, Skill, SourceLocation, ValidationResult,
+    ValidationWarning, ValidationWarningKind, ViolationLocation,
+};
+use crate::rules::RuleRegistry;
+use crate::validator::RuleSettings;
+use crate::validator::SkillContext;
+use crate::validator::links::prose_lines;
+use regex::Regex;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static MARKDOWN_DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<!--\s*madskills-(disable-next-line|disable|enable)\b(.*?)-->").unwrap()
+});
+
+static SCRIPT_DIRECTIVE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:#|//)\s*madskills:\s*ignore(?:\[([^\]]*)\])?").unwrap());
+
+/// A parsed suppression directive
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    /// File containing the directive
+    pub file: PathBuf,
+    /// Line of the directive (1-indexed)
+    pub line: usize,
+    /// Rule codes named by the directive (empty means all rules)
+    pub codes: Vec<String>,
+    /// First silenced line
+    start: usize,
+    /// Last silenced line per code (`None` means end of file)
+    end: Vec<Option<usize>>,
+    /// Per-code usage (a single flag for blanket directives)
+    used: Vec<bool>,
+}
+
+impl Suppression {
+    fn new(file: &Path, line: usize, codes: Vec<String>, start: usize, end: Option<usize>) -> Self {
+        let slots = codes.len().max(1);
+        Self {
+            file: file.to_path_buf(),
+            line,
+            codes,
+            start,
+            end: vec![end; slots],
+            used: vec![false; slots],
+        }
+    }
+
+    /// Try to silence a violation, recording the use
+    fn silences(&mut self, code: &BestPracticeCode, line: Option<usize>) -> bool {
+        let index = if self.codes.is_empty() {
+            // Blanket directives only apply by line
+            if line.is_none() {
+                return false;
+            }
+            Some(0)
+        } else {
+            self.codes
+                .iter()
+                .position(|c| c.eq_ignore_ascii_case(code.as_str()))
+        };
+
+        let Some(index) = index else {
+            return false;
+        };
+        let covered = match line {
+            Some(l) => l >= self.start && self.end[index].is_none_or(|end| l <= end),
+            None => true,
+        };
+        if covered {
+            self.used[index] = true;
+        }
+        covered
+    }
+}
+
+/// Parse suppression comments from markdown content
+pub fn parse_markdown_suppressions(file: &Path, content: &str) -> Vec<Suppression> {
+    let mut suppressions: Vec<Suppression> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    let mut line = 1;
+    let mut counted = 0;
+
+    for prose in prose_lines(content) {
+        line += content[counted..prose.offset].matches('\n').count();
+        counted = prose.offset;
+
+        // YAML has no HTML comments, so frontmatter takes `# madskills: ignore`
+        if prose.frontmatter {
+            suppressions.extend(script_suppression(file, line, &prose.text));
+            continue;
+        }
+
+        for cap in MARKDOWN_DIRECTIVE.captures_iter(&prose.text) {
+            let codes = parse_codes(&cap[2]);
+            match &cap[1] {
+                "disable-next-line" => {
+                    suppressions.push(Suppression::new(
+                        file,
+                        line,
+                        codes,
+                        line + 1,
+                        Some(line + 1),
+                    ));
+                }
+                "disable" => {
+                    open.push(suppressions.len());
+                    suppressions.push(Suppression::new(file, line, codes, line + 1, None));
+                }
+                _ => {
+                    // enable: end the named codes of open regions (everything
+                    // when no codes are given); regions covering all rules
+                    // only end with a bare enable
+                    open.retain(|&idx| {
+                        let region = &mut suppressions[idx];
+                        for (i, end) in region.end.iter_mut().enumerate() {
+                            let ends = if region.codes.is_empty() {
+                                codes.is_empty()
+                            } else {
+                                codes.is_empty() || codes.contains(&region.codes[i])
+                            };
+                            if ends && end.is_none() {
+                                *end = Some(line);
+                            }
+                        }
+                        region.end.iter().any(Option::is_none)
+                    });
+                }
+            }
+        }
+    }
+
+    suppressions
+}
+
+/// Parse `madskills: ignore[...]` comments from script content
+pub fn parse_script_suppressions(file: &Path, content: &str) -> Vec<Suppression> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, text)| script_suppression(file, i + 1, text))
+        .collect()
+}
+
+/// A `madskills: ignore[...]` directive on `line`, if it has one
+fn script_suppression(file: &Path, line: usize, text: &str) -> Option<Suppression> {
+    let cap = SCRIPT_DIRECTIVE.captures(text)?;
+    let codes = cap
+        .get(1)
+        .map(|m| parse_codes(m.as_str()))
+        .unwrap_or_default();
+    // A comment on its own line applies to the next line
+    let standalone = text.trim_start().starts_with(&cap[0][..1]);
+    let target = if standalone { line + 1 } else { line };
+    Some(Suppression::new(file, line, codes, target, Some(target)))
+}
+
+/// Remove a `madskills: ignore[...]` directive from a script line
+///
+/// Rules that look for explanatory comments use this so that a suppression
+/// directive does not count as documentation.
+pub fn strip_script_directive(line: &str) -> Cow<'_, str> {
+    SCRIPT_DIRECTIVE.replace_all(line, "")
+}
+
+fn parse_codes(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_ascii_uppercase())
+        .collect()
+}
+
+/// Collect suppression directives from every markdown file and script in a skill
+pub fn collect_suppressions(skill: &Skill) -> Vec<Suppression> {
+    collect_context_suppressions(&SkillContext::new(skill))
+}
+
+fn collect_context_suppressions(ctx: &SkillContext) -> Vec<Suppression> {
+    let mut suppressions = Vec::new();
+
+    for path in ctx.all_files() {
+        if !is_markdown(path) {
+            continue;
+        }
+        if let Some(file) = ctx.read(path) {
+            suppressions.extend(parse_markdown_suppressions(path, &file.content));
+        }
+    }
+
+    for script in &ctx.scripts {
+        if let Some(file) = ctx.read(script) {
+            suppressions.extend(parse_script_suppressions(script, &file.content));
+        }
+    }
+
+    suppressions
+}
+
+/// Drop suppressed best practice violations and report unused directives
+///
+/// Codes named by directives are resolved against `registry`.
+pub fn apply_suppressions(
+    skill: &Skill,
+    registry: &RuleRegistry,
+    rules: &RuleSettings,
+    result: &mut ValidationResult,
+) {
+    apply_context_suppressions(&SkillContext::new(skill), registry, rules, result);
+}
+
+/// [`apply_suppressions`] for a skill whose files are already loaded
+pub(crate) fn apply_context_suppressions(
+    ctx: &SkillContext,
+    registry: &RuleRegistry,
+    rules: &RuleSettings,
+    result: &mut ValidationResult,
+) {
+    let mut suppressions = collect_context_suppressions(ctx);
+
+    // Custom rules may report on files outside the usual scripts
+    let mut other_files: Vec<PathBuf> = result
+        .best_practice_violations
+        .iter()
+        .map(|violation| violation_position(ctx.skill, violation).0)
+        .filter(|path| !is_markdown(path) && !ctx.scripts.contains(path))
+        .collect();
+    other_files.sort();
+    other_files.dedup();
+    for path in &other_files {
+        if let Some(file) = ctx.read(path) {
+            suppressions.extend(parse_script_suppressions(path, &file.content));
+        }
+    }
+
+    if suppressions.is_empty() {
+        return;
+    }
+
+    result.best_practice_violations.retain(|violation| {
+        let (file, line) = violation_position(ctx.skill, violation);
+        // Every matching directive is marked used, so evaluate them all
+        let mut silenced = false;
+        for suppression in suppressions.iter_mut().filter(|s| s.file == file) {
+            silenced |= suppression.silences(&violation.code, line);
+        }
+        !silenced
+    });
+
+    for suppression in &suppressions {
+        for warning in unused_warnings(suppression, registry, rules) {
+            result.warnings.push(warning);
+        }
+    }
+}
+
+fn unused_warnings(
+    suppression: &Suppression,
+    registry: &RuleRegistry,
+    rules: &RuleSettings,
+) -> Vec<ValidationWarning> {
+    let location = Some(SourceLocation {
+        file: suppression.file.clone(),
+        line: suppression.line,
+        column: 1,
+        end_line: None,
+        end_column: None,
+    });
+
+    if suppression.codes.is_empty() {
+        if suppression.used[0] {
+            return Vec::new();
+        }
+        return vec![ValidationWarning {
+            kind: ValidationWarningKind::UnusedSuppression,
+            message: "Unused suppression comment (no violations silenced)".into(),
+            location,
+            fixable: false,
+        }];
+    }
+
+    suppression
+        .codes
+        .iter()
+        .zip(&suppression.used)
+        .filter(|(_, used)| !**used)
+        .filter_map(|(code, _)| {
+            let message = match registry.code(code) {
+                Some(parsed) if !is_enabled(parsed, registry, rules) => return None,
+                Some(_) => format!("Unused suppression for {} (no violations silenced)", code),
+                None => format!("Suppression names unknown rule '{}'", code),
+            };
+            Some(ValidationWarning {
+                kind: ValidationWarningKind::UnusedSuppression,
+                message,
+                location: location.clone(),
+                fixable: false,
+            })
+        })
+        .collect()
+}
+
+/// Whether `code` runs at all, given its configured or default level
+fn is_enabled(code: &BestPracticeCode, registry: &RuleRegistry, rules: &RuleSettings) -> bool {
+    let default = registry
+        .get(code)
+        .map_or(RuleLevel::Warning, |rule| rule.default_level());
+    rules.severity_with_default(code, default, false).is_some()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("md")
+}
+
+/// File and line a violation is reported against
+fn violation_position(
+    skill: &Skill,
+    violation: &BestPracticeViolation,
+) -> (PathBuf, Option<usize>) {
+    match &violation.location {
+        Some(ViolationLocation::File { path, line, .. })
+        | Some(ViolationLocation::Script { path, line }) => (path.clone(), *line),
+        Some(ViolationLocation::SkillBody { line })
+        | Some(ViolationLocation::Frontmatter {
+            line: Some(line), ..
+        }) => (skill.skill_md_path.clone(), Some(*line)),
+        Some(ViolationLocation::Frontmatter { line: None, .. }) | None => {
+            (skill.skill_md_path.clone(), None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+
+    fn violation(
+        code: BestPracticeCode,
+        path: &Path,
+        line: Option<usize>,
+    ) -> BestPracticeViolation {
+        BestPracticeViolation {
+            code,
+            severity: Severity::Warning,
+            message: "test".into(),
+            location: Some(ViolationLocation::File {
+                path: path.to_path_buf(),
+                line,
+                column: None,
+            }),
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_next_line() {
+        let path = Path::new("SKILL.md");
+        let content = "# Title\n<!-- madskills-disable-next-line AS010 -->\nAs of 2024-01-01\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert_eq!(s.len(), 1);
+        assert_eq!(s[0].codes, vec!["AS010"]);
+        assert!(s[0].silences(&BestPracticeCode::AS010, Some(3)));
+        assert!(!s[0].silences(&BestPracticeCode::AS010, Some(4)));
+        assert!(!s[0].silences(&BestPracticeCode::AS012, Some(3)));
+    }
+
+    #[test]
+    fn test_parse_region() {
+        let path = Path::new("SKILL.md");
+        let content =
+            "a\n<!-- madskills-disable AS012, AS010 -->\nb\nc\n<!-- madskills-enable -->\nd\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert_eq!(s.len(), 1);
+        assert!(s[0].silences(&BestPracticeCode::AS012, Some(3)));
+        assert!(s[0].silences(&BestPracticeCode::AS010, Some(4)));
+        assert!(!s[0].silences(&BestPracticeCode::AS012, Some(6)));
+    }
+
+    #[test]
+    fn test_enable_ends_only_named_codes() {
+        let path = Path::new("SKILL.md");
+        let content = "a\n<!-- madskills-disable AS012, AS010 -->\nb\n<!-- madskills-enable AS010 -->\nc\n<!-- madskills-enable AS012 -->\nd\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert_eq!(s.len(), 1);
+        assert!(s[0].silences(&BestPracticeCode::AS010, Some(3)));
+        assert!(!s[0].silences(&BestPracticeCode::AS010, Some(5)));
+        assert!(s[0].silences(&BestPracticeCode::AS012, Some(5)));
+        assert!(!s[0].silences(&BestPracticeCode::AS012, Some(7)));
+    }
+
+    #[test]
+    fn test_named_enable_keeps_blanket_region_open() {
+        let path = Path::new("SKILL.md");
+        let content = "<!-- madskills-disable -->\nb\n<!-- madskills-enable AS012 -->\nc\n<!-- madskills-enable -->\nd\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert!(s[0].silences(&BestPracticeCode::AS012, Some(4)));
+        assert!(!s[0].silences(&BestPracticeCode::AS012, Some(6)));
+    }
+
+    #[test]
+    fn test_unterminated_region_runs_to_end() {
+        let path = Path::new("SKILL.md");
+        let content = "<!-- madskills-disable AS012 -->\nb\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert!(s[0].silences(&BestPracticeCode::AS012, Some(1000)));
+    }
+
+    #[test]
+    fn test_directives_in_code_fences_ignored() {
+        let path = Path::new("SKILL.md");
+        let content = "```markdown\n<!-- madskills-disable AS012 -->\n```\n";
+        assert!(parse_markdown_suppressions(path, content).is_empty());
+    }
+
+    #[test]
+    fn test_directives_in_code_spans_ignored() {
+        let path = Path::new("SKILL.md");
+        let content = "Write `<!-- madskills-disable AS010 -->` to silence AS010.\nIn 2024\n";
+        assert!(parse_markdown_suppressions(path, content).is_empty());
+    }
+
+    #[test]
+    fn test_frontmatter_directives() {
+        let path = Path::new("SKILL.md");
+        let content = "---\nname: demo\n# madskills: ignore[AS002]\ndescription: Helps\n---\n\n<!-- madskills-disable-next-line AS010 -->\nIn 2024\n";
+        let mut s = parse_markdown_suppressions(path, content);
+
+        assert_eq!(s.len(), 2);
+        assert!(s[0].silences(&BestPracticeCode::AS002, Some(4)));
+        assert!(s[1].silences(&BestPracticeCode::AS010, Some(8)));
+    }
+
+    #[test]
+    fn test_parse_script_directives() {
+        let path = Path::new("run.py");
+        let content =
+            "# madskills: ignore[AS018]\nTIMEOUT = 30\nMAX = 5  # madskills: ignore[AS018]\n";
+        let mut s = parse_script_suppressions(path, content);
+
+        assert_eq!(s.len(), 2);
+        assert!(s[0].silences(&BestPracticeCode::AS018, Some(2)));
+        assert!(s[1].silences(&BestPracticeCode::AS018, Some(3)));
+
+        let js = parse_script_suppressions(Path::new("run.js"), "// madskills: ignore\nx\n");
+        assert_eq!(js.len(), 1);
+        assert!(js[0].codes.is_empty());
+    }
+
+    #[test]
+    fn test_strip_script_directive() {
+        assert_eq!(
+            strip_script_directive("MAX = 5  # madskills: ignore[AS018]").trim_end(),
+            "MAX = 5"
+        );
+        assert_eq!(
+            strip_script_directive("MAX = 5  # seconds"),
+            "MAX = 5  # seconds"
+        );
+    }
+
+    #[test]
+    fn test_lineless_findings_need_named_code() {
+        let path = Path::new("SKILL.md");
+        let mut named =
+            parse_markdown_suppressions(path, "<!-- madskills-disable-next-line AS013 -->\n");
+        assert!(named[0].silences(&BestPracticeCode::AS013, None));
+
+        let mut blanket =
+            parse_markdown_suppressions(path, "<!-- madskills-disable-next-line -->\n");
+        assert!(!blanket[0].silences(&BestPracticeCode::AS013, None));
+    }
+
+    #[test]
+    fn test_apply_suppressions_reports_unused() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().join("test-skill");
+        std::fs::create_dir(&root).unwrap();
+        let skill_md = root.join("SKILL.md");
+        std::fs::write(
+            &skill_md,
+            "---\nname: test-skill\n---\n<!-- madskills-disable-next-line AS010 -->\nIn 2024\n<!-- madskills-disable-next-line AS005 -->\nok\n",
+        )
+        .unwrap();
+
+        let skill = Skill {
+            root: root.clone(),
+            skill_md_path: skill_md.clone(),
+            metadata: crate::models::SkillMetadata {
+                name: "test-skill".into(),
+                description: "Test".into(),
+                license: None,
+                compatibility: None,
+                allowed_tools: None,
+                metadata: Default::default(),
+                all_fields: Default::default(),
+                spans: Default::default(),
+            },
+            source: None,
+        };
+
+        let mut result = ValidationResult::new(root);
+        result.best_practice_violations.push(violation(
+            BestPracticeCode::AS010,
+            &skill_md,
+            Some(5),
+        ));
+
+        apply_suppressions(
+            &skill,
+            &RuleRegistry::builtin(),
+            &RuleSettings::default(),
+            &mut result,
+        );
+
+        assert!(result.best_practice_violations.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(
+            result.warnings[0].kind,
+            ValidationWarningKind::UnusedSuppression
+        );
+        assert!(result.warnings[0].message.contains("AS005"));
+        assert_eq!(result.warnings[0].location.as_ref().unwrap().line, 6);
+    }
+
+    #[test]
+    fn test_frontmatter_findings_match_by_line() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().join("test-skill");
+        std::fs::create_dir(&root).unwrap();
+        let skill_md = root.join("SKILL.md");
+        std::fs::write(
+            &skill_md,
+            "---\nname: test-skill\ndescription: Helps\n---\n<!-- madskills-disable-next-line AS002 -->\nVague line\n",
+        )
+        .unwrap();
+        let skill = Skill {
+            root: root.clone(),
+            skill_md_path: skill_md.clone(),
+            metadata: crate::models::SkillMetadata {
+                name: "test-skill".into(),
+                description: "Helps".into(),
+                license: None,
+                compatibility: None,
+                allowed_tools: None,
+                metadata: Default::default(),
+                all_fields: Default::default(),
+                spans: Default::default(),
+            },
+            source: None,
+        };
+
+        let mut result = ValidationResult::new(root);
+        result.best_practice_violations.push(BestPracticeViolation {
+            code: BestPracticeCode::AS002,
+            severity: Severity::Warning,
+            message: "Vague description".into(),
+            location: Some(ViolationLocation::Frontmatter {
+                field: "description".into(),
+                line: Some(3),
+                column: Some(14),
+                end_line: Some(3),
+                end_column: Some(19),
+            }),
+            fix: None,
+        });
+
+        apply_suppressions(
+            &skill,
+            &RuleRegistry::builtin(),
+            &RuleSettings::default(),
+            &mut result,
+        );
+
+        assert_eq!(result.best_practice_violations.len(), 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].location.as_ref().unwrap().line, 5);
+    }
+
+    #[test]
+    fn test_unused_suppression_for_disabled_rule_not_reported() {
+        let path = Path::new("SKILL.md");
+        let s = parse_markdown_suppressions(path, "<!-- madskills-disable-next-line AS005 -->\n");
+        let rules = RuleSettings {
+            ignore: vec!["AS005".into()],
+            ..Default::default()
+        };
+        assert!(unused_warnings(&s[0], &RuleRegistry::builtin(), &rules).is_empty());
+    }
+
+    #[test]
+    fn test_unused_suppression_for_rule_turned_off_not_reported() {
+        let path = Path::new("SKILL.md");
+        let s = parse_markdown_suppressions(path, "<!-- madskills-disable-next-line AS005 -->\n");
+        let rules = RuleSettings {
+            levels: [(BestPracticeCode::AS005, RuleLevel::Off)].into(),
+            ..Default::default()
+        };
+        assert!(unused_warnings(&s[0], &RuleRegistry::builtin(), &rules).is_empty());
+    }
+}
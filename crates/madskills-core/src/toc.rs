@@ -0,0 +1,310 @@
+//! Table of contents generation for skill markdown files
+//!
+//! Generated tables of contents live between marker comments so they can be
+//! refreshed without touching hand-written content:
+//!
+//! ```markdown
+//! <!-- madskills-toc-start -->
+//!
+//! ## Contents
+//!
+//! - [Setup](#setup)
+//!   - [Requirements](#requirements)
+//!
+//! <!-- madskills-toc-end -->
+//! ```
+
+use crate::models::TextEdit;
+use crate::validator::helpers::{count_lines, has_table_of_contents};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Comment opening a generated table of contents
+pub const TOC_START: &str = "<!-- madskills-toc-start -->";
+/// Comment closing a generated table of contents
+pub const TOC_END: &str = "<!-- madskills-toc-end -->";
+/// Files longer than this need a table of contents (AS008)
+pub const TOC_MIN_LINES: usize = 100;
+
+/// Heading levels listed in a generated table of contents
+const TOC_LEVELS: std::ops::RangeInclusive<usize> = 2..=3;
+
+/// Headings that name a table of contents rather than a section
+const TOC_TITLES: [&str; 3] = ["table of contents", "contents", "toc"];
+
+static ATX_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$").unwrap());
+static INLINE_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap());
+
+/// A markdown heading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 for `#`, 2 for `##`, ...
+    pub level: usize,
+    /// Heading text with inline links reduced to their text
+    pub text: String,
+    /// GitHub anchor, unique within the document
+    pub anchor: String,
+    /// Byte offset of the heading line
+    pub offset: usize,
+}
+
+impl Heading {
+    /// Whether this heading titles a table of contents
+    pub fn is_toc_title(&self) -> bool {
+        let lower = self.text.to_lowercase();
+        TOC_TITLES.contains(&lower.as_str())
+    }
+}
+
+/// GitHub-style anchor for heading text
+///
+/// Lowercases, drops punctuation (keeping letters and digits in any script,
+/// `-` and `_`) and turns spaces into hyphens.
+pub fn github_anchor(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// ATX headings outside code fences and frontmatter, with anchors made
+/// unique the way GitHub does (`setup`, `setup-1`, `setup-2`, ...)
+pub fn headings(content: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut fence: Option<&str> = None;
+    let mut in_frontmatter = false;
+    let mut offset = 0;
+
+    for (idx, raw) in content.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+
+        if idx == 0 && line == "---" {
+            in_frontmatter = true;
+            continue;
+        }
+        if in_frontmatter {
+            in_frontmatter = line != "---";
+            continue;
+        }
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+
+        let Some(cap) = ATX_HEADING.captures(line) else {
+            continue;
+        };
+        let text = INLINE_LINK.replace_all(&cap[2], "$1").trim().to_string();
+        let base = github_anchor(&text);
+        let anchor = match seen.get_mut(&base) {
+            Some(count) => {
+                *count += 1;
+                format!("{}-{}", base, count)
+            }
+            None => {
+                seen.insert(base.clone(), 0);
+                base
+            }
+        };
+        headings.push(Heading {
+            level: cap[1].len(),
+            text,
+            anchor,
+            offset: start,
+        });
+    }
+
+    headings
+}
+
+/// Nested markdown list linking to each heading
+pub fn generate_toc(headings: &[Heading]) -> String {
+    let top = *TOC_LEVELS.start();
+    headings
+        .iter()
+        .filter(|h| TOC_LEVELS.contains(&h.level) && !h.is_toc_title())
+        .map(|h| {
+            format!(
+                "{}- [{}](#{})\n",
+                "  ".repeat(h.level - top),
+                h.text,
+                h.anchor
+            )
+        })
+        .collect()
+}
+
+/// Byte range of an existing generated table of contents, including markers
+fn marked_region(content: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(TOC_START)?;
+    let end = start + content[start..].find(TOC_END)? + TOC_END.len();
+    Some(start..end)
+}
+
+/// Edit that inserts a table of contents, or refreshes the generated one
+///
+/// Returns `None` when the generated table is already current, or when there
+/// is nothing to list.
+pub fn toc_edit(content: &str) -> Option<TextEdit> {
+    let region = marked_region(content);
+    let all = headings(content);
+    let listed: Vec<Heading> = all
+        .iter()
+        .filter(|h| !region.as_ref().is_some_and(|r| r.contains(&h.offset)))
+        .cloned()
+        .collect();
+    let list = generate_toc(&listed);
+    if list.is_empty() && region.is_none() {
+        return None;
+    }
+    let block = format!("{}\n\n## Contents\n\n{}\n{}", TOC_START, list, TOC_END);
+
+    if let Some(range) = region {
+        return (content[range.clone()] != block).then_some(TextEdit {
+            range,
+            replacement: block,
+        });
+    }
+
+    // Insert below the title when the document opens with one, else at the top
+    match listed.first() {
+        Some(title) if title.level == 1 => {
+            let at = content[title.offset..]
+                .find('\n')
+                .map_or(content.len(), |i| title.offset + i + 1);
+            let lead = if content[..at].ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+            Some(TextEdit::insert(at, format!("{}\n{}\n", lead, block)))
+        }
+        _ => {
+            let at = listed
+                .first()
+                .map_or(0, |h| h.offset.min(body_start(content)));
+            Some(TextEdit::insert(at, format!("{}\n\n", block)))
+        }
+    }
+}
+
+/// Offset just past the frontmatter, or 0 without one
+fn body_start(content: &str) -> usize {
+    if !content.starts_with("---\n") && !content.starts_with("---\r\n") {
+        return 0;
+    }
+    let after = content.find('\n').map_or(content.len(), |i| i + 1);
+    content[after..]
+        .split_inclusive('\n')
+        .scan(after, |offset, line| {
+            *offset += line.len();
+            Some((*offset, line))
+        })
+        .find(|(_, line)| line.trim_end() == "---")
+        .map_or(0, |(end, _)| end)
+}
+
+/// Content with its table of contents inserted or refreshed
+///
+/// Generated tables are always refreshed; a new one is only inserted into
+/// files over [`TOC_MIN_LINES`] lines that have no table of contents yet.
+pub fn update_toc(content: &str) -> Option<String> {
+    let needs_toc = marked_region(content).is_some()
+        || (count_lines(content) > TOC_MIN_LINES && !has_table_of_contents(content));
+    if !needs_toc {
+        return None;
+    }
+
+    let edit = toc_edit(content)?;
+    let mut updated = content.to_string();
+    updated.replace_range(edit.range, &edit.replacement);
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_anchor() {
+        assert_eq!(github_anchor("Getting Started"), "getting-started");
+        assert_eq!(github_anchor("What's new?"), "whats-new");
+        assert_eq!(github_anchor("API: `fetch_data()`"), "api-fetch_data");
+        assert_eq!(github_anchor("Résumé 2.0"), "résumé-20");
+        assert_eq!(github_anchor("C++ & Rust"), "c--rust");
+    }
+
+    #[test]
+    fn test_headings_dedupe_and_skip_code() {
+        let content = "# Guide\n\n## Setup\n\n```bash\n## not a heading\n```\n\n## Setup\n### [Linked](x.md) step ##\n";
+        let found = headings(content);
+
+        let anchors: Vec<&str> = found.iter().map(|h| h.anchor.as_str()).collect();
+        assert_eq!(anchors, ["guide", "setup", "setup-1", "linked-step"]);
+        assert_eq!(found[3].level, 3);
+        assert_eq!(found[3].text, "Linked step");
+    }
+
+    #[test]
+    fn test_generate_toc_nests_levels() {
+        let toc = generate_toc(&headings("# T\n## Contents\n## A\n### B\n#### C\n## D\n"));
+        assert_eq!(toc, "- [A](#a)\n  - [B](#b)\n- [D](#d)\n");
+    }
+
+    #[test]
+    fn test_insert_and_refresh() {
+        let content = "# Guide\n\nIntro.\n\n## One\n\n## Two\n";
+        let edit = toc_edit(content).unwrap();
+        let mut inserted = content.to_string();
+        inserted.replace_range(edit.range, &edit.replacement);
+        assert_eq!(
+            inserted,
+            format!(
+                "# Guide\n\n{}\n\n## Contents\n\n- [One](#one)\n- [Two](#two)\n\n{}\n\nIntro.\n\n## One\n\n## Two\n",
+                TOC_START, TOC_END
+            )
+        );
+
+        // Already current
+        assert!(toc_edit(&inserted).is_none());
+        assert!(update_toc(&inserted).is_none());
+
+        // A new heading is picked up
+        let refreshed = update_toc(&format!("{}\n## Three\n", inserted)).unwrap();
+        assert!(refreshed.contains("- [Three](#three)\n\n<!-- madskills-toc-end -->"));
+    }
+
+    #[test]
+    fn test_update_toc_only_inserts_into_long_files() {
+        let short = "# Guide\n\n## One\n";
+        assert!(update_toc(short).is_none());
+
+        let long = format!("# Guide\n\n## One\n{}", "text\n".repeat(TOC_MIN_LINES));
+        let updated = update_toc(&long).unwrap();
+        assert!(updated.contains("- [One](#one)"));
+    }
+}
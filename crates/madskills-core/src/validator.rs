@@ -1,18 +1,30 @@
 //! AgentSkills specification validation
 
 mod best_practices;
-mod helpers;
+mod context;
+pub(crate) mod helpers;
+pub(crate) mod links;
 
 #[cfg(test)]
 #[path = "validator/best_practices_tests/mod.rs"]
 mod best_practices_tests;
 
 pub use best_practices::BestPracticesValidator;
+pub(crate) use best_practices::builtin_rules;
+pub use context::{SkillContext, SourceFile};
 
+use crate::discovery::PathFilter;
+use crate::error::CoreResult;
+use crate::markdown::{MarkdownConfig, MarkdownLinter};
 use crate::models::{
-    ALLOWED_FRONTMATTER_FIELDS, Skill, ValidationError, ValidationErrorKind, ValidationResult,
+    ALLOWED_FRONTMATTER_FIELDS, BestPracticeCode, RuleLevel, Severity, Skill, SourceLocation,
+    ValidationError, ValidationErrorKind, ValidationResult,
 };
+use crate::rules::RuleRegistry;
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
 use unicode_normalization::UnicodeNormalization;
 
 /// Validator configuration
@@ -26,8 +38,12 @@ pub struct ValidationConfig {
     pub check_markdown: bool,
     /// Enable best practices validation
     pub check_best_practices: bool,
-    /// Path to mdlint config file
-    pub mdlint_config: Option<std::path::PathBuf>,
+    /// Markdown lint settings
+    pub markdown_config: MarkdownConfig,
+    /// Per-rule selection and severity overrides for best practices
+    pub rules: RuleSettings,
+    /// Files inside skills to leave alone
+    pub exclude: PathFilter,
 }
 
 impl Default for ValidationConfig {
@@ -37,37 +53,172 @@ impl Default for ValidationConfig {
             check_spec: true,
             check_markdown: false,
             check_best_practices: true,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            rules: RuleSettings::default(),
+            exclude: PathFilter::default(),
         }
     }
 }
 
+/// Which best practice rules run, and at what severity
+///
+/// Selectors are rule codes or code prefixes (`AS01` matches AS010-AS019);
+/// `ALL` matches every rule.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleSettings {
+    /// Rules to enable (empty means all)
+    pub select: Vec<String>,
+    /// Rules to disable
+    pub ignore: Vec<String>,
+    /// Per-rule levels, keyed by rule code
+    pub levels: HashMap<BestPracticeCode, RuleLevel>,
+}
+
+impl RuleSettings {
+    /// Check whether a rule is enabled by the select/ignore lists
+    pub fn is_selected(&self, code: &BestPracticeCode) -> bool {
+        let selected = self.select.is_empty()
+            || self
+                .select
+                .iter()
+                .any(|s| selector_matches(s, code.as_str()));
+        selected
+            && !self
+                .ignore
+                .iter()
+                .any(|s| selector_matches(s, code.as_str()))
+    }
+
+    /// Effective severity of a rule, or `None` when it is disabled
+    pub fn severity(&self, code: &BestPracticeCode, strict: bool) -> Option<Severity> {
+        self.severity_with_default(code, RuleLevel::Warning, strict)
+    }
+
+    /// Effective severity of a rule whose level defaults to `default`
+    pub fn severity_with_default(
+        &self,
+        code: &BestPracticeCode,
+        default: RuleLevel,
+        strict: bool,
+    ) -> Option<Severity> {
+        if !self.is_selected(code) {
+            return None;
+        }
+        self.levels
+            .get(code)
+            .copied()
+            .unwrap_or(default)
+            .severity(strict)
+    }
+}
+
+/// Check that a selector matches at least one rule in `registry`
+pub fn parse_rule_selector(selector: &str, registry: &RuleRegistry) -> Result<String, String> {
+    let matches_any = registry
+        .codes()
+        .any(|code| selector_matches(selector, code.as_str()));
+    if matches_any {
+        Ok(selector.to_ascii_uppercase())
+    } else {
+        Err(format!("'{}' does not match any rule code", selector))
+    }
+}
+
+fn selector_matches(selector: &str, code: &str) -> bool {
+    selector.eq_ignore_ascii_case("ALL")
+        || (!selector.is_empty()
+            && code
+                .get(..selector.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(selector)))
+}
+
 /// Validator for AgentSkills specification
 pub struct Validator {
     pub config: ValidationConfig,
+    /// Best practice rules to run
+    registry: Arc<RuleRegistry>,
+    /// Markdown linter, loaded from `config` on first use
+    markdown_linter: OnceLock<CoreResult<MarkdownLinter>>,
 }
 
 impl Validator {
-    /// Create a new validator with the given configuration
+    /// Create a new validator with the given configuration, running the
+    /// built-in rules
     pub fn new(config: ValidationConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            registry: Arc::new(RuleRegistry::builtin()),
+            markdown_linter: OnceLock::new(),
+        }
+    }
+
+    /// Run the rules in `registry` instead of the built-in ones
+    pub fn with_registry(mut self, registry: RuleRegistry) -> Self {
+        self.registry = Arc::new(registry);
+        self
+    }
+
+    /// Best practice rules this validator runs
+    pub fn registry(&self) -> &RuleRegistry {
+        &self.registry
+    }
+
+    /// Validate skills in parallel
+    ///
+    /// Results come back in the order of `skills`, whatever order the
+    /// threads finish in.
+    pub fn validate_all(&self, skills: &[Skill]) -> Vec<ValidationResult> {
+        self.validate_all_with(skills, &HashMap::new())
+    }
+
+    /// Validate skills in parallel, reading `contents` in place of the files
+    /// on disk
+    pub fn validate_all_with(
+        &self,
+        skills: &[Skill],
+        contents: &HashMap<PathBuf, String>,
+    ) -> Vec<ValidationResult> {
+        skills
+            .par_iter()
+            .map(|skill| self.validate_skill_with(skill, contents))
+            .collect()
     }
 
     /// Validate a single skill
     pub fn validate_skill(&self, skill: &Skill) -> ValidationResult {
+        self.validate_skill_with(skill, &HashMap::new())
+    }
+
+    /// Validate a single skill, reading `contents` in place of the files on
+    /// disk
+    pub fn validate_skill_with(
+        &self,
+        skill: &Skill,
+        contents: &HashMap<PathBuf, String>,
+    ) -> ValidationResult {
         let mut result = ValidationResult::new(skill.root.clone());
+        // Every check below reads the skill's files through this
+        let ctx =
+            SkillContext::with_contents(skill, contents).with_exclude(self.config.exclude.clone());
 
         if self.config.check_spec {
             self.validate_spec(skill, &mut result);
         }
 
         if self.config.check_markdown {
-            self.validate_markdown(skill, &mut result);
+            self.validate_markdown(&ctx, &mut result);
         }
 
         if self.config.check_best_practices {
-            let bp_validator = BestPracticesValidator::new(self.config.strict);
-            result.best_practice_violations = bp_validator.validate(skill);
+            result.best_practice_violations =
+                self.registry
+                    .check(&ctx, &self.config.rules, self.config.strict);
+            crate::suppression::apply_context_suppressions(
+                &ctx,
+                &self.registry,
+                &self.config.rules,
+                &mut result,
+            );
         }
 
         result
@@ -75,15 +226,28 @@ impl Validator {
 
     /// Perform AgentSkills spec validation
     fn validate_spec(&self, skill: &Skill, result: &mut ValidationResult) {
+        let spans = &skill.metadata.spans;
+        let at_value = |field: &str| {
+            spans
+                .field(field)
+                .map(|span| span.value_location(&skill.skill_md_path))
+        };
+
         // Validate name
+        let before = result.errors.len();
         self.validate_name(&skill.metadata.name, &skill.root, &mut result.errors);
+        locate(&mut result.errors[before..], at_value("name"));
 
         // Validate description
+        let before = result.errors.len();
         self.validate_description(&skill.metadata.description, &mut result.errors);
+        locate(&mut result.errors[before..], at_value("description"));
 
         // Validate optional fields
         if let Some(ref compat) = skill.metadata.compatibility {
+            let before = result.errors.len();
             self.validate_compatibility(compat, &mut result.errors);
+            locate(&mut result.errors[before..], at_value("compatibility"));
         }
         if let Some(ref license) = skill.metadata.license {
             self.validate_license(license, &mut result.errors);
@@ -96,36 +260,58 @@ impl Validator {
         self.validate_extra_fields(skill, &mut result.errors);
     }
 
-    /// Perform markdown linting
-    fn validate_markdown(&self, skill: &Skill, result: &mut ValidationResult) {
-        // Lint the SKILL.md file
-        match crate::markdown::lint_markdown(
-            &skill.skill_md_path,
-            self.config.mdlint_config.as_deref(),
-        ) {
-            Ok(violations) => {
-                // Convert markdown violations to warnings (or errors if strict mode)
-                for v in violations {
-                    let warning = crate::models::ValidationWarning {
-                        kind: crate::models::ValidationWarningKind::MarkdownLintWarning,
-                        message: format!("[{}] {}", v.rule, v.message),
+    /// Perform markdown linting on every markdown file in the skill
+    fn validate_markdown(&self, ctx: &SkillContext, result: &mut ValidationResult) {
+        let config = &self.config.markdown_config;
+        let linter = self
+            .markdown_linter
+            .get_or_init(|| MarkdownLinter::new(config));
+
+        for file in crate::discovery::markdown_files(ctx.skill, &self.config.exclude) {
+            let linted = match (linter, ctx.read(&file)) {
+                (Ok(linter), Some(source)) => linter
+                    .lint(&file, &source.content)
+                    .map_err(|e| e.to_string()),
+                // Not readable as text; read it again to report why
+                (Ok(_), None) => {
+                    crate::markdown::lint_markdown(&file, config).map_err(|e| e.to_string())
+                }
+                (Err(e), _) => Err(e.to_string()),
+            };
+            match linted {
+                Ok(violations) => {
+                    // Convert markdown violations to warnings (or errors if strict mode)
+                    for v in violations {
+                        let warning = crate::models::ValidationWarning {
+                            kind: crate::models::ValidationWarningKind::MarkdownLintWarning,
+                            message: format!("[{}] {}", v.rule, v.message),
+                            location: Some(crate::models::SourceLocation {
+                                file: file.clone(),
+                                line: v.line,
+                                column: v.column,
+                                end_line: Some(v.end_line),
+                                end_column: Some(v.end_column),
+                            }),
+                            fixable: v.fixable,
+                        };
+                        result.warnings.push(warning);
+                    }
+                }
+                Err(e) => {
+                    // If markdown linting fails, add it as an error
+                    result.errors.push(ValidationError {
+                        kind: ValidationErrorKind::MarkdownLintError,
+                        message: format!("Markdown linting failed: {}", e),
                         location: Some(crate::models::SourceLocation {
-                            file: skill.skill_md_path.clone(),
-                            line: v.line,
-                            column: v.column,
+                            file: file.clone(),
+                            line: 1,
+                            column: 1,
+                            end_line: None,
+                            end_column: None,
                         }),
-                    };
-                    result.warnings.push(warning);
+                    });
                 }
             }
-            Err(e) => {
-                // If markdown linting fails, add it as an error
-                result.errors.push(ValidationError {
-                    kind: ValidationErrorKind::MarkdownLintError,
-                    message: format!("Markdown linting failed: {}", e),
-                    location: None,
-                });
-            }
         }
     }
 
@@ -297,13 +483,20 @@ impl Validator {
     fn validate_extra_fields(&self, skill: &Skill, errors: &mut Vec<ValidationError>) {
         let allowed: HashSet<&str> = ALLOWED_FRONTMATTER_FIELDS.iter().copied().collect();
 
-        let extra: Vec<String> = skill
+        let mut extra: Vec<String> = skill
             .metadata
             .all_fields
             .iter()
             .filter(|f| !allowed.contains(f.as_str()))
             .cloned()
             .collect();
+        // Report in file order and point at the first offending key
+        let spans = &skill.metadata.spans;
+        extra.sort_by_key(|f| spans.field(f).map_or(usize::MAX, |span| span.line));
+        let location = extra
+            .first()
+            .and_then(|f| spans.field(f))
+            .map(|span| span.key_location(&skill.skill_md_path));
 
         if !extra.is_empty() {
             errors.push(ValidationError {
@@ -313,12 +506,19 @@ impl Validator {
                     extra.join(", "),
                     ALLOWED_FRONTMATTER_FIELDS
                 ),
-                location: None,
+                location,
             });
         }
     }
 }
 
+/// Attach a frontmatter location to errors that don't carry one yet
+fn locate(errors: &mut [ValidationError], location: Option<SourceLocation>) {
+    for error in errors.iter_mut().filter(|e| e.location.is_none()) {
+        error.location.clone_from(&location);
+    }
+}
+
 /// Validate uniqueness of skill names across all skills
 pub fn validate_uniqueness(skills: &[Skill]) -> Vec<ValidationError> {
     let mut errors = Vec::new();
@@ -334,7 +534,11 @@ pub fn validate_uniqueness(skills: &[Skill]) -> Vec<ValidationError> {
                     first_path.display(),
                     skill.root.display()
                 ),
-                location: None,
+                location: skill
+                    .metadata
+                    .spans
+                    .field("name")
+                    .map(|span| span.value_location(&skill.skill_md_path)),
             });
         }
     }
@@ -367,7 +571,9 @@ mod tests {
                 allowed_tools: None,
                 metadata: HashMap::new(),
                 all_fields,
+                spans: Default::default(),
             },
+            source: None,
         }
     }
 
@@ -378,7 +584,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("test-skill", "A valid test skill", "test-skill");
@@ -393,7 +600,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let long_name = "a".repeat(65);
@@ -415,7 +623,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("TestSkill", "Test", "TestSkill");
@@ -436,7 +645,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("test_skill", "Test", "test_skill");
@@ -457,7 +667,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("test--skill", "Test", "test--skill");
@@ -478,7 +689,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("test-skill", "Test", "wrong-dir");
@@ -499,7 +711,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("test-skill", "", "test-skill");
@@ -513,6 +726,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rule_settings_select_and_ignore() {
+        let rules = RuleSettings {
+            select: vec!["AS01".to_string()],
+            ignore: vec!["AS015".to_string()],
+            ..Default::default()
+        };
+
+        assert!(!rules.is_selected(&BestPracticeCode::AS003));
+        assert!(rules.is_selected(&BestPracticeCode::AS010));
+        assert!(!rules.is_selected(&BestPracticeCode::AS015));
+        assert!(!rules.is_selected(&BestPracticeCode::AS020));
+    }
+
+    #[test]
+    fn test_rule_settings_levels() {
+        let mut levels = HashMap::new();
+        levels.insert(BestPracticeCode::AS004, RuleLevel::Error);
+        levels.insert(BestPracticeCode::AS012, RuleLevel::Info);
+        levels.insert(BestPracticeCode::AS015, RuleLevel::Off);
+        let rules = RuleSettings {
+            levels,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            rules.severity(&BestPracticeCode::AS004, false),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            rules.severity(&BestPracticeCode::AS012, true),
+            Some(Severity::Info)
+        );
+        assert_eq!(rules.severity(&BestPracticeCode::AS015, false), None);
+        assert_eq!(
+            rules.severity(&BestPracticeCode::AS001, false),
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            rules.severity(&BestPracticeCode::AS001, true),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn test_parse_rule_selector() {
+        let registry = RuleRegistry::builtin();
+        let parse = |selector| parse_rule_selector(selector, &registry);
+        assert_eq!(parse("as015"), Ok("AS015".to_string()));
+        assert_eq!(parse("AS0"), Ok("AS0".to_string()));
+        assert_eq!(parse("all"), Ok("ALL".to_string()));
+        assert!(parse("AS9").is_err());
+        assert!(parse("MD013").is_err());
+    }
+
     #[test]
     fn test_validate_uniqueness_ok() {
         let skills = vec![
@@ -543,7 +811,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let skill = make_skill("café-skill", "A café skill", "café-skill");
@@ -559,7 +828,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         // café with composed é
@@ -587,7 +857,8 @@ mod tests {
             check_spec: true,
             check_markdown: false,
             check_best_practices: false,
-            mdlint_config: None,
+            markdown_config: MarkdownConfig::default(),
+            ..Default::default()
         });
 
         let mut all_fields = HashSet::new();
@@ -606,7 +877,9 @@ mod tests {
                 allowed_tools: None,
                 metadata: HashMap::new(),
                 all_fields,
+                spans: Default::default(),
             },
+            source: None,
         };
 
         let result = validator.validate_skill(&skill);
@@ -618,4 +891,73 @@ mod tests {
                 .any(|e| e.message.contains("Unexpected fields"))
         );
     }
+
+    #[test]
+    fn test_frontmatter_errors_point_at_field() {
+        let content = "---\nname: Bad_Name\ndescription: Test\nextra: true\n---\n";
+        let path = PathBuf::from("bad-name/SKILL.md");
+        let skill = Skill {
+            root: PathBuf::from("bad-name"),
+            metadata: crate::parser::parse_frontmatter(content, &path).unwrap(),
+            skill_md_path: path,
+            source: None,
+        };
+        let validator = Validator::new(ValidationConfig {
+            check_markdown: false,
+            check_best_practices: false,
+            ..Default::default()
+        });
+
+        let result = validator.validate_skill(&skill);
+
+        let name = result
+            .errors
+            .iter()
+            .find(|e| e.message.contains("lowercase"))
+            .and_then(|e| e.location.as_ref())
+            .unwrap();
+        assert_eq!((name.line, name.column), (2, 7));
+        let extra = result
+            .errors
+            .iter()
+            .find(|e| e.message.contains("Unexpected fields"))
+            .and_then(|e| e.location.as_ref())
+            .unwrap();
+        assert_eq!((extra.line, extra.column), (4, 1));
+    }
+
+    #[test]
+    fn test_validate_all_keeps_skill_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let skills: Vec<Skill> = (0..32)
+            .map(|i| {
+                let root = dir.path().join(format!("skill-{}", i));
+                std::fs::create_dir(&root).unwrap();
+                std::fs::write(
+                    root.join("SKILL.md"),
+                    "---\nname: x\ndescription: Test\n---\n\nIn 2024\n",
+                )
+                .unwrap();
+                make_skill(&format!("skill-{}", i), "Test", root.to_str().unwrap())
+            })
+            .collect();
+        let validator = Validator::new(ValidationConfig::default());
+
+        let results = validator.validate_all(&skills);
+
+        let roots: Vec<_> = results.iter().map(|r| r.skill_path.clone()).collect();
+        let expected: Vec<_> = skills.iter().map(|s| s.root.clone()).collect();
+        assert_eq!(roots, expected);
+        let messages = |result: &ValidationResult| {
+            result
+                .best_practice_violations
+                .iter()
+                .map(|v| v.message.clone())
+                .collect::<Vec<_>>()
+        };
+        for (result, skill) in results.iter().zip(&skills) {
+            assert!(!result.best_practice_violations.is_empty());
+            assert_eq!(messages(result), messages(&validator.validate_skill(skill)));
+        }
+    }
 }
@@ -1,851 +1,1102 @@
-//! Best practices validation for Agent Skills (AS001-AS020)
+//! Best practices validation for Agent Skills (AS001-AS024)
 
 use crate::models::*;
+use crate::rules::{Diagnostic, Rule, RuleRegistry};
+use crate::suppression::strip_script_directive;
+use crate::toc::{self, Heading, TOC_MIN_LINES};
+use crate::validator::RuleSettings;
+use crate::validator::context::SkillContext;
 use crate::validator::helpers::*;
+use crate::validator::links::{self, Link};
 use regex::Regex;
+use std::sync::LazyLock;
+
+/// Path-like runs of backslash-separated segments (AS005)
+static BACKSLASH_PATH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[a-zA-Z0-9_.-]*[a-zA-Z0-9_-](?:\\[a-zA-Z0-9_-][a-zA-Z0-9_.-]*)+").unwrap()
+});
+
+/// Generic file names that say nothing about their content (AS007)
+static GENERIC_FILE_NAMES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        r"^doc\d+\.md$",
+        r"^file\d+\.md$",
+        r"^script\d+\.(py|js|sh)$",
+        r"^helper\.(py|js|sh)$",
+        r"^utils\.(md|py|js)$",
+        r"^misc\.md$",
+        r"^temp\.md$",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// Backtick-quoted names that look like MCP tools (AS009)
+static MCP_TOOL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`([a-z_]+(?:_[a-z]+)+)`").unwrap());
+
+/// Absolute date phrases (AS010)
+static ABSOLUTE_DATES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        r"(?i)(before|after|in|as of|since)\s+(january|february|march|april|may|june|july|august|september|october|november|december)\s+20\d{2}",
+        r"(?i)(before|after|in)\s+20\d{2}",
+        r"(?i)Q[1-4]\s+20\d{2}",
+        r"20\d{2}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])", // YYYY-MM-DD
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// Interchangeable terms, each with its whole-word pattern
+type Terms = Vec<(&'static str, Regex)>;
+
+/// Synonym pairs that should not be mixed (AS012)
+static SYNONYM_PAIRS: LazyLock<Vec<[Terms; 2]>> = LazyLock::new(|| {
+    let terms = |words: &[&'static str]| {
+        words
+            .iter()
+            .map(|word| {
+                let re = Regex::new(&format!(r"\b{}\b", regex::escape(word))).unwrap();
+                (*word, re)
+            })
+            .collect()
+    };
+    vec![
+        [terms(&["user", "users"]), terms(&["customer", "customers"])],
+        [
+            terms(&["remove", "removing"]),
+            terms(&["delete", "deleting"]),
+        ],
+        [terms(&["error", "errors"]), terms(&["failure", "failures"])],
+    ]
+});
+
+/// Gerund names (`processing-pdfs`) (AS015)
+static GERUND: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\w+ing(-|$)").unwrap());
+
+/// Numeric constants without an explanation, by script extension (AS018)
+static PY_CONSTANTS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        r"^\s*[A-Z_]+\s*=\s*\d+\s*$", // CONSTANT = 42
+        r"timeout\s*=\s*\d+",         // timeout = 30
+        r"max_.*\s*=\s*\d+",          // max_retries = 5
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+static JS_CONSTANTS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        r"^\s*const\s+[A-Z_]+\s*=\s*\d+\s*;", // const MAX = 42;
+        r"timeout:\s*\d+",                    // timeout: 30
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// Numbered list items (AS019)
+static NUMBERED_LIST: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^\d+\.\s+").unwrap());
+
+/// Links to anchors in the same document (AS020)
+static ANCHOR_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(#([^)]+)\)").unwrap());
+
+/// Links to markdown files (AS006)
+static MARKDOWN_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]+\.md)\)").unwrap());
+
+/// Signature of the `asNNN_*` checks below
+type Check = fn(&SkillContext) -> Vec<Diagnostic>;
+
+/// A built-in rule: one of the `asNNN_*` checks below
+pub(crate) struct BuiltinRule {
+    code: BestPracticeCode,
+    check: Check,
+}
+
+impl Rule for BuiltinRule {
+    fn id(&self) -> &str {
+        self.code.as_str()
+    }
+
+    fn description(&self) -> &str {
+        self.code.description()
+    }
+
+    fn help(&self) -> &str {
+        self.code.help()
+    }
+
+    fn docs_url(&self) -> Option<String> {
+        self.code.help_uri()
+    }
+
+    fn fixable(&self) -> bool {
+        self.code.fixable()
+    }
+
+    fn check(&self, ctx: &SkillContext) -> Vec<Diagnostic> {
+        (self.check)(ctx)
+    }
+}
+
+/// The built-in rules, in code order
+pub(crate) fn builtin_rules() -> Vec<BuiltinRule> {
+    use BestPracticeCode::*;
+    let checks: [(BestPracticeCode, Check); 24] = [
+        // AS001-AS010: Core rules
+        (AS001, as001_name_format),
+        (AS002, as002_description),
+        (AS003, as003_third_person),
+        (AS004, as004_body_length),
+        (AS005, as005_forward_slashes),
+        (AS006, as006_reference_depth),
+        (AS007, as007_descriptive_naming),
+        (AS008, as008_toc_required),
+        (AS009, as009_mcp_format),
+        (AS010, as010_no_absolute_dates),
+        // AS011-AS020: Advanced rules
+        (AS011, as011_templates_present),
+        (AS012, as012_consistent_terminology),
+        (AS013, as013_required_packages),
+        (AS014, as014_usage_triggers),
+        (AS015, as015_gerund_naming),
+        (AS016, as016_no_reserved_words),
+        (AS017, as017_script_error_handling),
+        (AS018, as018_no_magic_constants),
+        (AS019, as019_numbered_workflow),
+        (AS020, as020_toc_completeness),
+        // AS021-AS024: Link checks
+        (AS021, as021_broken_links),
+        (AS022, as022_broken_anchors),
+        (AS023, as023_broken_images),
+        (AS024, as024_undefined_references),
+    ];
+    checks
+        .into_iter()
+        .map(|(code, check)| BuiltinRule { code, check })
+        .collect()
+}
 
+/// Runs the built-in rules on their own
 pub struct BestPracticesValidator {
     strict: bool,
+    rules: RuleSettings,
+    registry: RuleRegistry,
 }
 
 impl BestPracticesValidator {
     pub fn new(strict: bool) -> Self {
-        Self { strict }
+        Self {
+            strict,
+            rules: RuleSettings::default(),
+            registry: RuleRegistry::builtin(),
+        }
     }
 
-    pub fn validate(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        // AS001-AS010: Core rules
-        violations.extend(self.check_as001_name_format(&skill.metadata));
-        violations.extend(self.check_as002_description(&skill.metadata));
-        violations.extend(self.check_as003_third_person(&skill.metadata));
-        violations.extend(self.check_as004_body_length(skill));
-        violations.extend(self.check_as005_forward_slashes(skill));
-        violations.extend(self.check_as006_reference_depth(skill));
-        violations.extend(self.check_as007_descriptive_naming(skill));
-        violations.extend(self.check_as008_toc_required(skill));
-        violations.extend(self.check_as009_mcp_format(skill));
-        violations.extend(self.check_as010_no_absolute_dates(skill));
-
-        // AS011-AS020: Advanced rules
-        violations.extend(self.check_as011_templates_present(skill));
-        violations.extend(self.check_as012_consistent_terminology(skill));
-        violations.extend(self.check_as013_required_packages(skill));
-        violations.extend(self.check_as014_usage_triggers(&skill.metadata));
-        violations.extend(self.check_as015_gerund_naming(&skill.metadata));
-        violations.extend(self.check_as016_no_reserved_words(&skill.metadata));
-        violations.extend(self.check_as017_script_error_handling(skill));
-        violations.extend(self.check_as018_no_magic_constants(skill));
-        violations.extend(self.check_as019_numbered_workflow(skill));
-        violations.extend(self.check_as020_toc_completeness(skill));
-
-        violations
-    }
-
-    fn severity(&self) -> Severity {
-        if self.strict {
-            Severity::Error
-        } else {
-            Severity::Warning
-        }
+    /// Apply per-rule selection and severity overrides
+    pub fn with_rules(mut self, rules: RuleSettings) -> Self {
+        self.rules = rules;
+        self
     }
 
-    fn violation(
-        &self,
-        code: BestPracticeCode,
-        message: impl Into<String>,
-        location: Option<ViolationLocation>,
-    ) -> BestPracticeViolation {
-        BestPracticeViolation {
-            code,
-            severity: self.severity(),
-            message: message.into(),
-            location,
-        }
+    pub fn validate(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
+        self.registry
+            .check(&SkillContext::new(skill), &self.rules, self.strict)
     }
+}
 
-    /// AS001: Name format validation
-    fn check_as001_name_format(&self, metadata: &SkillMetadata) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-        let name = &metadata.name;
+/// AS001: Name format validation
+fn as001_name_format(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
+    let name = &metadata.name;
+
+    // Check for XML tags
+    if contains_xml_tags(name) {
+        violations.push(Diagnostic::new(
+            "Name cannot contain XML tags",
+            Some(ViolationLocation::frontmatter(metadata, "name")),
+        ));
+    }
 
-        // Check for XML tags
-        if contains_xml_tags(name) {
-            violations.push(self.violation(
-                BestPracticeCode::AS001,
-                "Name cannot contain XML tags",
-                Some(ViolationLocation::Frontmatter {
-                    field: "name".to_string(),
-                }),
-            ));
-        }
+    // Check for reserved words
+    let lower_name = name.to_lowercase();
+    if lower_name.contains("anthropic") || lower_name.contains("claude") {
+        violations.push(Diagnostic::new(
+            format!("Name cannot contain reserved words (found: {})", name),
+            Some(ViolationLocation::frontmatter(metadata, "name")),
+        ));
+    }
 
-        // Check for reserved words
-        let lower_name = name.to_lowercase();
-        if lower_name.contains("anthropic") || lower_name.contains("claude") {
-            violations.push(self.violation(
-                BestPracticeCode::AS001,
-                format!("Name cannot contain reserved words (found: {})", name),
-                Some(ViolationLocation::Frontmatter {
-                    field: "name".to_string(),
-                }),
-            ));
-        }
+    violations
+}
 
-        violations
+/// AS002: Description validation
+fn as002_description(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
+    let desc = &metadata.description;
+
+    // Check for XML tags
+    if contains_xml_tags(desc) {
+        violations.push(Diagnostic::new(
+            "Description cannot contain XML tags",
+            Some(ViolationLocation::frontmatter(metadata, "description")),
+        ));
     }
 
-    /// AS002: Description validation
-    fn check_as002_description(&self, metadata: &SkillMetadata) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-        let desc = &metadata.description;
+    violations
+}
 
-        // Check for XML tags
-        if contains_xml_tags(desc) {
-            violations.push(self.violation(
-                BestPracticeCode::AS002,
-                "Description cannot contain XML tags",
-                Some(ViolationLocation::Frontmatter {
-                    field: "description".to_string(),
-                }),
-            ));
-        }
+/// AS003: Third-person voice check
+fn as003_third_person(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
 
-        violations
+    if contains_first_or_second_person(&metadata.description) {
+        violations.push(Diagnostic::new(
+            "Description should use third-person voice (avoid 'I', 'you', 'we')",
+            Some(ViolationLocation::frontmatter(metadata, "description")),
+        ));
     }
 
-    /// AS003: Third-person voice check
-    fn check_as003_third_person(&self, metadata: &SkillMetadata) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        if contains_first_or_second_person(&metadata.description) {
-            violations.push(self.violation(
-                BestPracticeCode::AS003,
-                "Description should use third-person voice (avoid 'I', 'you', 'we')",
-                Some(ViolationLocation::Frontmatter {
-                    field: "description".to_string(),
-                }),
+/// AS004: SKILL.md body length check
+fn as004_body_length(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    if let (Some(file), Some(body)) = (&ctx.skill_md, &ctx.body) {
+        let line_count = count_lines(body);
+        if line_count > 500 {
+            // Point at the first line past the limit
+            let offset = ctx.body_offsets.get(500).copied().unwrap_or(0);
+            violations.push(Diagnostic::new(
+                format!(
+                    "SKILL.md body has {} lines (should be under 500 for optimal performance)",
+                    line_count
+                ),
+                file.location(offset),
             ));
         }
-
-        violations
     }
 
-    /// AS004: SKILL.md body length check
-    fn check_as004_body_length(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        // Read SKILL.md and count body lines
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Extract body (content after frontmatter)
-            if let Some(body) = Self::extract_body(&content) {
-                let line_count = count_lines(&body);
-                if line_count > 500 {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS004,
+/// AS005: Forward slashes only in paths
+fn as005_forward_slashes(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    // Check SKILL.md for backslashes outside code, where they are usually
+    // escape sequences (`printf "done\n"`) rather than paths
+    if let Some(file) = &ctx.skill_md
+        && contains_backslashes(&file.content)
+    {
+        for line in links::prose_lines(&file.content) {
+            for m in BACKSLASH_PATH.find_iter(&line.text) {
+                let range = line.offset + m.start()..line.offset + m.end();
+                let edits = vec![TextEdit {
+                    range,
+                    replacement: m.as_str().replace('\\', "/"),
+                }];
+                // Only rewrite what is clearly a path; `a\nb` may be an escape
+                let fix = if looks_like_path(m.as_str()) {
+                    Fix::safe(&file.path, edits)
+                } else {
+                    Fix::unsafe_edit(&file.path, edits)
+                };
+                violations.push(
+                    Diagnostic::new(
                         format!(
-                            "SKILL.md body has {} lines (should be under 500 for optimal performance)",
-                            line_count
+                            "Use forward slashes (/) in file paths, not backslashes (\\): '{}'",
+                            m.as_str().trim_end_matches('.')
                         ),
-                        Some(ViolationLocation::File {
-                            path: skill.skill_md_path.clone(),
-                            line: None,
-                        }),
-                    ));
-                }
+                        file.location(line.offset + m.start()),
+                    )
+                    .with_fix(fix),
+                );
             }
         }
-
-        violations
-    }
-
-    /// AS005: Forward slashes only in paths
-    fn check_as005_forward_slashes(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        // Check SKILL.md for backslashes
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path)
-            && contains_backslashes(&content)
-        {
-            // More detailed check: look for path-like backslashes (not escape sequences)
-            let re = Regex::new(r"[a-zA-Z0-9_-]+\\[a-zA-Z0-9_-]").unwrap();
-            if re.is_match(&content) {
-                violations.push(self.violation(
-                    BestPracticeCode::AS005,
-                    "Use forward slashes (/) in file paths, not backslashes (\\)",
-                    Some(ViolationLocation::File {
-                        path: skill.skill_md_path.clone(),
-                        line: None,
-                    }),
-                ));
-            }
-        }
-
-        violations
     }
 
-    /// AS006: References one level deep
-    fn check_as006_reference_depth(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        // Read SKILL.md and find referenced files
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            let referenced_files = Self::extract_markdown_links(&content);
+/// Whether a backslash-separated run is clearly a path: it has two or more
+/// separators or ends in a file extension
+fn looks_like_path(text: &str) -> bool {
+    let text = text.trim_end_matches('.');
+    let last = text.rsplit('\\').next().unwrap_or(text);
+    text.matches('\\').count() >= 2
+        || last
+            .rsplit_once('.')
+            .is_some_and(|(stem, ext)| !stem.is_empty() && !ext.is_empty())
+}
 
-            // Check each referenced file for nested references
-            for ref_file in referenced_files {
-                let ref_path = skill.root.join(&ref_file);
-                if ref_path.exists()
-                    && let Ok(ref_content) = std::fs::read_to_string(&ref_path)
-                {
-                    let nested_refs = Self::extract_markdown_links(&ref_content);
-                    if !nested_refs.is_empty() {
-                        violations.push(self.violation(
-                                BestPracticeCode::AS006,
-                                format!(
-                                    "File {} contains nested references (references should be one level deep from SKILL.md)",
-                                    ref_file
-                                ),
-                                Some(ViolationLocation::File {
-                                    path: ref_path,
-                                    line: None,
-                                }),
-                            ));
-                    }
+/// AS006: References one level deep
+fn as006_reference_depth(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    // Find files referenced from SKILL.md
+    if let Some(file) = &ctx.skill_md {
+        let referenced_files = extract_markdown_links(&file.content);
+
+        // Check each referenced file for nested references
+        for (_, ref_file) in referenced_files {
+            let ref_path = ctx.skill.root.join(&ref_file);
+            if let Some(referenced) = ctx.read(&ref_path) {
+                let nested_refs = extract_markdown_links(&referenced.content);
+                if let Some((offset, _)) = nested_refs.first() {
+                    violations.push(Diagnostic::new(format!(
+                                "File {} contains nested references (references should be one level deep from SKILL.md)",
+                                ref_file
+                            ),
+                            referenced.location(*offset),
+                        ));
                 }
             }
         }
-
-        violations
     }
 
-    /// AS007: Descriptive file naming
-    fn check_as007_descriptive_naming(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        let files = list_skill_files(&skill.root);
-        let generic_patterns = [
-            r"^doc\d+\.md$",
-            r"^file\d+\.md$",
-            r"^script\d+\.(py|js|sh)$",
-            r"^helper\.(py|js|sh)$",
-            r"^utils\.(md|py|js)$",
-            r"^misc\.md$",
-            r"^temp\.md$",
-        ];
+    violations
+}
 
-        for file in files {
-            let filename = file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+/// AS007: Descriptive file naming
+fn as007_descriptive_naming(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-            // Skip standard files
-            if filename == "SKILL.md" || filename == "README.md" || filename == "LICENSE.md" {
-                continue;
-            }
+    for file in &ctx.files {
+        let filename = file.file_name().and_then(|s| s.to_str()).unwrap_or("");
 
-            // Check against generic patterns
-            for pattern in &generic_patterns {
-                let re = Regex::new(pattern).unwrap();
-                if re.is_match(filename) {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS007,
-                        format!(
-                            "Use descriptive file names instead of generic names like '{}'",
-                            filename
-                        ),
-                        Some(ViolationLocation::File {
-                            path: file,
-                            line: None,
-                        }),
-                    ));
-                    break;
-                }
-            }
+        // Skip standard files
+        if filename == "SKILL.md" || filename == "README.md" || filename == "LICENSE.md" {
+            continue;
         }
 
-        violations
+        // Check against generic patterns
+        if GENERIC_FILE_NAMES.iter().any(|re| re.is_match(filename)) {
+            violations.push(Diagnostic::new(
+                format!(
+                    "Use descriptive file names instead of generic names like '{}'",
+                    filename
+                ),
+                Some(ViolationLocation::File {
+                    path: file.clone(),
+                    line: None,
+                    column: None,
+                }),
+            ));
+        }
     }
 
-    /// AS008: Table of contents required for long files
-    fn check_as008_toc_required(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        let files = list_skill_files(&skill.root);
-        for file in files {
-            // Only check markdown files
-            if file.extension().and_then(|s| s.to_str()) != Some("md") {
-                continue;
-            }
+/// AS008: Table of contents required for long files
+fn as008_toc_required(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-            // Skip SKILL.md itself
-            if file == skill.skill_md_path {
-                continue;
-            }
+    for path in &ctx.files {
+        // Only check markdown files
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
 
-            if let Ok(content) = std::fs::read_to_string(&file) {
-                let line_count = count_lines(&content);
-                if line_count > 100 && !has_table_of_contents(&content) {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS008,
-                        format!(
-                            "File has {} lines but no table of contents (recommended for files > 100 lines)",
-                            line_count
-                        ),
-                        Some(ViolationLocation::File {
-                            path: file,
-                            line: None,
-                        }),
-                    ));
-                }
-            }
+        // Skip SKILL.md itself
+        if *path == ctx.skill.skill_md_path {
+            continue;
         }
 
-        violations
-    }
-
-    /// AS009: MCP tool format check
-    fn check_as009_mcp_format(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Look for potential MCP tool references without ServerName: prefix
-            // Pattern: backtick-quoted tool names that look like MCP tools
-            let re = Regex::new(r"`([a-z_]+(?:_[a-z]+)+)`").unwrap();
-
-            for cap in re.captures_iter(&content) {
-                let tool_name = &cap[1];
-                // Check if it looks like an MCP tool (has underscores, common verbs)
-                let mcp_verbs = [
-                    "get", "create", "update", "delete", "list", "search", "execute", "query",
-                    "send", "fetch",
-                ];
-
-                if mcp_verbs.iter().any(|v| tool_name.starts_with(v)) && !tool_name.contains(':') {
-                    // Check surrounding context for mentions of MCP, tool, server
-                    let context_start = cap.get(0).unwrap().start().saturating_sub(100);
-                    let context_end = (cap.get(0).unwrap().end() + 100).min(content.len());
-                    let context = &content[context_start..context_end].to_lowercase();
-
-                    if context.contains("mcp")
-                        || context.contains("server")
-                        || context.contains("tool")
-                    {
-                        violations.push(self.violation(
-                            BestPracticeCode::AS009,
-                            format!(
-                                "MCP tool '{}' should use ServerName:tool_name format (e.g., 'BigQuery:{}')",
-                                tool_name, tool_name
-                            ),
-                            Some(ViolationLocation::File {
-                                path: skill.skill_md_path.clone(),
-                                line: None,
-                            }),
-                        ));
-                    }
-                }
+        if let Some(file) = ctx.read(path) {
+            let content = &file.content;
+            let line_count = count_lines(content);
+            if line_count > TOC_MIN_LINES && !has_table_of_contents(content) {
+                let violation = Diagnostic::new(
+                    format!(
+                        "File has {} lines but no table of contents (recommended for files > 100 lines)",
+                        line_count
+                    ),
+                    // The table of contents belongs at the top
+                    file.location(0),
+                );
+                violations.push(match toc::toc_edit(content) {
+                    Some(edit) => violation.with_fix(Fix::safe(path, vec![edit])),
+                    None => violation,
+                });
             }
         }
-
-        violations
     }
 
-    /// AS010: No time-sensitive absolute dates
-    fn check_as010_no_absolute_dates(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Check for absolute date patterns (but not in "old patterns" sections)
-            let date_patterns = [
-                r"(?i)(before|after|in|as of|since)\s+(january|february|march|april|may|june|july|august|september|october|november|december)\s+20\d{2}",
-                r"(?i)(before|after|in)\s+20\d{2}",
-                r"(?i)Q[1-4]\s+20\d{2}",
-                r"20\d{2}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])", // YYYY-MM-DD
+/// AS009: MCP tool format check
+fn as009_mcp_format(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    if let Some(file) = &ctx.skill_md {
+        let content = &file.content;
+        // Look for potential MCP tool references without ServerName: prefix
+        // Pattern: backtick-quoted tool names that look like MCP tools
+        for cap in MCP_TOOL.captures_iter(content) {
+            let tool_name = &cap[1];
+            // Check if it looks like an MCP tool (has underscores, common verbs)
+            let mcp_verbs = [
+                "get", "create", "update", "delete", "list", "search", "execute", "query", "send",
+                "fetch",
             ];
 
-            // Check if content is in an "old patterns" section
-            let in_old_patterns = content.to_lowercase().contains("<details>")
-                && (content.to_lowercase().contains("deprecated")
-                    || content.to_lowercase().contains("legacy"));
-
-            if !in_old_patterns {
-                for pattern in &date_patterns {
-                    let re = Regex::new(pattern).unwrap();
-                    if re.is_match(&content) {
-                        violations.push(self.violation(
-                            BestPracticeCode::AS010,
-                            "Avoid time-sensitive information with absolute dates (use 'old patterns' section for deprecated content)",
-                            Some(ViolationLocation::File {
-                                path: skill.skill_md_path.clone(),
-                                line: None,
-                            }),
-                        ));
-                        break; // Only report once per file
-                    }
+            if mcp_verbs.iter().any(|v| tool_name.starts_with(v)) && !tool_name.contains(':') {
+                // Check surrounding context for mentions of MCP, tool, server
+                let context_start = cap.get(0).unwrap().start().saturating_sub(100);
+                let context_end = (cap.get(0).unwrap().end() + 100).min(content.len());
+                let context = &content[context_start..context_end].to_lowercase();
+
+                if context.contains("mcp") || context.contains("server") || context.contains("tool")
+                {
+                    violations.push(Diagnostic::new(format!(
+                            "MCP tool '{}' should use ServerName:tool_name format (e.g., 'BigQuery:{}')",
+                            tool_name, tool_name
+                        ),
+                        file.location(cap.get(1).unwrap().start()),
+                    ));
                 }
             }
         }
-
-        violations
     }
 
-    /// AS011: Templates for output-generating skills
-    fn check_as011_templates_present(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Check if description mentions output generation
-            let desc_lower = skill.metadata.description.to_lowercase();
-            let output_keywords = [
-                "generate", "create", "write", "produce", "output", "format", "export",
-            ];
+/// AS010: No time-sensitive absolute dates
+fn as010_no_absolute_dates(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-            let is_output_skill = output_keywords.iter().any(|kw| desc_lower.contains(kw));
-
-            if is_output_skill {
-                // Check for template/example patterns
-                let has_template = content.contains("## Template")
-                    || content.contains("## Example Output")
-                    || content.contains("```") && content.contains("Output format:");
-
-                if !has_template {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS011,
-                        "Output-generating skills should include templates or examples (## Template or ## Example Output section)",
-                        Some(ViolationLocation::File {
-                            path: skill.skill_md_path.clone(),
-                            line: None,
-                        }),
-                    ));
+    if let Some(file) = &ctx.skill_md {
+        let content = &file.content;
+
+        // Check if content is in an "old patterns" section
+        let lower = content.to_lowercase();
+        let in_old_patterns = lower.contains("<details>")
+            && (lower.contains("deprecated") || lower.contains("legacy"));
+
+        if !in_old_patterns {
+            let mut matches: Vec<regex::Match> = ABSOLUTE_DATES
+                .iter()
+                .flat_map(|re| re.find_iter(content))
+                .collect();
+            matches.sort_by_key(|m| (m.start(), std::cmp::Reverse(m.end())));
+
+            // Report each dated phrase once, even when several patterns match it
+            let mut covered = 0;
+            for m in matches {
+                if m.start() < covered {
+                    continue;
                 }
+                covered = m.end();
+                violations.push(Diagnostic::new(format!(
+                        "Avoid time-sensitive information with absolute dates like '{}' (use 'old patterns' section for deprecated content)",
+                        m.as_str()
+                    ),
+                    file.location(m.start()),
+                ));
             }
         }
-
-        violations
     }
 
-    /// AS012: Consistent terminology
-    fn check_as012_consistent_terminology(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Common synonym pairs to check
-            let synonym_pairs = [
-                (vec!["user", "users"], vec!["customer", "customers"]),
-                (vec!["remove", "removing"], vec!["delete", "deleting"]),
-                (vec!["error", "errors"], vec!["failure", "failures"]),
-            ];
+/// AS011: Templates for output-generating skills
+fn as011_templates_present(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-            for (set_a, set_b) in &synonym_pairs {
-                let has_a = set_a.iter().any(|term| {
-                    let re = Regex::new(&format!(r"\b{}\b", regex::escape(term))).unwrap();
-                    re.is_match(&content)
-                });
+    if let Some(file) = &ctx.skill_md {
+        let content = &file.content;
+        // Check if description mentions output generation
+        let desc_lower = ctx.skill.metadata.description.to_lowercase();
+        let output_keywords = [
+            "generate", "create", "write", "produce", "output", "format", "export",
+        ];
 
-                let has_b = set_b.iter().any(|term| {
-                    let re = Regex::new(&format!(r"\b{}\b", regex::escape(term))).unwrap();
-                    re.is_match(&content)
-                });
+        let is_output_skill = output_keywords.iter().any(|kw| desc_lower.contains(kw));
 
-                if has_a && has_b {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS012,
-                        format!(
-                            "Use consistent terminology: mixing {:?} and {:?} (pick one)",
-                            set_a[0], set_b[0]
-                        ),
-                        Some(ViolationLocation::File {
-                            path: skill.skill_md_path.clone(),
-                            line: None,
-                        }),
-                    ));
-                }
+        if is_output_skill {
+            // Check for template/example patterns
+            let has_template = content.contains("## Template")
+                || content.contains("## Example Output")
+                || content.contains("```") && content.contains("Output format:");
+
+            if !has_template {
+                violations.push(Diagnostic::new("Output-generating skills should include templates or examples (## Template or ## Example Output section)",
+                    ctx.body_location(),
+                ));
             }
         }
-
-        violations
     }
 
-    /// AS013: Document required packages
-    fn check_as013_required_packages(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        let scripts = find_script_files(&skill.root);
-        if scripts.is_empty() {
-            return violations;
-        }
+/// AS012: Consistent terminology
+fn as012_consistent_terminology(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Check for dependencies/requirements/installation sections
-            let has_deps_section = content.to_lowercase().contains("## dependencies")
-                || content.to_lowercase().contains("## requirements")
-                || content.to_lowercase().contains("## installation")
-                || content.contains("pip install")
-                || content.contains("npm install");
-
-            if !has_deps_section {
-                violations.push(self.violation(
-                    BestPracticeCode::AS013,
-                    "Scripts found but no ## Dependencies or ## Requirements section documenting required packages",
-                    Some(ViolationLocation::File {
-                        path: skill.skill_md_path.clone(),
-                        line: None,
-                    }),
+    if let Some(file) = &ctx.skill_md {
+        // Offset of the first use of any term in a set
+        let first_use = |terms: &[(&str, Regex)]| {
+            terms
+                .iter()
+                .filter_map(|(_, re)| re.find(&file.content).map(|m| m.start()))
+                .min()
+        };
+
+        for [set_a, set_b] in SYNONYM_PAIRS.iter() {
+            if let (Some(a), Some(b)) = (first_use(set_a), first_use(set_b)) {
+                // Point at where the second term first appears
+                violations.push(Diagnostic::new(
+                    format!(
+                        "Use consistent terminology: mixing {:?} and {:?} (pick one)",
+                        set_a[0].0, set_b[0].0
+                    ),
+                    file.location(a.max(b)),
                 ));
             }
         }
-
-        violations
     }
 
-    /// AS014: Description includes usage triggers
-    fn check_as014_usage_triggers(&self, metadata: &SkillMetadata) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        let desc_lower = metadata.description.to_lowercase();
-        let has_trigger = desc_lower.contains("use when")
-            || desc_lower.contains("use this when")
-            || desc_lower.contains("for ")
-            || desc_lower.contains("to help");
+/// AS013: Document required packages
+fn as013_required_packages(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
 
-        if !has_trigger {
-            violations.push(self.violation(
-                BestPracticeCode::AS014,
-                "Description should include usage triggers (e.g., 'Use when...', 'For...', 'To help...')",
-                Some(ViolationLocation::Frontmatter {
-                    field: "description".to_string(),
-                }),
+    if ctx.scripts.is_empty() {
+        return violations;
+    }
+
+    if let Some(file) = &ctx.skill_md {
+        let content = &file.content;
+        // Check for dependencies/requirements/installation sections
+        let lower = content.to_lowercase();
+        let has_deps_section = lower.contains("## dependencies")
+            || lower.contains("## requirements")
+            || lower.contains("## installation")
+            || content.contains("pip install")
+            || content.contains("npm install");
+
+        if !has_deps_section {
+            // Point at the first mention of a script, if SKILL.md has one
+            let mention = ctx
+                .scripts
+                .iter()
+                .filter_map(|script| script.file_name()?.to_str())
+                .filter_map(|name| content.find(name))
+                .min();
+            violations.push(Diagnostic::new("Scripts found but no ## Dependencies or ## Requirements section documenting required packages",
+                match mention {
+                    Some(offset) => file.location(offset),
+                    None => ctx.body_location(),
+                },
             ));
         }
+    }
 
-        violations
-    }
-
-    /// AS015: Prefer gerund naming (verb-ing pattern)
-    fn check_as015_gerund_naming(&self, metadata: &SkillMetadata) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        let name = &metadata.name;
-        let gerund_pattern = Regex::new(r"\w+ing(-|$)").unwrap();
-
-        // Check if name follows gerund pattern
-        if !gerund_pattern.is_match(name) {
-            // Check if it's an imperative verb form
-            let imperative_verbs = [
-                "analyze",
-                "process",
-                "generate",
-                "create",
-                "validate",
-                "parse",
-                "extract",
-                "format",
-                "convert",
-                "transform",
-            ];
+    violations
+}
 
-            let has_imperative = imperative_verbs.iter().any(|verb| name.starts_with(verb));
+/// AS014: Description includes usage triggers
+fn as014_usage_triggers(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
+
+    let desc_lower = metadata.description.to_lowercase();
+    let has_trigger = desc_lower.contains("use when")
+        || desc_lower.contains("use this when")
+        || desc_lower.contains("for ")
+        || desc_lower.contains("to help");
+
+    if !has_trigger {
+        violations.push(Diagnostic::new("Description should include usage triggers (e.g., 'Use when...', 'For...', 'To help...')",
+            Some(ViolationLocation::frontmatter(metadata, "description")),
+        ));
+    }
 
-            if has_imperative {
-                violations.push(self.violation(
-                    BestPracticeCode::AS015,
-                    format!(
-                        "Consider using gerund form for action names (e.g., '{}-ing' instead of '{}')",
-                        name.split('-').next().unwrap_or(name),
-                        name
-                    ),
-                    Some(ViolationLocation::Frontmatter {
-                        field: "name".to_string(),
-                    }),
-                ));
-            }
-        }
+    violations
+}
 
-        violations
-    }
+/// AS015: Prefer gerund naming (verb-ing pattern)
+fn as015_gerund_naming(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
+
+    let name = &metadata.name;
+
+    // Check if name follows gerund pattern
+    if !GERUND.is_match(name) {
+        // Check if it's an imperative verb form
+        let imperative_verbs = [
+            "analyze",
+            "process",
+            "generate",
+            "create",
+            "validate",
+            "parse",
+            "extract",
+            "format",
+            "convert",
+            "transform",
+        ];
 
-    /// AS016: Avoid reserved words in name
-    fn check_as016_no_reserved_words(
-        &self,
-        metadata: &SkillMetadata,
-    ) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+        let has_imperative = imperative_verbs.iter().any(|verb| name.starts_with(verb));
 
-        let name_lower = metadata.name.to_lowercase();
-        if name_lower.contains("anthropic") || name_lower.contains("claude") {
-            violations.push(self.violation(
-                BestPracticeCode::AS016,
+        if has_imperative {
+            violations.push(Diagnostic::new(
                 format!(
-                    "Name '{}' contains reserved words (anthropic, claude)",
-                    metadata.name
+                    "Consider using gerund form for action names (e.g., '{}-ing' instead of '{}')",
+                    name.split('-').next().unwrap_or(name),
+                    name
                 ),
-                Some(ViolationLocation::Frontmatter {
-                    field: "name".to_string(),
-                }),
+                Some(ViolationLocation::frontmatter(metadata, "name")),
             ));
         }
-
-        violations
     }
 
-    /// AS017: Scripts have error handling
-    fn check_as017_script_error_handling(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        let scripts = find_script_files(&skill.root);
-        for script in scripts {
-            if let Ok(content) = std::fs::read_to_string(&script) {
-                let ext = script.extension().and_then(|e| e.to_str()).unwrap_or("");
+    violations
+}
 
-                let has_error_handling = match ext {
-                    "py" => {
-                        content.contains("try:")
-                            || content.contains("except ")
-                            || content.contains("if not ")
-                            || content.contains("sys.exit(")
-                    }
-                    "sh" => {
-                        content.contains("set -e")
-                            || content.contains("if [ ")
-                            || content.contains("exit 1")
-                            || content.contains("||")
-                    }
-                    "js" | "ts" => {
-                        content.contains("try {")
-                            || content.contains("catch (")
-                            || content.contains("if (!")
-                            || content.contains("process.exit(")
-                    }
-                    _ => true, // Skip unknown script types
-                };
+/// AS016: Avoid reserved words in name
+fn as016_no_reserved_words(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let metadata = &ctx.skill.metadata;
+    let mut violations = Vec::new();
+
+    let name_lower = metadata.name.to_lowercase();
+    if name_lower.contains("anthropic") || name_lower.contains("claude") {
+        violations.push(Diagnostic::new(
+            format!(
+                "Name '{}' contains reserved words (anthropic, claude)",
+                metadata.name
+            ),
+            Some(ViolationLocation::frontmatter(metadata, "name")),
+        ));
+    }
 
-                if !has_error_handling {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS017,
-                        format!(
-                            "Script {} lacks error handling (add try/catch, if checks, or exit codes)",
-                            script.file_name().unwrap().to_string_lossy()
-                        ),
-                        Some(ViolationLocation::Script {
-                            path: script,
-                            line: None,
-                        }),
-                    ));
+    violations
+}
+/// AS017: Scripts have error handling
+fn as017_script_error_handling(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    for script in &ctx.scripts {
+        if let Some(file) = ctx.read(script) {
+            let content = &file.content;
+            let ext = script.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+            let has_error_handling = match ext {
+                "py" => {
+                    content.contains("try:")
+                        || content.contains("except ")
+                        || content.contains("if not ")
+                        || content.contains("sys.exit(")
+                }
+                "sh" => {
+                    content.contains("set -e")
+                        || content.contains("if [ ")
+                        || content.contains("exit 1")
+                        || content.contains("||")
                 }
+                "js" | "ts" => {
+                    content.contains("try {")
+                        || content.contains("catch (")
+                        || content.contains("if (!")
+                        || content.contains("process.exit(")
+                }
+                _ => true, // Skip unknown script types
+            };
+
+            if !has_error_handling {
+                // Shell scripts can opt into exiting on the first failure;
+                // that changes behavior, so the fix is unsafe
+                let fix = (ext == "sh").then(|| {
+                    let after_shebang = if content.starts_with("#!") {
+                        content.find('\n').map_or(content.len(), |i| i + 1)
+                    } else {
+                        0
+                    };
+                    let prefix = if after_shebang == content.len() && !content.ends_with('\n') {
+                        "\n"
+                    } else {
+                        ""
+                    };
+                    Fix::unsafe_edit(
+                        script,
+                        vec![TextEdit::insert(
+                            after_shebang,
+                            format!("{}set -e\n", prefix),
+                        )],
+                    )
+                });
+                let violation = Diagnostic::new(
+                    format!(
+                        "Script {} lacks error handling (add try/catch, if checks, or exit codes)",
+                        script.file_name().unwrap().to_string_lossy()
+                    ),
+                    Some(ViolationLocation::Script {
+                        path: script.clone(),
+                        line: None,
+                    }),
+                );
+                violations.push(match fix {
+                    Some(fix) => violation.with_fix(fix),
+                    None => violation,
+                });
             }
         }
+    }
 
-        violations
-    }
-
-    /// AS018: No undocumented magic constants
-    fn check_as018_no_magic_constants(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
-
-        let scripts = find_script_files(&skill.root);
-        for script in scripts {
-            if let Ok(content) = std::fs::read_to_string(&script) {
-                let ext = script.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-                // Look for numeric assignments without nearby comments
-                let patterns = match ext {
-                    "py" => vec![
-                        r"^\s*[A-Z_]+\s*=\s*\d+\s*$", // CONSTANT = 42
-                        r"timeout\s*=\s*\d+",         // timeout = 30
-                        r"max_.*\s*=\s*\d+",          // max_retries = 5
-                    ],
-                    "js" | "ts" => vec![
-                        r"^\s*const\s+[A-Z_]+\s*=\s*\d+\s*;", // const MAX = 42;
-                        r"timeout:\s*\d+",                    // timeout: 30
-                    ],
-                    _ => vec![],
-                };
+    violations
+}
 
-                for pattern in patterns {
-                    let re = Regex::new(pattern).unwrap();
-                    for (i, line) in content.lines().enumerate() {
-                        if re.is_match(line) {
-                            // Check if previous line or current line has a comment
-                            let lines: Vec<&str> = content.lines().collect();
-                            let has_comment = if i > 0 {
-                                lines[i - 1].contains('#') || lines[i - 1].contains("//")
-                            } else {
-                                false
-                            } || line.contains('#')
-                                || line.contains("//");
-
-                            if !has_comment {
-                                violations.push(self.violation(
-                                    BestPracticeCode::AS018,
-                                    format!(
-                                        "Undocumented constant in {} line {}: add comment explaining the value",
-                                        script.file_name().unwrap().to_string_lossy(),
-                                        i + 1
-                                    ),
-                                    Some(ViolationLocation::Script {
-                                        path: script.clone(),
-                                        line: Some(i + 1),
-                                    }),
-                                ));
-                                break; // Only report once per script
-                            }
-                        }
+/// AS018: No undocumented magic constants
+fn as018_no_magic_constants(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    for script in &ctx.scripts {
+        // Look for numeric assignments without nearby comments
+        let patterns: &[Regex] = match script.extension().and_then(|e| e.to_str()) {
+            Some("py") => &PY_CONSTANTS,
+            Some("js" | "ts") => &JS_CONSTANTS,
+            _ => continue,
+        };
+        let Some(file) = ctx.read(script) else {
+            continue;
+        };
+        let lines: Vec<&str> = file.content.lines().collect();
+        let is_commented = |text: &str| {
+            let text = strip_script_directive(text);
+            text.contains('#') || text.contains("//")
+        };
+
+        for re in patterns {
+            for (i, line) in lines.iter().enumerate() {
+                if re.is_match(line) {
+                    // Check if previous line or current line has a comment
+                    let has_comment = (i > 0 && is_commented(lines[i - 1])) || is_commented(line);
+
+                    if !has_comment {
+                        violations.push(Diagnostic::new(format!(
+                                "Undocumented constant in {} line {}: add comment explaining the value",
+                                script.file_name().unwrap().to_string_lossy(),
+                                i + 1
+                            ),
+                            Some(ViolationLocation::Script {
+                                path: script.clone(),
+                                line: Some(i + 1),
+                            }),
+                        ));
+                        break; // Only report once per script
                     }
                 }
             }
         }
-
-        violations
     }
 
-    /// AS019: Workflows use numbered steps and checkboxes
-    fn check_as019_numbered_workflow(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    violations
+}
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            // Check for workflow-like sections
-            let workflow_indicators = [
-                "## Workflow",
-                "## Process",
-                "## Steps",
-                "## Procedure",
-                "multi-step",
-            ];
+/// AS019: Workflows use numbered steps and checkboxes
+fn as019_numbered_workflow(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    if let Some(file) = &ctx.skill_md {
+        let content = &file.content;
+        // Check for workflow-like sections
+        let workflow_indicators = [
+            "## Workflow",
+            "## Process",
+            "## Steps",
+            "## Procedure",
+            "multi-step",
+        ];
 
-            let has_workflow_section = workflow_indicators.iter().any(|ind| content.contains(ind));
-
-            if has_workflow_section {
-                // Check for numbered lists or checkboxes
-                let has_numbered_list = Regex::new(r"(?m)^\d+\.\s+").unwrap().is_match(&content);
-                let has_checkboxes = content.contains("- [ ]");
-
-                if !has_numbered_list && !has_checkboxes {
-                    violations.push(self.violation(
-                        BestPracticeCode::AS019,
-                        "Workflow found but not using numbered lists (1. 2. 3.) or checkboxes (- [ ])",
-                        Some(ViolationLocation::File {
-                            path: skill.skill_md_path.clone(),
-                            line: None,
-                        }),
-                    ));
-                }
+        let workflow_start = workflow_indicators
+            .iter()
+            .filter_map(|ind| content.find(ind))
+            .min();
+
+        if let Some(workflow_start) = workflow_start {
+            // Check for numbered lists or checkboxes
+            let has_numbered_list = NUMBERED_LIST.is_match(content);
+            let has_checkboxes = content.contains("- [ ]");
+
+            if !has_numbered_list && !has_checkboxes {
+                violations.push(Diagnostic::new(
+                    "Workflow found but not using numbered lists (1. 2. 3.) or checkboxes (- [ ])",
+                    file.location(workflow_start),
+                ));
             }
         }
+    }
 
-        violations
+    violations
+}
+
+/// AS020: TOC completeness (matches actual headers)
+fn as020_toc_completeness(ctx: &SkillContext) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    let Some(file) = &ctx.skill_md else {
+        return violations;
+    };
+    let content = &file.content;
+    if !has_table_of_contents(content) {
+        return violations;
     }
 
-    /// AS020: TOC completeness (matches actual headers)
-    fn check_as020_toc_completeness(&self, skill: &Skill) -> Vec<BestPracticeViolation> {
-        let mut violations = Vec::new();
+    // Extract TOC links
+    let toc_anchors: Vec<&str> = ANCHOR_LINK
+        .captures_iter(content)
+        .filter_map(|cap| Some(cap.get(2)?.as_str()))
+        .collect();
+
+    // Level 2 headings (excluding TOC headings themselves)
+    let headings: Vec<&Heading> = ctx
+        .headings
+        .iter()
+        .filter(|h| h.level == 2 && !h.is_toc_title())
+        .collect();
+
+    // A generated TOC can be refreshed in place
+    let refresh = content
+        .contains(toc::TOC_START)
+        .then(|| toc::toc_edit(content))
+        .flatten();
+
+    // Report each level 2 heading missing from the TOC
+    for heading in &headings {
+        if toc_anchors.contains(&heading.anchor.as_str()) {
+            continue;
+        }
+        let violation = Diagnostic::new(
+            format!(
+                "TOC incomplete: header '{}' is missing ({} headers total, {} in TOC)",
+                heading.text,
+                headings.len(),
+                toc_anchors.len()
+            ),
+            file.location(heading.offset),
+        );
+        violations.push(match &refresh {
+            Some(edit) => violation.with_fix(Fix::safe(&file.path, vec![edit.clone()])),
+            None => violation,
+        });
+    }
 
-        if let Ok(content) = std::fs::read_to_string(&skill.skill_md_path) {
-            if !has_table_of_contents(&content) {
-                return violations;
-            }
+    violations
+}
 
-            // Extract TOC links
-            let toc_re = Regex::new(r"\[([^\]]+)\]\(#([^)]+)\)").unwrap();
-            let toc_anchors: Vec<String> = toc_re
-                .captures_iter(&content)
-                .map(|cap| cap[2].to_string())
-                .collect();
+/// AS021: Relative links point to existing files
+fn as021_broken_links(ctx: &SkillContext) -> Vec<Diagnostic> {
+    check_links(ctx, BestPracticeCode::AS021)
+}
 
-            // Extract actual headers (excluding TOC headers themselves)
-            let headers = extract_headers(&content);
-            let toc_keywords = ["table of contents", "contents", "toc"];
-            let header_anchors: Vec<String> = headers
-                .iter()
-                .filter(|h| {
-                    let lower = h.to_lowercase();
-                    !toc_keywords.iter().any(|kw| lower == *kw)
-                })
-                .map(|h| Self::header_to_anchor(h))
-                .collect();
+/// AS022: Link anchors match a heading in the target document
+fn as022_broken_anchors(ctx: &SkillContext) -> Vec<Diagnostic> {
+    check_links(ctx, BestPracticeCode::AS022)
+}
 
-            // Check if all level 2 headers are in TOC
-            let missing_in_toc: Vec<_> = header_anchors
-                .iter()
-                .filter(|anchor| !toc_anchors.contains(anchor))
-                .collect();
+/// AS023: Images point to existing files
+fn as023_broken_images(ctx: &SkillContext) -> Vec<Diagnostic> {
+    check_links(ctx, BestPracticeCode::AS023)
+}
 
-            if !missing_in_toc.is_empty() {
-                violations.push(self.violation(
-                    BestPracticeCode::AS020,
-                    format!(
-                        "TOC incomplete: missing {} header(s) ({} headers total, {} in TOC)",
-                        missing_in_toc.len(),
-                        header_anchors.len(),
-                        toc_anchors.len()
-                    ),
-                    Some(ViolationLocation::File {
-                        path: skill.skill_md_path.clone(),
-                        line: None,
-                    }),
-                ));
+/// AS024: Reference-style links have a definition
+fn as024_undefined_references(ctx: &SkillContext) -> Vec<Diagnostic> {
+    check_links(ctx, BestPracticeCode::AS024)
+}
+
+/// Findings of one of the link checks (AS021-AS024) across every markdown
+/// file in the skill
+fn check_links(ctx: &SkillContext, code: BestPracticeCode) -> Vec<Diagnostic> {
+    let mut violations = Vec::new();
+
+    for (file, found) in ctx.markdown_links() {
+        let path = &file.path;
+        if code == BestPracticeCode::AS024 {
+            for reference in &found.references {
+                if !found
+                    .definitions
+                    .contains_key(&links::normalize_label(&reference.label))
+                {
+                    violations.push(Diagnostic::new(
+                        format!(
+                            "Undefined reference: no definition for '[{}]'",
+                            reference.label
+                        ),
+                        file.location(reference.offset),
+                    ));
+                }
             }
+            continue;
         }
 
-        violations
-    }
-
-    /// Convert header text to GitHub-style anchor
-    fn header_to_anchor(header: &str) -> String {
-        header
-            .to_lowercase()
-            .trim()
-            .replace(' ', "-")
-            .replace(|c: char| !c.is_alphanumeric() && c != '-', "")
-    }
-
-    /// Extract markdown body (content after frontmatter)
-    fn extract_body(content: &str) -> Option<String> {
-        let mut in_frontmatter = false;
-        let mut frontmatter_count = 0;
-        let mut body_lines = Vec::new();
+        // A definition is an image when only images use it
+        let definitions = found.definitions.iter().map(|(label, link)| {
+            let mut uses = found
+                .references
+                .iter()
+                .filter(|r| links::normalize_label(&r.label) == *label)
+                .peekable();
+            let image = uses.peek().is_some() && uses.all(|r| r.image);
+            Link {
+                image,
+                ..link.clone()
+            }
+        });
+        let mut checked: Vec<Link> = found.links.iter().cloned().chain(definitions).collect();
+        checked.sort_by_key(|link| link.offset);
 
-        for line in content.lines() {
-            if line.trim() == "---" {
-                frontmatter_count += 1;
-                if frontmatter_count == 1 {
-                    in_frontmatter = true;
-                } else if frontmatter_count == 2 {
-                    in_frontmatter = false;
+        let dir = path.parent().unwrap_or(&ctx.skill.root);
+        for link in checked {
+            let Some(target) = links::target(&link.destination) else {
+                continue;
+            };
+            let resolved = match &target.path {
+                Some(relative) => dir.join(relative),
+                None => path.clone(),
+            };
+
+            if !resolved.exists() {
+                let (wanted, kind) = if link.image {
+                    (BestPracticeCode::AS023, "image")
+                } else {
+                    (BestPracticeCode::AS021, "link")
+                };
+                if code == wanted {
+                    violations.push(Diagnostic::new(
+                        format!("Broken {}: '{}' does not exist", kind, link.destination),
+                        file.location(link.offset),
+                    ));
                 }
                 continue;
             }
 
-            if !in_frontmatter && frontmatter_count >= 2 {
-                body_lines.push(line);
+            // Only markdown targets have heading anchors
+            let Some(fragment) = &target.fragment else {
+                continue;
+            };
+            if code != BestPracticeCode::AS022 || link.image || !links::is_markdown(&resolved) {
+                continue;
+            }
+            if !links::has_anchor(&ctx.anchors(&resolved), fragment) {
+                let name = resolved
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                violations.push(Diagnostic::new(
+                    format!("Broken anchor: no heading '#{}' in {}", fragment, name),
+                    file.location(link.offset),
+                ));
             }
-        }
-
-        if body_lines.is_empty() {
-            None
-        } else {
-            Some(body_lines.join("\n"))
         }
     }
 
-    /// Extract markdown links from content
-    fn extract_markdown_links(content: &str) -> Vec<String> {
-        let re = Regex::new(r"\[([^\]]+)\]\(([^)]+\.md)\)").unwrap();
-        re.captures_iter(content)
-            .filter_map(|cap| cap.get(2).map(|m| m.as_str().to_string()))
-            .filter(|link| !link.starts_with("http://") && !link.starts_with("https://"))
-            .collect()
-    }
+    violations
+}
+
+/// Extract markdown links from content, with the byte offset of each link
+fn extract_markdown_links(content: &str) -> Vec<(usize, String)> {
+    MARKDOWN_LINK
+        .captures_iter(content)
+        .filter_map(|cap| {
+            let start = cap.get(0)?.start();
+            cap.get(2).map(|m| (start, m.as_str().to_string()))
+        })
+        .filter(|(_, link)| !link.starts_with("http://") && !link.starts_with("https://"))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// A skill named `claude-helper` with an empty SKILL.md body
+    fn claude_helper(dir: &std::path::Path) -> Skill {
+        let root = dir.join("claude-helper");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(root.join("SKILL.md"), "---\nname: claude-helper\n---\n").unwrap();
+
+        Skill {
+            skill_md_path: root.join("SKILL.md"),
+            root,
+            metadata: SkillMetadata {
+                name: "claude-helper".to_string(),
+                description: "I help".to_string(),
+                license: None,
+                compatibility: None,
+                allowed_tools: None,
+                metadata: std::collections::HashMap::new(),
+                all_fields: std::collections::HashSet::new(),
+                spans: Default::default(),
+            },
+            source: None,
+        }
+    }
+
     #[test]
     fn test_strict_mode_severity() {
-        let validator_warning = BestPracticesValidator::new(false);
-        assert_eq!(validator_warning.severity(), Severity::Warning);
+        let dir = tempfile::TempDir::new().unwrap();
+        let skill = claude_helper(dir.path());
+
+        let severity = |strict: bool| {
+            BestPracticesValidator::new(strict)
+                .validate(&skill)
+                .into_iter()
+                .find(|v| v.code == BestPracticeCode::AS014)
+                .unwrap()
+                .severity
+        };
+        assert_eq!(severity(false), Severity::Warning);
+        assert_eq!(severity(true), Severity::Error);
+    }
 
-        let validator_error = BestPracticesValidator::new(true);
-        assert_eq!(validator_error.severity(), Severity::Error);
+    #[test]
+    fn test_rule_overrides_applied() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let skill = claude_helper(dir.path());
+
+        let mut levels = std::collections::HashMap::new();
+        levels.insert(BestPracticeCode::AS003, RuleLevel::Info);
+        levels.insert(BestPracticeCode::AS016, RuleLevel::Error);
+        let validator = BestPracticesValidator::new(false).with_rules(RuleSettings {
+            ignore: vec!["AS001".to_string()],
+            levels,
+            ..Default::default()
+        });
+        let violations = validator.validate(&skill);
+
+        assert!(!violations.iter().any(|v| v.code == BestPracticeCode::AS001));
+        assert!(
+            violations
+                .iter()
+                .any(|v| v.code == BestPracticeCode::AS003 && v.severity == Severity::Info)
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|v| v.code == BestPracticeCode::AS016 && v.severity == Severity::Error)
+        );
+        assert!(
+            violations
+                .iter()
+                .any(|v| v.code == BestPracticeCode::AS014 && v.severity == Severity::Warning)
+        );
     }
 }
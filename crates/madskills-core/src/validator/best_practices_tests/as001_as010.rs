@@ -32,12 +32,26 @@ fn setup_skill(name: &str, description: &str, body: &str) -> (TempDir, Skill) {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     (dir, skill)
 }
 
+/// Line and column of a file-level violation
+fn position(violation: &BestPracticeViolation) -> Option<(usize, usize)> {
+    match violation.location {
+        Some(ViolationLocation::File {
+            line: Some(line),
+            column: Some(column),
+            ..
+        }) => Some((line, column)),
+        _ => None,
+    }
+}
+
 // AS001: Name format validation
 
 #[test]
@@ -219,6 +233,66 @@ fn test_as005_backslashes_in_paths() {
     );
 }
 
+#[test]
+fn test_as005_reports_each_path() {
+    let body = "Run scripts\\setup.py first.\nThen open docs\\guide.md";
+    let (_dir, skill) = setup_skill("test-skill", "Test", body);
+    let validator = BestPracticesValidator::new(false);
+    let violations: Vec<_> = validator
+        .validate(&skill)
+        .into_iter()
+        .filter(|v| v.code == BestPracticeCode::AS005)
+        .collect();
+
+    assert_eq!(violations.len(), 2);
+    assert!(violations[0].message.contains("'scripts\\setup.py'"));
+    assert_eq!(position(&violations[0]), Some((6, 5)));
+    assert_eq!(position(&violations[1]), Some((7, 11)));
+}
+
+#[test]
+fn test_as005_fix() {
+    let body = "Run scripts\\tools\\setup.py first.";
+    let (_dir, skill) = setup_skill("test-skill", "Test", body);
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+    let fix = violations
+        .iter()
+        .find(|v| v.code == BestPracticeCode::AS005)
+        .and_then(|v| v.fix.as_ref())
+        .unwrap();
+    assert_eq!(fix.applicability, Applicability::Safe);
+
+    let content = fs::read_to_string(&skill.skill_md_path).unwrap();
+    let (fixed, applied) = crate::fix::apply_fixes(&content, &[fix]);
+    assert_eq!(applied, 1);
+    assert!(fixed.ends_with("Run scripts/tools/setup.py first."));
+}
+
+#[test]
+fn test_as005_skips_code() {
+    let body = "```bash\nprintf \"a\\nb\"\n```\n\nUse `a\\nb` to split lines.";
+    let (_dir, skill) = setup_skill("test-skill", "Test", body);
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+
+    assert!(!violations.iter().any(|v| v.code == BestPracticeCode::AS005));
+}
+
+#[test]
+fn test_as005_fix_unsafe_unless_path() {
+    let body = "Split on a\\nb here.";
+    let (_dir, skill) = setup_skill("test-skill", "Test", body);
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+    let fix = violations
+        .iter()
+        .find(|v| v.code == BestPracticeCode::AS005)
+        .and_then(|v| v.fix.as_ref())
+        .unwrap();
+    assert_eq!(fix.applicability, Applicability::Unsafe);
+}
+
 #[test]
 fn test_as005_forward_slashes_valid() {
     let body = "See [guide](reference/guide.md) for details";
@@ -261,7 +335,9 @@ fn test_as006_nested_references() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -303,7 +379,9 @@ fn test_as006_one_level_valid() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -342,7 +420,9 @@ fn test_as007_generic_doc_names() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -384,7 +464,9 @@ fn test_as007_descriptive_names_valid() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -426,7 +508,9 @@ fn test_as008_long_file_no_toc() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -471,7 +555,9 @@ fn test_as008_long_file_with_toc() {
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     let validator = BestPracticesValidator::new(false);
@@ -480,6 +566,31 @@ fn test_as008_long_file_with_toc() {
     assert!(!violations.iter().any(|v| v.code == BestPracticeCode::AS008));
 }
 
+#[test]
+fn test_as008_fix_inserts_toc() {
+    let (_dir, skill) = setup_skill("test-skill", "Test", "# Test\n");
+    let reference = skill.root.join("reference.md");
+    let content = format!(
+        "# Reference\n\n## Setup\n\n{}## Usage\n",
+        "Line\n".repeat(150)
+    );
+    fs::write(&reference, &content).unwrap();
+
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+    let fix = violations
+        .iter()
+        .find(|v| v.code == BestPracticeCode::AS008)
+        .and_then(|v| v.fix.as_ref())
+        .unwrap();
+    assert_eq!(fix.path, reference);
+
+    let (fixed, _) = crate::fix::apply_fixes(&content, &[fix]);
+    assert!(fixed.starts_with("# Reference\n\n<!-- madskills-toc-start -->\n"));
+    assert!(fixed.contains("- [Setup](#setup)\n- [Usage](#usage)\n"));
+    assert!(crate::validator::helpers::has_table_of_contents(&fixed));
+}
+
 // AS009: MCP tool format
 
 #[test]
@@ -506,6 +617,23 @@ fn test_as009_qualified_mcp_tool_valid() {
 
 // AS010: No absolute dates
 
+#[test]
+fn test_as010_reports_each_date() {
+    let body = "Before August 2025, use the old API.\n\nReleased on 2024-03-01.";
+    let (_dir, skill) = setup_skill("test-skill", "Test", body);
+    let validator = BestPracticesValidator::new(false);
+    let violations: Vec<_> = validator
+        .validate(&skill)
+        .into_iter()
+        .filter(|v| v.code == BestPracticeCode::AS010)
+        .collect();
+
+    assert_eq!(violations.len(), 2);
+    assert!(violations[0].message.contains("'Before August 2025'"));
+    assert_eq!(position(&violations[0]), Some((6, 1)));
+    assert_eq!(position(&violations[1]), Some((8, 13)));
+}
+
 #[test]
 fn test_as010_absolute_date_month_year() {
     let body = "Before August 2025, use the old API";
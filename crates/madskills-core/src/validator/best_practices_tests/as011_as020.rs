@@ -40,7 +40,9 @@ fn setup_skill_with_files(
             allowed_tools: None,
             metadata: HashMap::new(),
             all_fields,
+            spans: Default::default(),
         },
+        source: None,
     };
 
     (dir, skill)
@@ -372,6 +374,30 @@ print(result)
     assert_eq!(as017_violations.len(), 1);
 }
 
+#[test]
+fn test_as017_bash_fix_is_unsafe() {
+    let script_content = "#!/bin/bash\ncp a b\n";
+
+    let (_dir, skill) = setup_skill_with_files(
+        "test-skill",
+        "Test",
+        "Body",
+        vec![("copy.sh", script_content)],
+    );
+
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+    let fix = violations
+        .iter()
+        .find(|v| v.code == BestPracticeCode::AS017)
+        .and_then(|v| v.fix.as_ref())
+        .unwrap();
+    assert_eq!(fix.applicability, Applicability::Unsafe);
+
+    let (fixed, _) = crate::fix::apply_fixes(script_content, &[fix]);
+    assert_eq!(fixed, "#!/bin/bash\nset -e\ncp a b\n");
+}
+
 #[test]
 fn test_as017_bash_with_error_handling() {
     let script_content = r#"#!/bin/bash
@@ -551,6 +577,54 @@ More content not in TOC!"#;
         .filter(|v| v.code == BestPracticeCode::AS020)
         .collect();
     assert_eq!(as020_violations.len(), 1);
+    assert!(as020_violations[0].message.contains("'Usage'"));
+    assert!(matches!(
+        as020_violations[0].location,
+        Some(ViolationLocation::File {
+            line: Some(14),
+            column: Some(1),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_as020_refreshes_generated_toc() {
+    let content = r#"<!-- madskills-toc-start -->
+
+## Contents
+
+- [Introduction](#introduction)
+
+<!-- madskills-toc-end -->
+
+## Introduction
+
+## Usage
+
+## Usage
+"#;
+
+    let (_dir, skill) = setup_skill_with_files("test-skill", "Test", content, vec![]);
+
+    let validator = BestPracticesValidator::new(false);
+    let violations = validator.validate(&skill);
+    let as020_violations: Vec<_> = violations
+        .iter()
+        .filter(|v| v.code == BestPracticeCode::AS020)
+        .collect();
+    assert_eq!(as020_violations.len(), 2);
+
+    let fixes: Vec<&Fix> = as020_violations
+        .iter()
+        .filter_map(|v| v.fix.as_ref())
+        .collect();
+    let original = fs::read_to_string(&skill.skill_md_path).unwrap();
+    let (fixed, applied) = crate::fix::apply_fixes(&original, &fixes);
+    assert_eq!(applied, 1);
+    assert!(
+        fixed.contains("- [Introduction](#introduction)\n- [Usage](#usage)\n- [Usage](#usage-1)\n")
+    );
 }
 
 #[test]
@@ -0,0 +1,265 @@
+use crate::models::*;
+use crate::validator::best_practices::BestPracticesValidator;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use tempfile::TempDir;
+
+/// A skill whose SKILL.md body starts on line 6, with extra files at paths
+/// relative to the skill root
+fn setup_skill_with_files(body: &str, files: Vec<(&str, &str)>) -> (TempDir, Skill) {
+    let dir = TempDir::new().unwrap();
+    let skill_path = dir.path().join("test-skill");
+    fs::create_dir(&skill_path).unwrap();
+
+    let content = format!("---\nname: test-skill\ndescription: Test\n---\n\n{}", body);
+    fs::write(skill_path.join("SKILL.md"), content).unwrap();
+
+    for (filename, file_content) in files {
+        let path = skill_path.join(filename);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, file_content).unwrap();
+    }
+
+    let mut all_fields = HashSet::new();
+    all_fields.insert("name".to_string());
+    all_fields.insert("description".to_string());
+
+    let skill = Skill {
+        root: skill_path.clone(),
+        skill_md_path: skill_path.join("SKILL.md"),
+        metadata: SkillMetadata {
+            name: "test-skill".to_string(),
+            description: "Test".to_string(),
+            license: None,
+            compatibility: None,
+            allowed_tools: None,
+            metadata: HashMap::new(),
+            all_fields,
+            spans: Default::default(),
+        },
+        source: None,
+    };
+
+    (dir, skill)
+}
+
+/// Violations of one rule, with the file name, line and column of each
+fn findings(skill: &Skill, code: BestPracticeCode) -> Vec<(String, String, usize, usize)> {
+    BestPracticesValidator::new(false)
+        .validate(skill)
+        .into_iter()
+        .filter(|v| v.code == code)
+        .map(|v| match v.location {
+            Some(ViolationLocation::File {
+                path,
+                line: Some(line),
+                column: Some(column),
+            }) => (
+                v.message,
+                path.file_name().unwrap().to_string_lossy().into_owned(),
+                line,
+                column,
+            ),
+            location => panic!("unexpected location {:?}", location),
+        })
+        .collect()
+}
+
+// AS021: Broken file links
+
+#[test]
+fn test_as021_existing_links() {
+    let (_dir, skill) = setup_skill_with_files(
+        "See [forms](FORMS.md), run [the script](scripts/run.py) and read [the docs](https://example.com/missing.md).\n\nAlso [the reference](docs/my%20guide.md?plain=1) and [templates](templates/).\n",
+        vec![
+            ("FORMS.md", "# Forms\n"),
+            ("scripts/run.py", "print('ok')\n"),
+            ("docs/my guide.md", "# Guide\n"),
+            ("templates/report.md", "# Report\n"),
+        ],
+    );
+
+    assert!(findings(&skill, BestPracticeCode::AS021).is_empty());
+}
+
+#[test]
+fn test_as021_missing_targets() {
+    let (_dir, skill) = setup_skill_with_files(
+        "Run [the script](scripts/gone.py).\n\nSee [the guide][guide] and `[ignored](nope.md)`.\n\n```markdown\n[also ignored](nope.md)\n```\n\n[guide]: GUIDE.md\n",
+        vec![],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS021),
+        vec![
+            (
+                "Broken link: 'scripts/gone.py' does not exist".to_string(),
+                "SKILL.md".to_string(),
+                6,
+                18
+            ),
+            (
+                "Broken link: 'GUIDE.md' does not exist".to_string(),
+                "SKILL.md".to_string(),
+                14,
+                10
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_as021_resolves_relative_to_linking_file() {
+    let (_dir, skill) = setup_skill_with_files(
+        "See [the API](reference/api.md).\n",
+        vec![
+            (
+                "reference/api.md",
+                "# API\n\nSee [errors](errors.md) and [forms](../FORMS.md) and [home](../SKILL.md).\n",
+            ),
+            ("reference/errors.md", "# Errors\n"),
+        ],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS021),
+        vec![(
+            "Broken link: '../FORMS.md' does not exist".to_string(),
+            "api.md".to_string(),
+            3,
+            37
+        )]
+    );
+}
+
+#[test]
+fn test_as021_skips_excluded_files() {
+    use crate::discovery::PathFilter;
+    use crate::validator::{ValidationConfig, Validator};
+
+    let (dir, skill) = setup_skill_with_files(
+        "See [the notes](drafts/notes.md).\n",
+        vec![("drafts/notes.md", "# Notes\n\nSee [todo](todo.md).\n")],
+    );
+    let validator = Validator::new(ValidationConfig {
+        exclude: PathFilter::new(dir.path(), &["drafts/".to_string()]).unwrap(),
+        ..Default::default()
+    });
+
+    let broken: Vec<_> = validator
+        .validate_skill(&skill)
+        .best_practice_violations
+        .into_iter()
+        .filter(|v| v.code == BestPracticeCode::AS021)
+        .collect();
+    assert!(broken.is_empty(), "{:?}", broken);
+    assert_eq!(findings(&skill, BestPracticeCode::AS021).len(), 1);
+}
+
+// AS022: Broken anchors
+
+#[test]
+fn test_as022_same_file_anchors() {
+    let (_dir, skill) = setup_skill_with_files(
+        "## Quick Start\n\n## Quick Start\n\n<a id=\"legacy\"></a>\n\nSee [start](#quick-start), [again](#Quick-Start-1), [old](#legacy) and [typo](#quickstart).\n",
+        vec![],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS022),
+        vec![(
+            "Broken anchor: no heading '#quickstart' in SKILL.md".to_string(),
+            "SKILL.md".to_string(),
+            12,
+            79
+        )]
+    );
+}
+
+#[test]
+fn test_as022_cross_file_anchors() {
+    let (_dir, skill) = setup_skill_with_files(
+        "See [types](FORMS.md#field-types), [missing](FORMS.md#fields) and [line](scripts/run.py#L3).\n",
+        vec![
+            (
+                "FORMS.md",
+                "# Forms\n\n## Field Types\n\n```\n## Fields\n```\n",
+            ),
+            ("scripts/run.py", "print('ok')\n"),
+        ],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS022),
+        vec![(
+            "Broken anchor: no heading '#fields' in FORMS.md".to_string(),
+            "SKILL.md".to_string(),
+            6,
+            46
+        )]
+    );
+}
+
+#[test]
+fn test_as022_skips_missing_files() {
+    let (_dir, skill) = setup_skill_with_files("See [gone](GONE.md#setup).\n", vec![]);
+
+    assert!(findings(&skill, BestPracticeCode::AS022).is_empty());
+    assert_eq!(findings(&skill, BestPracticeCode::AS021).len(), 1);
+}
+
+// AS023: Broken images
+
+#[test]
+fn test_as023_broken_images() {
+    let (_dir, skill) = setup_skill_with_files(
+        "![flow](images/flow.png) ![arch](images/arch.png) ![remote](https://example.com/a.png)\n\n[![badge](images/badge.svg)](FORMS.md) ![logo][logo]\n\n[logo]: images/logo.png\n",
+        vec![("images/flow.png", ""), ("FORMS.md", "# Forms\n")],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS023),
+        vec![
+            (
+                "Broken image: 'images/arch.png' does not exist".to_string(),
+                "SKILL.md".to_string(),
+                6,
+                34
+            ),
+            (
+                "Broken image: 'images/badge.svg' does not exist".to_string(),
+                "SKILL.md".to_string(),
+                8,
+                11
+            ),
+            (
+                "Broken image: 'images/logo.png' does not exist".to_string(),
+                "SKILL.md".to_string(),
+                10,
+                9
+            ),
+        ]
+    );
+    assert!(findings(&skill, BestPracticeCode::AS021).is_empty());
+}
+
+// AS024: Undefined references
+
+#[test]
+fn test_as024_undefined_references() {
+    let (_dir, skill) = setup_skill_with_files(
+        "Use [the guide][Form  Guide], the [API reference][] and [setup][missing].\n\nCode like `matrix[0][1]` is fine.\n\n[form guide]: FORMS.md\n[api reference]: FORMS.md#api\n",
+        vec![("FORMS.md", "# Forms\n\n## API\n")],
+    );
+
+    assert_eq!(
+        findings(&skill, BestPracticeCode::AS024),
+        vec![(
+            "Undefined reference: no definition for '[missing]'".to_string(),
+            "SKILL.md".to_string(),
+            6,
+            57
+        )]
+    );
+    assert!(findings(&skill, BestPracticeCode::AS022).is_empty());
+}
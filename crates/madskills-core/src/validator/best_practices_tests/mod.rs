@@ -1,2 +1,3 @@
 mod as001_as010;
 mod as011_as020;
+mod as021_as024;
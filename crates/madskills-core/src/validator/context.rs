@@ -0,0 +1,258 @@
+//! Per-skill state shared by every check
+//!
+//! Each file a skill's checks look at is read from disk once and indexed by
+//! line, so rules work from the same content instead of re-reading it.
+
+use crate::discovery::PathFilter;
+use crate::models::{Skill, ViolationLocation};
+use crate::toc::{self, Heading};
+use crate::validator::helpers::{body_line_offsets, find_script_files, list_skill_files};
+use crate::validator::links::{self, MarkdownLinks};
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// A file's content with the byte offset of each line
+#[derive(Debug)]
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub content: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(path: PathBuf, content: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            path,
+            content,
+            line_starts,
+        }
+    }
+
+    /// Read a file, or `None` when it cannot be read as UTF-8
+    pub fn read(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(Self::new(path.to_path_buf(), content))
+    }
+
+    /// 1-indexed line and column (in characters) of a byte offset
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let index = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.content[self.line_starts[index]..offset]
+            .chars()
+            .count()
+            + 1;
+        (index + 1, column)
+    }
+
+    /// Violation location of a byte offset
+    pub fn location(&self, offset: usize) -> Option<ViolationLocation> {
+        let (line, column) = self.line_col(offset);
+        Some(ViolationLocation::File {
+            path: self.path.clone(),
+            line: Some(line),
+            column: Some(column),
+        })
+    }
+}
+
+/// Everything the checks need to know about one skill
+pub struct SkillContext<'a> {
+    pub skill: &'a Skill,
+    /// SKILL.md, unless it could not be read
+    pub skill_md: Option<Rc<SourceFile>>,
+    /// SKILL.md body (content after the frontmatter), if it has one
+    pub body: Option<String>,
+    /// Byte offsets of the SKILL.md body lines
+    pub body_offsets: Vec<usize>,
+    /// SKILL.md headings
+    pub headings: Vec<Heading>,
+    /// Files directly in the skill root, sorted
+    pub files: Vec<PathBuf>,
+    /// Scripts directly in the skill root, sorted
+    pub scripts: Vec<PathBuf>,
+    /// Files left out of [`all_files`](Self::all_files)
+    exclude: PathFilter,
+    tree: OnceCell<Vec<PathBuf>>,
+    links: OnceCell<Vec<(Rc<SourceFile>, MarkdownLinks)>>,
+    anchors: RefCell<HashMap<PathBuf, Rc<Vec<String>>>>,
+    cache: RefCell<HashMap<PathBuf, Option<Rc<SourceFile>>>>,
+}
+
+impl<'a> SkillContext<'a> {
+    pub fn new(skill: &'a Skill) -> Self {
+        Self::with_contents(skill, &HashMap::new())
+    }
+
+    /// Context reading `contents` in place of the files on disk
+    ///
+    /// Used to re-lint fixed content before it is written.
+    pub fn with_contents(skill: &'a Skill, contents: &HashMap<PathBuf, String>) -> Self {
+        let mut files = list_skill_files(&skill.root);
+        files.sort();
+        let mut scripts = find_script_files(&skill.root);
+        scripts.sort();
+
+        let context = Self {
+            skill,
+            skill_md: None,
+            body: None,
+            body_offsets: Vec::new(),
+            headings: Vec::new(),
+            files,
+            scripts,
+            exclude: PathFilter::default(),
+            tree: OnceCell::new(),
+            links: OnceCell::new(),
+            anchors: RefCell::default(),
+            cache: RefCell::new(
+                contents
+                    .iter()
+                    .filter(|(path, _)| path.starts_with(&skill.root))
+                    .map(|(path, content)| {
+                        let file = SourceFile::new(path.clone(), content.clone());
+                        (path.clone(), Some(Rc::new(file)))
+                    })
+                    .collect(),
+            ),
+        };
+        let skill_md = context.read(&skill.skill_md_path);
+        let (body, body_offsets, headings) =
+            skill_md.as_ref().map_or_else(Default::default, |file| {
+                (
+                    extract_body(&file.content),
+                    body_line_offsets(&file.content),
+                    toc::headings(&file.content),
+                )
+            });
+
+        Self {
+            skill_md,
+            body,
+            body_offsets,
+            headings,
+            ..context
+        }
+    }
+
+    /// Leave files matching `exclude` out of [`all_files`](Self::all_files)
+    pub fn with_exclude(mut self, exclude: PathFilter) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    /// A file's content, read on first use
+    pub fn read(&self, path: &Path) -> Option<Rc<SourceFile>> {
+        self.cache
+            .borrow_mut()
+            .entry(path.to_path_buf())
+            .or_insert_with(|| SourceFile::read(path).map(Rc::new))
+            .clone()
+    }
+
+    /// Every file in the skill, including subdirectories, sorted
+    ///
+    /// Listed on first use; see [`crate::discovery::skill_files`].
+    pub fn all_files(&self) -> &[PathBuf] {
+        self.tree
+            .get_or_init(|| crate::discovery::skill_files(self.skill, &self.exclude))
+    }
+
+    /// Links in each readable markdown file of the skill, extracted on first
+    /// use
+    pub fn markdown_links(&self) -> &[(Rc<SourceFile>, MarkdownLinks)] {
+        self.links.get_or_init(|| {
+            self.all_files()
+                .iter()
+                .filter(|path| links::is_markdown(path))
+                .filter_map(|path| self.read(path))
+                .map(|file| {
+                    let found = links::extract(&file.content);
+                    (file, found)
+                })
+                .collect()
+        })
+    }
+
+    /// Anchors a markdown file defines, extracted on first use (empty when
+    /// it cannot be read)
+    pub fn anchors(&self, path: &Path) -> Rc<Vec<String>> {
+        if let Some(known) = self.anchors.borrow().get(path) {
+            return known.clone();
+        }
+        let found = Rc::new(
+            self.read(path)
+                .map(|file| links::anchors(&file.content))
+                .unwrap_or_default(),
+        );
+        self.anchors
+            .borrow_mut()
+            .insert(path.to_path_buf(), found.clone());
+        found
+    }
+
+    /// Location of the first SKILL.md body line, for findings about missing
+    /// content
+    pub fn body_location(&self) -> Option<ViolationLocation> {
+        let file = self.skill_md.as_ref()?;
+        file.location(self.body_offsets.first().copied().unwrap_or(0))
+    }
+}
+
+/// Extract markdown body (content after frontmatter)
+fn extract_body(content: &str) -> Option<String> {
+    let mut in_frontmatter = false;
+    let mut frontmatter_count = 0;
+    let mut body_lines = Vec::new();
+
+    for line in content.lines() {
+        if line.trim() == "---" {
+            frontmatter_count += 1;
+            if frontmatter_count == 1 {
+                in_frontmatter = true;
+            } else if frontmatter_count == 2 {
+                in_frontmatter = false;
+            }
+            continue;
+        }
+
+        if !in_frontmatter && frontmatter_count >= 2 {
+            body_lines.push(line);
+        }
+    }
+
+    if body_lines.is_empty() {
+        None
+    } else {
+        Some(body_lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col() {
+        let content = "first\nsecond é line\n\nlast";
+        let file = SourceFile::new(PathBuf::from("x.md"), content.to_string());
+        assert_eq!(file.line_col(0), (1, 1));
+        assert_eq!(file.line_col(6), (2, 1));
+        assert_eq!(file.line_col(content.find("line").unwrap()), (2, 10));
+        assert_eq!(file.line_col(content.find("last").unwrap()), (4, 1));
+        assert_eq!(file.line_col(content.len()), (4, 5));
+    }
+
+    #[test]
+    fn test_extract_body() {
+        assert_eq!(
+            extract_body("---\nname: x\n---\n# Title\nText\n").as_deref(),
+            Some("# Title\nText")
+        );
+        assert!(extract_body("---\nname: x\n---\n").is_none());
+    }
+}
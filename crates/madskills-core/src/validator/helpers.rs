@@ -2,11 +2,13 @@
 
 use regex::Regex;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static XML_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[a-zA-Z][a-zA-Z0-9]*>").unwrap());
 
 /// Check if text contains XML-like tags (e.g., <tag>)
 pub fn contains_xml_tags(text: &str) -> bool {
-    let re = Regex::new(r"<[a-zA-Z][a-zA-Z0-9]*>").unwrap();
-    re.is_match(text)
+    XML_TAG.is_match(text)
 }
 
 /// List all files in a skill directory (non-recursive)
@@ -52,6 +54,27 @@ pub fn count_lines(content: &str) -> usize {
     }
 }
 
+/// Byte offsets of the body lines (everything after the frontmatter)
+pub fn body_line_offsets(content: &str) -> Vec<usize> {
+    let mut delimiters = 0;
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if line.trim() == "---" && delimiters < 2 {
+            delimiters += 1;
+            continue;
+        }
+        if delimiters >= 2 {
+            offsets.push(start);
+        }
+    }
+
+    offsets
+}
+
 /// Check for first/second person pronouns
 pub fn contains_first_or_second_person(text: &str) -> bool {
     let lower = text.to_lowercase();
@@ -64,15 +87,6 @@ pub fn contains_backslashes(content: &str) -> bool {
     content.contains('\\')
 }
 
-/// Extract markdown headers from content (level 2 headers only: ##)
-pub fn extract_headers(content: &str) -> Vec<String> {
-    let re = Regex::new(r"^##\s+(.+)$").unwrap();
-    content
-        .lines()
-        .filter_map(|line| re.captures(line).map(|cap| cap[1].to_string()))
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +115,15 @@ mod tests {
         assert!(!contains_backslashes("path/to/file"));
     }
 
+    #[test]
+    fn test_body_line_offsets() {
+        let content = "---\nname: x\n---\n# Title\n\nText\n";
+        let offsets = body_line_offsets(content);
+        assert_eq!(offsets.len(), 3);
+        assert_eq!(&content[offsets[0]..offsets[1]], "# Title\n");
+        assert!(body_line_offsets("no frontmatter").is_empty());
+    }
+
     #[test]
     fn test_count_lines() {
         assert_eq!(count_lines("line1\nline2\nline3"), 3);
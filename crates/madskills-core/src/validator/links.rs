@@ -0,0 +1,396 @@
+//! Markdown link extraction for the link checks (AS021-AS024)
+//!
+//! Finds inline links and images, reference-style links and link reference
+//! definitions outside frontmatter, code fences and code spans, with the byte
+//! offset of each destination so findings point at the exact spot.
+//! [`prose_lines`] is shared with other checks that must not look at code.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// `[label]: destination "title"`
+static DEFINITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}\[([^\]]+)\]:[ \t]*(<[^>]*>|\S+)").unwrap());
+
+/// `[text](destination "title")` and `![alt](destination)`, allowing one
+/// level of nested links or images in the text
+static INLINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(!?)\[((?:[^\[\]]|!?\[[^\[\]]*\]\([^)]*\))*)\]\([ \t]*(<[^>]*>|[^)\s]*)(?:[ \t]+(?:"[^"]*"|'[^']*'|\([^)]*\)))?[ \t]*\)"#,
+    )
+    .unwrap()
+});
+
+/// `[text][label]` and `[text][]`
+static REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(!?)\[([^\[\]]+)\]\[([^\[\]]*)\]").unwrap());
+
+/// `id="x"` / `name="x"` on HTML elements, which also make anchors
+static HTML_ANCHOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"<[a-zA-Z][^>]*\s(?:id|name)=["']([^"']+)["']"#).unwrap());
+
+/// URL scheme (`https:`, `mailto:`)
+static SCHEME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*:").unwrap());
+
+/// A link or image with an inline destination, or a reference definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// `![alt](...)` rather than `[text](...)`
+    pub image: bool,
+    /// Destination as written, without angle brackets
+    pub destination: String,
+    /// Byte offset of the destination
+    pub offset: usize,
+}
+
+/// A `[text][label]` link or `![alt][label]` image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub image: bool,
+    /// Label as written (the text for collapsed `[text][]` links)
+    pub label: String,
+    /// Byte offset of the link
+    pub offset: usize,
+}
+
+/// Links found in one markdown file
+#[derive(Debug, Default)]
+pub struct MarkdownLinks {
+    /// Inline links and images
+    pub links: Vec<Link>,
+    /// Reference definitions, keyed by normalized label
+    pub definitions: HashMap<String, Link>,
+    /// Reference-style links
+    pub references: Vec<Reference>,
+}
+
+/// A relative destination split into file and fragment
+#[derive(Debug, PartialEq, Eq)]
+pub struct Target {
+    /// Path relative to the linking file, `None` for `#fragment` links
+    pub path: Option<PathBuf>,
+    pub fragment: Option<String>,
+}
+
+/// A markdown line outside code fences, with code spans blanked out
+#[derive(Debug)]
+pub struct ProseLine {
+    /// Byte offset of the line start
+    pub offset: usize,
+    /// Line text without its terminator; code spans are replaced by spaces,
+    /// so byte offsets still line up with the source
+    pub text: String,
+    /// Whether the line is part of the frontmatter
+    pub frontmatter: bool,
+}
+
+/// Lines of `content` that are not inside code fences, with code spans
+/// masked; fence delimiter lines are skipped too
+pub fn prose_lines(content: &str) -> Vec<ProseLine> {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_frontmatter = false;
+    let mut offset = 0;
+
+    for (idx, raw) in content.split_inclusive('\n').enumerate() {
+        let start = offset;
+        offset += raw.len();
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim_start();
+
+        if idx == 0 && line == "---" {
+            in_frontmatter = true;
+            continue;
+        }
+        if in_frontmatter {
+            in_frontmatter = line != "---";
+            if in_frontmatter {
+                lines.push(ProseLine {
+                    offset: start,
+                    text: line.to_string(),
+                    frontmatter: true,
+                });
+            }
+            continue;
+        }
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+
+        lines.push(ProseLine {
+            offset: start,
+            text: mask_code_spans(line),
+            frontmatter: false,
+        });
+    }
+
+    lines
+}
+
+/// Extract links from markdown content
+pub fn extract(content: &str) -> MarkdownLinks {
+    let mut found = MarkdownLinks::default();
+
+    for prose in prose_lines(content) {
+        if prose.frontmatter {
+            continue;
+        }
+        let (start, line) = (prose.offset, prose.text);
+        if let Some(cap) = DEFINITION.captures(&line) {
+            let destination = cap.get(2).unwrap();
+            found
+                .definitions
+                .entry(normalize_label(&cap[1]))
+                .or_insert_with(|| link(false, destination.as_str(), start + destination.start()));
+            continue;
+        }
+        extract_inline(&line, start, &mut found.links);
+        for cap in REFERENCE.captures_iter(&line) {
+            let whole = cap.get(0).unwrap();
+            // `[text](url)[other]` is an inline link followed by text
+            if line[..whole.start()].ends_with(')') {
+                continue;
+            }
+            let label = if cap[3].trim().is_empty() {
+                &cap[2]
+            } else {
+                &cap[3]
+            };
+            found.references.push(Reference {
+                image: !cap[1].is_empty(),
+                label: label.to_string(),
+                offset: start + whole.start(),
+            });
+        }
+    }
+
+    found
+}
+
+/// Inline links in `text`, including links nested in link text
+fn extract_inline(text: &str, base: usize, links: &mut Vec<Link>) {
+    for cap in INLINE.captures_iter(text) {
+        let destination = cap.get(3).unwrap();
+        links.push(link(
+            !cap[1].is_empty(),
+            destination.as_str(),
+            base + destination.start(),
+        ));
+        let inner = cap.get(2).unwrap();
+        extract_inline(inner.as_str(), base + inner.start(), links);
+    }
+}
+
+fn link(image: bool, destination: &str, offset: usize) -> Link {
+    match destination
+        .strip_prefix('<')
+        .and_then(|d| d.strip_suffix('>'))
+    {
+        Some(inner) => Link {
+            image,
+            destination: inner.to_string(),
+            offset: offset + 1,
+        },
+        None => Link {
+            image,
+            destination: destination.to_string(),
+            offset,
+        },
+    }
+}
+
+/// Blank out code spans so links inside them are ignored
+fn mask_code_spans(line: &str) -> String {
+    let mut masked = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(open) = rest.find('`') {
+        let ticks = rest[open..].len() - rest[open..].trim_start_matches('`').len();
+        let fence = &rest[open..open + ticks];
+        let after = &rest[open + ticks..];
+        let Some(close) = after.find(fence) else {
+            break;
+        };
+        masked.push_str(&rest[..open]);
+        let span = ticks * 2 + close;
+        masked.push_str(&" ".repeat(span));
+        rest = &rest[open + span..];
+    }
+
+    masked.push_str(rest);
+    masked
+}
+
+/// Case-insensitive label with runs of whitespace collapsed
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Split a destination into path and fragment, or `None` when it is not a
+/// relative link (URLs, `mailto:`, absolute paths)
+pub fn target(destination: &str) -> Option<Target> {
+    if destination.is_empty()
+        || SCHEME.is_match(destination)
+        || destination.starts_with('/')
+        || destination.starts_with('\\')
+    {
+        return None;
+    }
+
+    let (path, fragment) = match destination.split_once('#') {
+        Some((path, fragment)) => (path, Some(percent_decode(fragment))),
+        None => (destination, None),
+    };
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    Some(Target {
+        path: (!path.is_empty()).then(|| PathBuf::from(percent_decode(path))),
+        fragment,
+    })
+}
+
+/// Anchors defined in a markdown document: heading slugs and HTML ids
+pub fn anchors(content: &str) -> Vec<String> {
+    crate::toc::headings(content)
+        .into_iter()
+        .map(|heading| heading.anchor)
+        .chain(
+            HTML_ANCHOR
+                .captures_iter(content)
+                .map(|cap| cap[1].to_string()),
+        )
+        .collect()
+}
+
+/// Whether `fragment` names one of `anchors`
+pub fn has_anchor(anchors: &[String], fragment: &str) -> bool {
+    fragment.is_empty() || anchors.iter().any(|a| a.eq_ignore_ascii_case(fragment))
+}
+
+/// Whether a path has a markdown extension
+pub fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+}
+
+/// Decode `%20`-style escapes, leaving malformed ones as written
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_inline_links_and_images() {
+        let content = "See [guide](docs/guide.md \"Guide\") and ![logo](<img/a b.png>).\n[![badge](badge.svg)](#setup)\n";
+
+        let links = extract(content).links;
+
+        let found: Vec<_> = links
+            .iter()
+            .map(|l| {
+                (
+                    l.image,
+                    l.destination.as_str(),
+                    &content[l.offset..l.offset + 1],
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (false, "docs/guide.md", "d"),
+                (true, "img/a b.png", "i"),
+                (false, "#setup", "#"),
+                (true, "badge.svg", "b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_skips_code() {
+        let content =
+            "---\nlink: \"[a](x.md)\"\n---\n`[b](y.md)`\n```\n[c](z.md)\n```\n[d](w.md)\n";
+
+        let links = extract(content).links;
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].destination, "w.md");
+    }
+
+    #[test]
+    fn test_extract_references() {
+        let content = "Read [the guide][Guide  Doc] or [setup][].\n\n[guide doc]: guide.md\n";
+
+        let found = extract(content);
+
+        let labels: Vec<_> = found.references.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["Guide  Doc", "setup"]);
+        assert_eq!(found.definitions["guide doc"].destination, "guide.md");
+        assert!(found.links.is_empty());
+    }
+
+    #[test]
+    fn test_target() {
+        assert_eq!(target("https://example.com"), None);
+        assert_eq!(target("mailto:a@b.c"), None);
+        assert_eq!(target("/abs.md"), None);
+        assert_eq!(
+            target("ref%20guide.md#set-up"),
+            Some(Target {
+                path: Some(PathBuf::from("ref guide.md")),
+                fragment: Some("set-up".to_string()),
+            })
+        );
+        assert_eq!(
+            target("#usage"),
+            Some(Target {
+                path: None,
+                fragment: Some("usage".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_anchors() {
+        let anchors = anchors("# Setup\n\n## Setup\n\n<a id=\"custom\"></a>\n");
+        assert_eq!(anchors, vec!["setup", "setup-1", "custom"]);
+        assert!(has_anchor(&anchors, "Setup-1"));
+        assert!(!has_anchor(&anchors, "install"));
+    }
+}
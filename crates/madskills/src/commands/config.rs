@@ -0,0 +1,361 @@
+//! Inspect project configuration command
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use madskills_core::config::{
+    Config, DiscoverySection, FmtSection, LintSection, ResolvedConfig, Sourced, discover_config,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the effective configuration and where each value came from
+    Show(ShowArgs),
+}
+
+#[derive(Args)]
+pub struct ShowArgs {
+    /// Directory to resolve configuration for
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: Format,
+
+    /// Treat warnings as errors
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Disable spec checks
+    #[arg(long)]
+    pub no_spec: bool,
+
+    /// Disable markdown linting and rumdl-based fixes
+    #[arg(long)]
+    pub no_mdlint: bool,
+
+    /// Disable best practices validation
+    #[arg(long)]
+    pub no_best_practices: bool,
+
+    /// Do not rewrite YAML frontmatter
+    #[arg(long)]
+    pub no_frontmatter: bool,
+
+    /// Markdown lint config file
+    #[arg(long)]
+    pub mdlint_config: Option<PathBuf>,
+
+    /// Only run these best practice rules (codes or prefixes, e.g. AS00)
+    #[arg(long, value_delimiter = ',')]
+    pub select: Vec<String>,
+
+    /// Skip these best practice rules (codes or prefixes)
+    #[arg(long, value_delimiter = ',')]
+    pub ignore: Vec<String>,
+
+    /// Additional SKILL.md glob(s) to include (repeatable)
+    #[arg(long)]
+    pub include: Vec<String>,
+
+    /// Path glob(s) to exclude (repeatable)
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
+    /// Discover skills in every detected skills directory, not just the first
+    #[arg(long)]
+    pub all_roots: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl ShowArgs {
+    /// Settings given explicitly on the command line, as `lint` and `fmt`
+    /// would apply them
+    pub fn config_overrides(&self) -> Config {
+        Config {
+            lint: LintSection {
+                strict: self.strict.then_some(true),
+                spec: self.no_spec.then_some(false),
+                mdlint: self.no_mdlint.then_some(false),
+                best_practices: self.no_best_practices.then_some(false),
+                mdlint_config: self.mdlint_config.clone(),
+                select: (!self.select.is_empty()).then(|| self.select.clone()),
+                ignore: (!self.ignore.is_empty()).then(|| self.ignore.clone()),
+            },
+            fmt: FmtSection {
+                mdlint: self.no_mdlint.then_some(false),
+                frontmatter: self.no_frontmatter.then_some(false),
+                mdlint_config: self.mdlint_config.clone(),
+            },
+            discovery: DiscoverySection {
+                include: (!self.include.is_empty()).then(|| self.include.clone()),
+                exclude: (!self.exclude.is_empty()).then(|| self.exclude.clone()),
+                all_roots: self.all_roots.then_some(true),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Load the nearest config file for `path` and merge CLI overrides over it
+pub fn resolve_config(path: &Path, overrides: &Config) -> Result<ResolvedConfig> {
+    let loaded = discover_config(path).context("Failed to load configuration")?;
+    Ok(ResolvedConfig::resolve(loaded.as_ref(), overrides))
+}
+
+pub fn cmd_config(args: ConfigArgs, _quiet: bool) -> Result<()> {
+    match args.command {
+        ConfigCommand::Show(show) => cmd_show(show),
+    }
+}
+
+fn cmd_show(args: ShowArgs) -> Result<()> {
+    let resolved = resolve_config(&args.path, &args.config_overrides())?;
+
+    match args.format {
+        Format::Text => print!("{}", render_text(&resolved)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&render_json(&resolved))?),
+    }
+
+    Ok(())
+}
+
+/// Render the effective config as annotated TOML
+fn render_text(resolved: &ResolvedConfig) -> String {
+    let mut out = String::new();
+
+    match &resolved.file {
+        Some(path) => out.push_str(&format!("# Config file: {}\n", path.display())),
+        None => out.push_str("# No config file found; using defaults\n"),
+    }
+
+    out.push_str("\n[lint]\n");
+    push_entry(&mut out, "strict", &resolved.lint.strict, toml_bool);
+    push_entry(&mut out, "spec", &resolved.lint.spec, toml_bool);
+    push_entry(&mut out, "mdlint", &resolved.lint.mdlint, toml_bool);
+    push_entry(
+        &mut out,
+        "best-practices",
+        &resolved.lint.best_practices,
+        toml_bool,
+    );
+    push_entry(
+        &mut out,
+        "mdlint-config",
+        &resolved.lint.mdlint_config,
+        toml_path,
+    );
+    push_entry(&mut out, "select", &resolved.lint.select, |v| toml_list(v));
+    push_entry(&mut out, "ignore", &resolved.lint.ignore, |v| toml_list(v));
+
+    out.push_str("\n[fmt]\n");
+    push_entry(&mut out, "mdlint", &resolved.fmt.mdlint, toml_bool);
+    push_entry(
+        &mut out,
+        "frontmatter",
+        &resolved.fmt.frontmatter,
+        toml_bool,
+    );
+    push_entry(
+        &mut out,
+        "mdlint-config",
+        &resolved.fmt.mdlint_config,
+        toml_path,
+    );
+
+    out.push_str("\n[discovery]\n");
+    push_entry(&mut out, "include", &resolved.discovery.include, |v| {
+        toml_list(v)
+    });
+    push_entry(&mut out, "exclude", &resolved.discovery.exclude, |v| {
+        toml_list(v)
+    });
+    push_entry(
+        &mut out,
+        "all-roots",
+        &resolved.discovery.all_roots,
+        toml_bool,
+    );
+
+    out.push_str("\n[markdown]\n");
+    push_entry(&mut out, "enable", &resolved.markdown.enable, |v| {
+        toml_list(v)
+    });
+    push_entry(&mut out, "disable", &resolved.markdown.disable, |v| {
+        toml_list(v)
+    });
+    push_entry(&mut out, "flavor", &resolved.markdown.flavor, |v| {
+        v.map(|flavor| toml::Value::String(flavor.to_string()).to_string())
+    });
+
+    out.push_str("\n[rules]\n");
+    for (code, level) in &resolved.rules {
+        push_entry(&mut out, code, level, |v| {
+            Some(toml::Value::String(v.as_str().to_string()).to_string())
+        });
+    }
+
+    if !resolved.custom_rules.is_empty() {
+        out.push_str("\n# [[custom-rules]]\n");
+        for rule in &resolved.custom_rules {
+            out.push_str(&format!(
+                "# {} ({}, {}): {}\n",
+                rule.id,
+                rule.target.as_str(),
+                rule.severity.as_str(),
+                rule.message
+            ));
+        }
+    }
+
+    out
+}
+
+/// Append a `key = value  # source` line; unset values are written as a comment
+fn push_entry<T>(
+    out: &mut String,
+    key: &str,
+    setting: &Sourced<T>,
+    render: impl Fn(&T) -> Option<String>,
+) {
+    match render(&setting.value) {
+        Some(value) => out.push_str(&format!("{} = {}  # {}\n", key, value, setting.source)),
+        None => out.push_str(&format!("# {} is not set  # {}\n", key, setting.source)),
+    }
+}
+
+fn toml_bool(value: &bool) -> Option<String> {
+    Some(value.to_string())
+}
+
+fn toml_path(value: &Option<PathBuf>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|path| toml::Value::String(path.display().to_string()).to_string())
+}
+
+fn toml_list(value: &[String]) -> Option<String> {
+    let items: Vec<String> = value
+        .iter()
+        .map(|s| toml::Value::String(s.clone()).to_string())
+        .collect();
+    Some(format!("[{}]", items.join(", ")))
+}
+
+/// Render the effective config as JSON with a value/source pair per setting
+fn render_json(resolved: &ResolvedConfig) -> serde_json::Value {
+    fn entry<T: serde::Serialize>(setting: &Sourced<T>) -> serde_json::Value {
+        serde_json::json!({
+            "value": setting.value,
+            "source": setting.source.to_string(),
+        })
+    }
+
+    serde_json::json!({
+        "file": resolved.file.as_ref().map(|p| p.display().to_string()),
+        "lint": {
+            "strict": entry(&resolved.lint.strict),
+            "spec": entry(&resolved.lint.spec),
+            "mdlint": entry(&resolved.lint.mdlint),
+            "best-practices": entry(&resolved.lint.best_practices),
+            "mdlint-config": entry(&resolved.lint.mdlint_config),
+            "select": entry(&resolved.lint.select),
+            "ignore": entry(&resolved.lint.ignore),
+        },
+        "fmt": {
+            "mdlint": entry(&resolved.fmt.mdlint),
+            "frontmatter": entry(&resolved.fmt.frontmatter),
+            "mdlint-config": entry(&resolved.fmt.mdlint_config),
+        },
+        "discovery": {
+            "include": entry(&resolved.discovery.include),
+            "exclude": entry(&resolved.discovery.exclude),
+            "all-roots": entry(&resolved.discovery.all_roots),
+        },
+        "markdown": {
+            "enable": entry(&resolved.markdown.enable),
+            "disable": entry(&resolved.markdown.disable),
+            "flavor": entry(&resolved.markdown.flavor),
+        },
+        "rules": resolved
+            .rules
+            .iter()
+            .map(|(code, level)| (code.clone(), entry(level)))
+            .collect::<serde_json::Map<_, _>>(),
+        "custom-rules": resolved
+            .custom_rules
+            .iter()
+            .map(|rule| serde_json::json!({
+                "id": rule.id,
+                "target": rule.target.as_str(),
+                "severity": rule.severity.as_str(),
+                "message": rule.message,
+            }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        show: ShowArgs,
+    }
+
+    #[test]
+    fn test_render_text_annotates_sources() {
+        let cli = Config {
+            lint: LintSection {
+                strict: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let resolved = ResolvedConfig::resolve(None, &cli);
+        let text = render_text(&resolved);
+
+        assert!(text.contains("No config file found"));
+        assert!(text.contains("strict = true  # command line\n"));
+        assert!(text.contains("spec = true  # default\n"));
+        assert!(text.contains("exclude = []  # default\n"));
+        assert!(text.contains("# mdlint-config is not set  # default\n"));
+        assert!(text.contains("# flavor is not set  # default\n"));
+    }
+
+    #[test]
+    fn test_show_applies_command_line_overrides() {
+        let cli = Cli::parse_from([
+            "show",
+            "--no-mdlint",
+            "--select",
+            "AS001,AS002",
+            "--exclude",
+            "drafts/",
+        ]);
+        let resolved = ResolvedConfig::resolve(None, &cli.show.config_overrides());
+        let text = render_text(&resolved);
+
+        assert!(text.contains("mdlint = false  # command line\n"));
+        assert!(text.contains("select = [\"AS001\", \"AS002\"]  # command line\n"));
+        assert!(text.contains("exclude = [\"drafts/\"]  # command line\n"));
+        assert!(text.contains("strict = false  # default\n"));
+        assert!(!resolved.lint.mdlint.value);
+        assert!(!resolved.fmt.mdlint.value);
+    }
+}
@@ -2,7 +2,11 @@
 
 use anyhow::{Context, Result};
 use clap::Args;
-use madskills_core::{DiscoveryConfig, discovery::discover_skills};
+use madskills_core::{
+    config::{Config, DiscoverySection, FmtSection},
+    discovery::discover_skills_in_roots,
+    format::{FormatConfig, Formatter},
+};
 use std::path::PathBuf;
 
 #[derive(Args)]
@@ -15,6 +19,10 @@ pub struct FmtArgs {
     #[arg(long)]
     pub check: bool,
 
+    /// Do not write; print the changes as a unified diff (exit nonzero if any)
+    #[arg(long)]
+    pub diff: bool,
+
     /// Output format
     #[arg(long, value_enum, default_value = "text")]
     pub format: Format,
@@ -27,6 +35,10 @@ pub struct FmtArgs {
     #[arg(long)]
     pub exclude: Vec<String>,
 
+    /// Discover skills in every detected skills directory, not just the first
+    #[arg(long)]
+    pub all_roots: bool,
+
     /// Do not apply rumdl-based fixes
     #[arg(long)]
     pub no_mdlint: bool,
@@ -38,6 +50,10 @@ pub struct FmtArgs {
     /// Path to mdlint config file
     #[arg(long)]
     pub mdlint_config: Option<PathBuf>,
+
+    /// Insert or refresh the table of contents in reference markdown files
+    #[arg(long)]
+    pub toc: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy)]
@@ -46,294 +62,109 @@ pub enum Format {
     Json,
 }
 
+impl FmtArgs {
+    /// Settings given explicitly on the command line
+    pub fn config_overrides(&self) -> Config {
+        Config {
+            fmt: FmtSection {
+                mdlint: self.no_mdlint.then_some(false),
+                frontmatter: self.no_frontmatter.then_some(false),
+                mdlint_config: self.mdlint_config.clone(),
+            },
+            discovery: DiscoverySection {
+                include: (!self.include.is_empty()).then(|| self.include.clone()),
+                exclude: (!self.exclude.is_empty()).then(|| self.exclude.clone()),
+                all_roots: self.all_roots.then_some(true),
+            },
+            ..Default::default()
+        }
+    }
+}
+
 pub fn cmd_fmt(args: FmtArgs, quiet: bool) -> Result<()> {
-    // Detect skills directory
-    let skills_base = madskills_core::discovery::detect_skills_directory(&args.path)?;
+    let settings = super::config::resolve_config(&args.path, &args.config_overrides())?;
 
-    // Discover skills
-    let config = DiscoveryConfig {
-        root_path: args.path,
-        skills_base_path: skills_base,
-        include_patterns: args.include,
-        exclude_patterns: args.exclude,
+    // Detect skills directories
+    let roots = settings.skills_roots(&args.path)?;
+    let skills_base = roots[0].path.clone();
+
+    // Fall back to a markdown lint config file next to the skills
+    let mut format_config = FormatConfig {
+        toc: args.toc,
+        ..settings.format_config(&args.path)?
     };
+    format_config.markdown_config = format_config
+        .markdown_config
+        .with_discovered_file(&skills_base, &args.path);
+    if format_config.markdown {
+        format_config
+            .markdown_config
+            .check()
+            .context("Failed to load markdown lint config")?;
+    }
 
-    let skills = discover_skills(&config).context("Failed to discover skills")?;
+    // Discover skills
+    let config = settings.discovery_config(args.path.clone(), skills_base);
+    let formatter = Formatter::new(format_config);
+
+    let discovered =
+        discover_skills_in_roots(&config, &roots).context("Failed to discover skills")?;
+    super::warn_failures(&discovered.failures);
 
-    if skills.is_empty() {
+    if discovered.skills.is_empty() {
         if !quiet {
             eprintln!("No skills found");
         }
         return Ok(());
     }
 
-    let mut changes_needed = false;
-    let mut formatted_count = 0;
-
-    for skill in &skills {
-        // Read SKILL.md
-        let content = std::fs::read_to_string(&skill.skill_md_path)
-            .with_context(|| format!("Failed to read {}", skill.skill_md_path.display()))?;
-
-        let mut modified = false;
-        let mut current_content = content.clone();
+    // Everything is computed in memory; only a plain run writes
+    let write = !args.check && !args.diff;
+    let mut changed_count = 0;
 
-        // Step 1: Frontmatter normalization (unless --no-frontmatter)
-        if !args.no_frontmatter {
-            let normalized = normalize_frontmatter(&current_content, &skill.skill_md_path)?;
-            if normalized != current_content {
-                current_content = normalized;
-                modified = true;
-            }
-        }
+    for skill in &discovered.skills {
+        let formatted = formatter
+            .format_skill(skill)
+            .with_context(|| format!("Failed to format {}", skill.skill_md_path.display()))?;
 
-        // Step 2: Markdown formatting (unless --no-mdlint)
-        if !args.no_mdlint {
-            // Write current content to temp file for markdown formatting
-            if !args.check {
-                std::fs::write(&skill.skill_md_path, &current_content).with_context(|| {
-                    format!(
-                        "Failed to write temp content to {}",
-                        skill.skill_md_path.display()
-                    )
-                })?;
-            }
+        for file in formatted.changed() {
+            changed_count += 1;
 
-            // Apply markdown fixes
-            let markdown_changed = madskills_core::markdown::format_markdown(
-                &skill.skill_md_path,
-                args.check,
-                args.mdlint_config.as_deref(),
-            )
-            .with_context(|| {
-                format!(
-                    "Failed to format markdown in {}",
-                    skill.skill_md_path.display()
-                )
-            })?;
-
-            if markdown_changed {
-                modified = true;
-                if !args.check {
-                    // Re-read the file after markdown formatting
-                    current_content =
-                        std::fs::read_to_string(&skill.skill_md_path).with_context(|| {
-                            format!("Failed to read formatted {}", skill.skill_md_path.display())
-                        })?;
-                }
-            }
-        }
-
-        // Handle check mode and output
-        if modified {
-            changes_needed = true;
-            formatted_count += 1;
-
-            if args.check {
+            if args.diff {
+                print!("{}", file.diff());
+            } else if args.check {
                 if !quiet {
-                    println!("Would format: {}", skill.skill_md_path.display());
+                    println!("Would format: {}", file.path.display());
                 }
-                // Restore original content in check mode
-                std::fs::write(&skill.skill_md_path, &content).ok();
             } else {
-                // Make sure final content is written
-                std::fs::write(&skill.skill_md_path, &current_content).with_context(|| {
-                    format!(
-                        "Failed to write final content to {}",
-                        skill.skill_md_path.display()
-                    )
-                })?;
-
+                file.write()
+                    .with_context(|| format!("Failed to write {}", file.path.display()))?;
                 if !quiet {
-                    println!("Formatted: {}", skill.skill_md_path.display());
+                    println!("Formatted: {}", file.path.display());
                 }
             }
         }
     }
 
-    if args.check && changes_needed {
+    if write {
         if !quiet {
-            eprintln!("{} file(s) would be formatted", formatted_count);
+            println!("Formatted {} file(s)", changed_count);
+        }
+    } else if changed_count > 0 {
+        if !quiet {
+            eprintln!("{} file(s) would be formatted", changed_count);
         }
         std::process::exit(2);
-    } else if !quiet && !args.check {
-        println!("Formatted {} file(s)", formatted_count);
     }
 
     Ok(())
 }
 
-/// Normalize frontmatter formatting
-fn normalize_frontmatter(content: &str, path: &std::path::Path) -> Result<String> {
-    // Simple normalization: ensure consistent YAML formatting
-    // Parse frontmatter, re-serialize it in canonical form
-
-    use madskills_core::parser::parse_frontmatter;
-
-    let metadata = parse_frontmatter(content, path)?;
-
-    // Rebuild frontmatter
-    let mut frontmatter = String::from("---\n");
-    frontmatter.push_str(&format!("name: {}\n", metadata.name));
-    frontmatter.push_str(&format!("description: {}\n", metadata.description));
-
-    if let Some(ref license) = metadata.license {
-        frontmatter.push_str(&format!("license: {}\n", license));
-    }
-
-    if let Some(ref compat) = metadata.compatibility {
-        frontmatter.push_str(&format!("compatibility: {}\n", compat));
-    }
-
-    if let Some(ref tools) = metadata.allowed_tools {
-        frontmatter.push_str(&format!("allowed-tools: {}\n", tools));
-    }
-
-    if !metadata.metadata.is_empty() {
-        frontmatter.push_str("metadata:\n");
-        let mut keys: Vec<_> = metadata.metadata.keys().collect();
-        keys.sort();
-        for key in keys {
-            if let Some(value) = metadata.metadata.get(key) {
-                frontmatter.push_str(&format!("  {}: {}\n", key, value));
-            }
-        }
-    }
-
-    frontmatter.push_str("---\n");
-
-    // Extract markdown content (everything after closing ---)
-    let markdown_start = content
-        .find("\n---\n")
-        .or_else(|| content.find("\n---\r\n"))
-        .map(|idx| {
-            if content[idx..].starts_with("\n---\r\n") {
-                idx + 6
-            } else {
-                idx + 5
-            }
-        })
-        .unwrap_or(content.len());
-
-    let markdown = &content[markdown_start..];
-
-    Ok(frontmatter + markdown)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
-    #[test]
-    fn test_normalize_frontmatter() {
-        let content = r#"---
-description: Test skill
-name: test-skill
----
-# Content
-"#;
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        // Should reorder fields: name first, then description
-        assert!(normalized.contains("name: test-skill\ndescription: Test skill\n"));
-    }
-
-    #[test]
-    fn test_normalize_frontmatter_with_all_fields() {
-        let content = r#"---
-name: full-skill
-description: A complete skill
-license: MIT
-compatibility: claude-code
-allowed-tools: Read, Write
----
-# Full Content
-"#;
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        assert!(normalized.contains("name: full-skill\n"));
-        assert!(normalized.contains("description: A complete skill\n"));
-        assert!(normalized.contains("license: MIT\n"));
-        assert!(normalized.contains("compatibility: claude-code\n"));
-        assert!(normalized.contains("allowed-tools: Read, Write\n"));
-        assert!(normalized.contains("# Full Content"));
-    }
-
-    #[test]
-    fn test_normalize_frontmatter_with_metadata() {
-        let content = r#"---
-name: metadata-skill
-description: Skill with metadata
-metadata:
-  author: Test Author
-  version: 1.0.0
----
-# Content with metadata
-"#;
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        assert!(normalized.contains("name: metadata-skill\n"));
-        assert!(normalized.contains("description: Skill with metadata\n"));
-        assert!(normalized.contains("metadata:\n"));
-        assert!(normalized.contains("  author: Test Author\n"));
-        assert!(normalized.contains("  version: 1.0.0\n"));
-    }
-
-    #[test]
-    fn test_normalize_frontmatter_preserves_markdown() {
-        let content = r#"---
-name: preserve-test
-description: Test preservation
----
-# Header
-
-This is some **markdown** content with:
-- Lists
-- And other formatting
-
-```rust
-fn example() {}
-```
-"#;
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        assert!(normalized.contains("# Header"));
-        assert!(normalized.contains("This is some **markdown** content"));
-        assert!(normalized.contains("- Lists"));
-        assert!(normalized.contains("```rust"));
-        assert!(normalized.contains("fn example() {}"));
-    }
-
-    #[test]
-    fn test_normalize_frontmatter_empty_markdown() {
-        let content = r#"---
-name: empty-markdown
-description: No markdown content
----
-"#;
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        assert!(normalized.contains("name: empty-markdown\n"));
-        assert!(normalized.contains("description: No markdown content\n"));
-        assert!(normalized.ends_with("---\n"));
-    }
-
-    #[test]
-    fn test_normalize_frontmatter_windows_line_endings() {
-        let content = "---\r\nname: windows-skill\r\ndescription: Windows line endings\r\n---\r\n# Content\r\n";
-        let path = PathBuf::from("test.md");
-        let normalized = normalize_frontmatter(content, &path).unwrap();
-
-        assert!(normalized.contains("name: windows-skill\n"));
-        assert!(normalized.contains("description: Windows line endings\n"));
-    }
-
     #[test]
     fn test_format_enum_variants() {
         let text = Format::Text;
@@ -349,12 +180,15 @@ description: No markdown content
         let args = FmtArgs {
             path: PathBuf::from("."),
             check: false,
+            diff: false,
             format: Format::Text,
             include: vec![],
             exclude: vec![],
+            all_roots: false,
             no_mdlint: false,
             no_frontmatter: false,
             mdlint_config: None,
+            toc: false,
         };
 
         assert_eq!(args.path, PathBuf::from("."));
@@ -372,12 +206,15 @@ description: No markdown content
         let args = FmtArgs {
             path: PathBuf::from("/custom/path"),
             check: true,
+            diff: false,
             format: Format::Json,
             include: vec!["**/*.md".to_string()],
             exclude: vec!["**/node_modules/**".to_string()],
+            all_roots: false,
             no_mdlint: true,
             no_frontmatter: true,
             mdlint_config: Some(config_path.clone()),
+            toc: true,
         };
 
         assert_eq!(args.path, PathBuf::from("/custom/path"));
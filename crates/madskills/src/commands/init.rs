@@ -2,6 +2,7 @@
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
+use madskills_core::frontmatter::yaml_value;
 use std::fs;
 use std::path::PathBuf;
 
@@ -67,8 +68,8 @@ description: {}
 
 TODO: Add skill content here
 "#,
-        args.name,
-        description,
+        yaml_value(&args.name, 0),
+        yaml_value(&description, 0),
         capitalize_skill_name(&args.name)
     );
 
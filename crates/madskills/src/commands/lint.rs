@@ -3,13 +3,21 @@
 use anyhow::{Context, Result};
 use clap::Args;
 use madskills_core::{
-    DiscoveryConfig,
-    discovery::discover_skills,
+    Skill, ValidationResult,
+    baseline::Baseline,
+    config::{Config, DiscoverySection, LintSection},
+    discovery::{Discovered, discover_skills_in_roots},
+    fix,
     output::{OutputFormat, OutputFormatter},
-    validator::{ValidationConfig, Validator, validate_uniqueness},
+    parser::parse_frontmatter,
+    validator::{Validator, validate_uniqueness},
 };
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+/// Upper bound on fix passes, in case fixes keep producing new findings
+const MAX_FIX_PASSES: usize = 10;
+
 #[derive(Args)]
 pub struct LintArgs {
     /// Root to scan
@@ -20,9 +28,13 @@ pub struct LintArgs {
     #[arg(long)]
     pub strict: bool,
 
-    /// Output format
-    #[arg(long, value_enum, default_value = "text")]
-    pub format: Format,
+    /// Output format (defaults to github when GITHUB_ACTIONS=true, else text)
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// Write the report to FILE and print text output to stdout
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
 
     /// Disable markdown linting (spec checks only)
     #[arg(long)]
@@ -40,6 +52,14 @@ pub struct LintArgs {
     #[arg(long)]
     pub no_best_practices: bool,
 
+    /// Only run these best practice rules (codes or prefixes, e.g. AS00)
+    #[arg(long, value_delimiter = ',')]
+    pub select: Vec<String>,
+
+    /// Skip these best practice rules (codes or prefixes)
+    #[arg(long, value_delimiter = ',')]
+    pub ignore: Vec<String>,
+
     /// Additional SKILL.md glob(s) to include (repeatable)
     #[arg(long)]
     pub include: Vec<String>,
@@ -47,29 +67,96 @@ pub struct LintArgs {
     /// Path glob(s) to exclude (repeatable)
     #[arg(long)]
     pub exclude: Vec<String>,
+
+    /// Discover skills in every detected skills directory, not just the first
+    #[arg(long)]
+    pub all_roots: bool,
+
+    /// Only report findings not recorded in this baseline file
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record all current findings in a baseline file and exit
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Apply automatic fixes, then report what remains
+    #[arg(long)]
+    pub fix: bool,
+
+    /// With --fix or --diff, also apply fixes that may change behavior
+    #[arg(long)]
+    pub unsafe_fixes: bool,
+
+    /// Print the fixes as a unified diff instead of applying them (no report
+    /// is printed)
+    #[arg(long, conflicts_with_all = ["fix", "format", "output"])]
+    pub diff: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy)]
 pub enum Format {
     Text,
     Json,
+    Sarif,
+    Github,
+    Junit,
+    Checkstyle,
 }
 
-pub fn cmd_lint(args: LintArgs, quiet: bool) -> Result<()> {
-    // Detect skills directory
-    let skills_base = madskills_core::discovery::detect_skills_directory(&args.path)?;
+impl LintArgs {
+    /// Settings given explicitly on the command line
+    pub fn config_overrides(&self) -> Config {
+        Config {
+            lint: LintSection {
+                strict: self.strict.then_some(true),
+                spec: self.no_spec.then_some(false),
+                mdlint: self.no_mdlint.then_some(false),
+                best_practices: self.no_best_practices.then_some(false),
+                mdlint_config: self.mdlint_config.clone(),
+                select: (!self.select.is_empty()).then(|| self.select.clone()),
+                ignore: (!self.ignore.is_empty()).then(|| self.ignore.clone()),
+            },
+            discovery: DiscoverySection {
+                include: (!self.include.is_empty()).then(|| self.include.clone()),
+                exclude: (!self.exclude.is_empty()).then(|| self.exclude.clone()),
+                all_roots: self.all_roots.then_some(true),
+            },
+            ..Default::default()
+        }
+    }
+}
 
-    // Discover skills
-    let config = DiscoveryConfig {
-        root_path: args.path,
-        skills_base_path: skills_base,
-        include_patterns: args.include,
-        exclude_patterns: args.exclude,
-    };
+pub fn cmd_lint(args: LintArgs, quiet: bool, use_color: bool, verbose: u8) -> Result<()> {
+    let settings = super::config::resolve_config(&args.path, &args.config_overrides())?;
+
+    let registry = settings
+        .rule_registry()
+        .context("Failed to load custom rules")?;
+    // Rule codes and selectors are checked against the rules in `registry`
+    let mut validation = settings.validation_config(&args.path, &registry)?;
+
+    // Detect skills directories
+    let roots = settings.skills_roots(&args.path)?;
+    let skills_base = roots[0].path.clone();
 
-    let skills = discover_skills(&config).context("Failed to discover skills")?;
+    // Fall back to a markdown lint config file next to the skills
+    validation.markdown_config = validation
+        .markdown_config
+        .with_discovered_file(&skills_base, &args.path);
+    if validation.check_markdown {
+        validation
+            .markdown_config
+            .check()
+            .context("Failed to load markdown lint config")?;
+    }
+
+    // Discover skills
+    let config = settings.discovery_config(args.path.clone(), skills_base);
+    let discovered =
+        discover_skills_in_roots(&config, &roots).context("Failed to discover skills")?;
 
-    if skills.is_empty() {
+    if discovered.is_empty() {
         if !quiet {
             eprintln!("No skills found");
         }
@@ -77,46 +164,148 @@ pub fn cmd_lint(args: LintArgs, quiet: bool) -> Result<()> {
     }
 
     if !quiet {
-        eprintln!("Found {} skill(s)", skills.len());
+        eprintln!(
+            "Found {} skill(s)",
+            discovered.skills.len() + discovered.failures.len()
+        );
     }
 
     // Validate
-    let validator = Validator::new(ValidationConfig {
-        strict: args.strict,
-        check_spec: !args.no_spec,
-        check_markdown: !args.no_mdlint,
-        check_best_practices: !args.no_best_practices,
-        mdlint_config: args.mdlint_config,
-    });
+    let validator = Validator::new(validation).with_registry(registry);
+    let strict = validator.config.strict;
+
+    let mut results = validate_all(&validator, &discovered);
+
+    if args.fix || args.diff {
+        let plans = fix::fix_until_stable(results, args.unsafe_fixes, MAX_FIX_PASSES, |contents| {
+            let skills = Discovered {
+                skills: with_contents(&discovered.skills, contents),
+                failures: discovered.failures.clone(),
+            };
+            validate_all_with(&validator, &skills, contents)
+        })
+        .context("Failed to plan fixes")?;
+        let count: usize = plans.iter().map(|p| p.applied).sum();
+
+        if args.diff {
+            for plan in &plans {
+                print!("{}", plan.diff());
+            }
+            if !quiet {
+                eprintln!("{} fix(es) available in {} file(s)", count, plans.len());
+            }
+            if !plans.is_empty() {
+                std::process::exit(2);
+            }
+            return Ok(());
+        }
+
+        for plan in &plans {
+            fix::write_atomic(&plan.path, &plan.fixed)
+                .with_context(|| format!("Failed to write {}", plan.path.display()))?;
+        }
+
+        // Report what remains in the files as written
+        let skills =
+            discover_skills_in_roots(&config, &roots).context("Failed to discover skills")?;
+        results = validate_all(&validator, &skills);
+        if !quiet {
+            eprintln!("Fixed {} finding(s)", count);
+            let remaining = fix::collect_fixes(&results, true).len();
+            let unsafe_only = remaining - fix::collect_fixes(&results, false).len();
+            if unsafe_only > 0 && !args.unsafe_fixes {
+                eprintln!("{} more fix(es) available with --unsafe-fixes", unsafe_only);
+            }
+        }
+    }
 
-    let mut results = Vec::new();
-    for skill in &skills {
-        let result = validator.validate_skill(skill);
-        results.push(result);
+    if let Some(path) = &args.write_baseline {
+        let baseline = Baseline::from_results(&results, &args.path);
+        baseline.save(path).context("Failed to write baseline")?;
+        if !quiet {
+            eprintln!(
+                "Wrote {} finding(s) to baseline {}",
+                baseline.len(),
+                path.display()
+            );
+        }
+        return Ok(());
     }
 
-    // Check uniqueness across all skills
-    if !args.no_spec {
-        let uniqueness_errors = validate_uniqueness(&skills);
-        if !uniqueness_errors.is_empty() {
-            let mut global_result =
-                madskills_core::ValidationResult::new(PathBuf::from("<workspace>"));
-            global_result.errors = uniqueness_errors;
-            results.push(global_result);
+    if let Some(path) = &args.baseline {
+        let baseline = Baseline::load(path).context("Failed to load baseline")?;
+        baseline
+            .check_codes(path, validator.registry())
+            .context("Failed to load baseline")?;
+        let report = baseline.apply(&mut results, &args.path);
+
+        if !quiet {
+            if report.suppressed > 0 {
+                eprintln!("{} finding(s) hidden by baseline", report.suppressed);
+            }
+            if !report.fixed.is_empty() {
+                let fixed: usize = report.fixed.iter().map(|e| e.count).sum();
+                eprintln!(
+                    "{} baseline finding(s) no longer occur; run --write-baseline {} to shrink it:",
+                    fixed,
+                    path.display()
+                );
+                for entry in &report.fixed {
+                    let fp = &entry.fingerprint;
+                    let file = if fp.file.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [{}]", fp.file)
+                    };
+                    let count = if entry.count > 1 {
+                        format!(" (x{})", entry.count)
+                    } else {
+                        String::new()
+                    };
+                    eprintln!(
+                        "  {}: [{}]{} {}{}",
+                        fp.skill, fp.code, file, fp.message, count
+                    );
+                }
+            }
         }
     }
 
     // Format output
-    let output_format = match args.format {
+    let format = args.format.unwrap_or_else(|| {
+        if std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true") {
+            Format::Github
+        } else {
+            Format::Text
+        }
+    });
+    let output_format = match format {
         Format::Text => OutputFormat::Text,
         Format::Json => OutputFormat::Json,
+        Format::Sarif => OutputFormat::Sarif,
+        Format::Github => OutputFormat::Github,
+        Format::Junit => OutputFormat::Junit,
+        Format::Checkstyle => OutputFormat::Checkstyle,
     };
 
-    let use_color = atty::is(atty::Stream::Stdout);
-    let formatter = OutputFormatter::new(output_format, use_color);
+    let formatter = |format| {
+        OutputFormatter::new(format, use_color)
+            .with_root(&args.path)
+            .with_registry(validator.registry())
+            .with_verbose(verbose > 0)
+    };
 
-    let output = formatter.format_validation_results(&results);
-    print!("{}", output);
+    let output = formatter(output_format).format_validation_results(&results);
+    match &args.output {
+        Some(path) => {
+            // Report goes to the file; humans still get text on stdout
+            std::fs::write(path, output)
+                .with_context(|| format!("Failed to write report to {}", path.display()))?;
+            let text = formatter(OutputFormat::Text);
+            print!("{}", text.format_validation_results(&results));
+        }
+        None => print!("{}", output),
+    }
 
     // Determine exit code
     let has_errors = results.iter().any(|r| !r.errors.is_empty());
@@ -124,9 +313,58 @@ pub fn cmd_lint(args: LintArgs, quiet: bool) -> Result<()> {
     let has_bp_errors = results.iter().any(|r| r.has_bp_errors());
     let has_bp_warnings = results.iter().any(|r| r.has_bp_warnings());
 
-    if has_errors || has_bp_errors || (args.strict && (has_warnings || has_bp_warnings)) {
+    if has_errors || has_bp_errors || (strict && (has_warnings || has_bp_warnings)) {
         std::process::exit(2);
     }
 
     Ok(())
 }
+
+/// Validate every skill, plus cross-skill uniqueness; skills that failed to
+/// load are reported as they are
+fn validate_all(validator: &Validator, discovered: &Discovered) -> Vec<ValidationResult> {
+    validate_all_with(validator, discovered, &HashMap::new())
+}
+
+/// [`validate_all`], reading `contents` in place of the files on disk
+fn validate_all_with(
+    validator: &Validator,
+    discovered: &Discovered,
+    contents: &HashMap<PathBuf, String>,
+) -> Vec<ValidationResult> {
+    let skills = &discovered.skills;
+    let mut results = validator.validate_all_with(skills, contents);
+    results.extend(discovered.failures.iter().cloned());
+
+    // Check uniqueness across all skills
+    if validator.config.check_spec {
+        let uniqueness_errors = validate_uniqueness(skills);
+        if !uniqueness_errors.is_empty() {
+            let mut global_result = ValidationResult::new(PathBuf::from("<workspace>"));
+            global_result.errors = uniqueness_errors;
+            results.push(global_result);
+        }
+    }
+
+    results
+}
+
+/// Skills with their frontmatter read from `contents` where it has their
+/// SKILL.md; a SKILL.md that no longer parses keeps its old frontmatter
+fn with_contents(skills: &[Skill], contents: &HashMap<PathBuf, String>) -> Vec<Skill> {
+    skills
+        .iter()
+        .map(|skill| {
+            let metadata = contents
+                .get(&skill.skill_md_path)
+                .and_then(|content| parse_frontmatter(content, &skill.skill_md_path).ok());
+            match metadata {
+                Some(metadata) => Skill {
+                    metadata,
+                    ..skill.clone()
+                },
+                None => skill.clone(),
+            }
+        })
+        .collect()
+}
@@ -2,7 +2,11 @@
 
 use anyhow::{Context, Result};
 use clap::Args;
-use madskills_core::{DiscoveryConfig, discovery::discover_skills};
+use madskills_core::{
+    config::{Config, DiscoverySection},
+    discovery::discover_skills_in_roots,
+    validator::validate_uniqueness,
+};
 use std::path::PathBuf;
 
 #[derive(Args)]
@@ -26,6 +30,10 @@ pub struct ListArgs {
     /// Path glob(s) to exclude (repeatable)
     #[arg(long)]
     pub exclude: Vec<String>,
+
+    /// Discover skills in every detected skills directory, not just the first
+    #[arg(long)]
+    pub all_roots: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy)]
@@ -34,19 +42,39 @@ pub enum Format {
     Json,
 }
 
+impl ListArgs {
+    /// Settings given explicitly on the command line
+    pub fn config_overrides(&self) -> Config {
+        Config {
+            discovery: DiscoverySection {
+                include: (!self.include.is_empty()).then(|| self.include.clone()),
+                exclude: (!self.exclude.is_empty()).then(|| self.exclude.clone()),
+                all_roots: self.all_roots.then_some(true),
+            },
+            ..Default::default()
+        }
+    }
+}
+
 pub fn cmd_list(args: ListArgs, _quiet: bool) -> Result<()> {
-    // Detect skills directory
-    let skills_base = madskills_core::discovery::detect_skills_directory(&args.path)?;
+    let settings = super::config::resolve_config(&args.path, &args.config_overrides())?;
+
+    // Detect skills directories
+    let roots = settings.skills_roots(&args.path)?;
+    let skills_base = roots[0].path.clone();
 
     // Discover skills
-    let config = DiscoveryConfig {
-        root_path: args.path,
-        skills_base_path: skills_base,
-        include_patterns: args.include,
-        exclude_patterns: args.exclude,
-    };
+    let config = settings.discovery_config(args.path.clone(), skills_base);
 
-    let skills = discover_skills(&config).context("Failed to discover skills")?;
+    let discovered =
+        discover_skills_in_roots(&config, &roots).context("Failed to discover skills")?;
+    super::warn_failures(&discovered.failures);
+    let skills = discovered.skills;
+
+    // The same name in two roots (or twice in one) shadows a skill
+    for error in validate_uniqueness(&skills) {
+        eprintln!("Warning: {}", error.message);
+    }
 
     match args.format {
         Format::Text => {
@@ -64,6 +92,9 @@ pub fn cmd_list(args: ListArgs, _quiet: bool) -> Result<()> {
                     if let Some(ref compat) = skill.metadata.compatibility {
                         println!("  compatibility: {}", compat);
                     }
+                    if let Some(agent) = skill.source.as_ref().and_then(|s| s.agent) {
+                        println!("  agent: {}", agent);
+                    }
                     println!();
                 } else {
                     println!("{}  {}", skill.metadata.name, skill.root.display());
@@ -80,6 +111,8 @@ pub fn cmd_list(args: ListArgs, _quiet: bool) -> Result<()> {
                         "description": s.metadata.description,
                         "license": s.metadata.license,
                         "compatibility": s.metadata.compatibility,
+                        "skills_root": s.source.as_ref().map(|r| r.path.display().to_string()),
+                        "agent": s.source.as_ref().and_then(|r| r.agent),
                     })
                 })
                 .collect();
@@ -115,6 +148,7 @@ mod tests {
             long: false,
             include: vec![],
             exclude: vec![],
+            all_roots: false,
         };
 
         assert_eq!(args.path, PathBuf::from("."));
@@ -131,6 +165,7 @@ mod tests {
             long: true,
             include: vec!["**/*.md".to_string()],
             exclude: vec!["**/node_modules/**".to_string()],
+            all_roots: false,
         };
 
         assert_eq!(args.path, PathBuf::from("/custom/path"));
@@ -1,6 +1,23 @@
 //! Command implementations
 
+pub mod config;
 pub mod fmt;
 pub mod init;
 pub mod lint;
 pub mod list;
+pub mod rules;
+
+use madskills_core::ValidationResult;
+
+/// Warn about skills that failed to load (only `lint` reports them as errors)
+fn warn_failures(failures: &[ValidationResult]) {
+    for failure in failures {
+        for error in &failure.errors {
+            let path = error
+                .location
+                .as_ref()
+                .map_or(&failure.skill_path, |location| &location.file);
+            eprintln!("Warning: Skipping {}: {}", path.display(), error.message);
+        }
+    }
+}
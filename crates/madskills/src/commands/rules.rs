@@ -0,0 +1,299 @@
+//! List and explain best practice rules
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use madskills_core::{
+    config::Config,
+    models::{BestPracticeCode, RuleLevel},
+    rules::{Rule, RuleRegistry},
+    validator::RuleSettings,
+};
+use std::path::PathBuf;
+
+const BOLD: &str = "1";
+const UNDERLINE: &str = "1;4";
+const CYAN: &str = "36";
+const DIM: &str = "2";
+
+#[derive(Args)]
+pub struct RulesArgs {
+    /// Directory whose configuration decides which rules are enabled
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: Format,
+}
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Rule code, e.g. AS006
+    pub code: String,
+
+    /// Directory whose configuration defines custom rules
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// A rule as configured for the scanned directory
+struct RuleInfo<'a> {
+    code: &'a BestPracticeCode,
+    rule: &'a dyn Rule,
+    /// Level from `[rules]`, else the rule's default
+    level: RuleLevel,
+    /// Selected and not turned off
+    enabled: bool,
+}
+
+/// Load the config for `path`, the rules it defines and their settings
+fn load(path: &std::path::Path) -> Result<(RuleSettings, RuleRegistry)> {
+    let settings = super::config::resolve_config(path, &Config::default())?;
+    let registry = settings
+        .rule_registry()
+        .context("Failed to load custom rules")?;
+    let rules = settings.rule_settings(&registry)?;
+    Ok((rules, registry))
+}
+
+fn rule_infos<'a>(rules: &RuleSettings, registry: &'a RuleRegistry) -> Vec<RuleInfo<'a>> {
+    registry
+        .iter()
+        .map(|(code, rule)| {
+            let level = rules
+                .levels
+                .get(code)
+                .copied()
+                .unwrap_or_else(|| rule.default_level());
+            RuleInfo {
+                code,
+                rule,
+                level,
+                enabled: rules.is_selected(code) && level != RuleLevel::Off,
+            }
+        })
+        .collect()
+}
+
+pub fn cmd_rules(args: RulesArgs, _quiet: bool) -> Result<()> {
+    let (rules, registry) = load(&args.path)?;
+    let infos = rule_infos(&rules, &registry);
+
+    match args.format {
+        Format::Text => print!("{}", render_table(&infos)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&render_json(&infos))?),
+    }
+
+    Ok(())
+}
+
+pub fn cmd_explain(args: ExplainArgs, _quiet: bool, use_color: bool) -> Result<()> {
+    let (rules, registry) = load(&args.path)?;
+    let Some(info) = rule_infos(&rules, &registry)
+        .into_iter()
+        .find(|info| info.code.as_str().eq_ignore_ascii_case(&args.code))
+    else {
+        bail!(
+            "unknown rule code '{}' (run `madskills rules` to list them)",
+            args.code
+        );
+    };
+
+    print!("{}", render_markdown(&explanation(&info), use_color));
+    Ok(())
+}
+
+/// Rule table: code, default level, configured level, fixable, description
+fn render_table(infos: &[RuleInfo]) -> String {
+    let width = infos
+        .iter()
+        .map(|info| info.code.as_str().len())
+        .max()
+        .unwrap_or(0)
+        .max("CODE".len());
+
+    let mut out = format!(
+        "{:<width$}  {:<7}  {:<7}  {:<3}  DESCRIPTION\n",
+        "CODE", "DEFAULT", "LEVEL", "FIX"
+    );
+    for info in infos {
+        let level = if info.enabled {
+            info.level.as_str()
+        } else {
+            RuleLevel::Off.as_str()
+        };
+        out.push_str(&format!(
+            "{:<width$}  {:<7}  {:<7}  {:<3}  {}\n",
+            info.code.as_str(),
+            info.rule.default_level().as_str(),
+            level,
+            if info.rule.fixable() { "yes" } else { "" },
+            info.rule.description()
+        ));
+    }
+    out
+}
+
+fn render_json(infos: &[RuleInfo]) -> serde_json::Value {
+    infos
+        .iter()
+        .map(|info| {
+            serde_json::json!({
+                "code": info.code.as_str(),
+                "description": info.rule.description(),
+                "default-level": info.rule.default_level().as_str(),
+                "level": info.level.as_str(),
+                "enabled": info.enabled,
+                "fixable": info.rule.fixable(),
+                "builtin": info.code.is_builtin(),
+                "docs-url": info.rule.docs_url(),
+            })
+        })
+        .collect()
+}
+
+/// Markdown explaining a rule: the built-in docs, or what is known about a
+/// custom rule
+fn explanation(info: &RuleInfo) -> String {
+    let mut markdown = match info.code.docs() {
+        Some(docs) => docs.to_string(),
+        None => {
+            let mut docs = format!("# {}: {}\n", info.code.as_str(), info.rule.description());
+            if !info.rule.help().is_empty() {
+                docs.push_str(&format!("\n{}\n", info.rule.help()));
+            }
+            docs
+        }
+    };
+
+    let state = if info.enabled {
+        format!("enabled at level **{}**", info.level.as_str())
+    } else {
+        "**disabled**".to_string()
+    };
+    markdown.push_str(&format!("\n## Configuration\n\nThis rule is {}", state));
+    match info.rule.docs_url() {
+        Some(url) => markdown.push_str(&format!(". Online docs: {}\n", url)),
+        None => markdown.push_str(".\n"),
+    }
+    markdown
+}
+
+/// Render markdown for the terminal
+///
+/// Headings are underlined, code blocks indented and inline markup reduced
+/// to ANSI styles (or plain text without color).
+fn render_markdown(markdown: &str, color: bool) -> String {
+    let mut out = String::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            out.push_str(&format!("    {}\n", paint(color, DIM, line)));
+            continue;
+        }
+
+        let hashes = line.len() - line.trim_start_matches('#').len();
+        if hashes > 0 && line[hashes..].starts_with(' ') {
+            let text = render_inline(line[hashes..].trim(), false);
+            if color {
+                let style = if hashes == 1 { UNDERLINE } else { BOLD };
+                out.push_str(&format!("{}\n", paint(true, style, &text)));
+            } else {
+                let rule = if hashes == 1 { '=' } else { '-' };
+                let underline: String = std::iter::repeat_n(rule, text.chars().count()).collect();
+                out.push_str(&format!("{}\n{}\n", text, underline));
+            }
+            continue;
+        }
+
+        out.push_str(&render_inline(line, color));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Render `**bold**`, `` `code` `` and `[text](url)` spans
+fn render_inline(line: &str, color: bool) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['*', '`', '[']) {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if let Some((bold, after)) = tail.strip_prefix("**").and_then(|t| t.split_once("**")) {
+            out.push_str(&paint(color, BOLD, bold));
+            rest = after;
+        } else if let Some((code, after)) = tail.strip_prefix('`').and_then(|t| t.split_once('`')) {
+            if color {
+                out.push_str(&paint(true, CYAN, code));
+            } else {
+                out.push_str(&format!("`{}`", code));
+            }
+            rest = after;
+        } else if let Some((text, after)) = tail.strip_prefix('[').and_then(|t| t.split_once("]("))
+            && let Some((url, after)) = after.split_once(')')
+            && !text.contains('[')
+        {
+            out.push_str(&format!("{} <{}>", render_inline(text, color), url));
+            rest = after;
+        } else {
+            let len = tail.chars().next().map_or(1, char::len_utf8);
+            out.push_str(&tail[..len]);
+            rest = &tail[len..];
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn paint(color: bool, style: &str, text: &str) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", style, text)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_plain() {
+        let markdown = "# AS006: Title\n\n## Rationale\n\nSee **this** and [FORMS.md](FORMS.md) with `head`.\n\n```markdown\n# SKILL.md\n```\n";
+
+        let out = render_markdown(markdown, false);
+
+        assert_eq!(
+            out,
+            "AS006: Title\n============\n\nRationale\n---------\n\nSee this and FORMS.md <FORMS.md> with `head`.\n\n    # SKILL.md\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_color() {
+        let out = render_markdown("## Severity\n\n**Default**: Warning\n", true);
+
+        assert!(out.starts_with("\x1b[1mSeverity\x1b[0m\n"));
+        assert!(out.contains("\x1b[1mDefault\x1b[0m: Warning"));
+    }
+
+    #[test]
+    fn test_render_inline_leaves_stray_markers() {
+        assert_eq!(render_inline("a * b [c] d`", false), "a * b [c] d`");
+    }
+}
@@ -25,9 +25,31 @@ pub struct Cli {
     #[arg(short, long, global = true, action = clap::ArgAction::Count)]
     pub verbose: u8,
 
-    /// Colorize output: auto|always|never
-    #[arg(long, global = true, default_value = "auto")]
-    pub color: String,
+    /// Colorize output (auto honors NO_COLOR and only colors terminals)
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+}
+
+/// When to use colors and hyperlinks in terminal output
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output written to stdout
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && atty::is(atty::Stream::Stdout)
+            }
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -43,6 +65,15 @@ pub enum Commands {
 
     /// Scaffold a new skill directory with SKILL.md
     Init(commands::init::InitArgs),
+
+    /// Inspect project configuration (madskills.toml)
+    Config(commands::config::ConfigArgs),
+
+    /// List best practice rules and whether they are enabled
+    Rules(commands::rules::RulesArgs),
+
+    /// Show the documentation of a best practice rule
+    Explain(commands::rules::ExplainArgs),
 }
 
 /// Returns the clap command for documentation generation
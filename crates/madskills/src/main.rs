@@ -14,9 +14,16 @@ fn main() -> anyhow::Result<()> {
 
     // Execute command
     match cli.command {
-        Commands::Lint(args) => commands::lint::cmd_lint(args, cli.quiet),
+        Commands::Lint(args) => {
+            commands::lint::cmd_lint(args, cli.quiet, cli.color.enabled(), cli.verbose)
+        }
         Commands::Fmt(args) => commands::fmt::cmd_fmt(args, cli.quiet),
         Commands::List(args) => commands::list::cmd_list(args, cli.quiet),
         Commands::Init(args) => commands::init::cmd_init(args, cli.quiet),
+        Commands::Config(args) => commands::config::cmd_config(args, cli.quiet),
+        Commands::Rules(args) => commands::rules::cmd_rules(args, cli.quiet),
+        Commands::Explain(args) => {
+            commands::rules::cmd_explain(args, cli.quiet, cli.color.enabled())
+        }
     }
 }
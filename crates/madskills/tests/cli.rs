@@ -6,9 +6,16 @@ use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
 
+/// The CLI binary, isolated from CI environment detection
+fn madskills() -> Command {
+    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    cmd.env_remove("GITHUB_ACTIONS");
+    cmd
+}
+
 #[test]
 fn test_help() {
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("--help")
         .assert()
         .success()
@@ -17,7 +24,7 @@ fn test_help() {
 
 #[test]
 fn test_version() {
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("--version")
         .assert()
         .success()
@@ -36,7 +43,7 @@ fn test_lint_valid_skill() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint").arg(temp.path()).assert().success().code(0);
 }
 
@@ -52,7 +59,7 @@ fn test_lint_invalid_name() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
@@ -73,7 +80,7 @@ fn test_lint_name_directory_mismatch() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
@@ -97,7 +104,7 @@ fn test_lint_duplicate_names() {
     fs::write(skill_dir1.join("SKILL.md"), skill_content1).unwrap();
     fs::write(skill_dir2.join("SKILL.md"), skill_content2).unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
@@ -106,6 +113,104 @@ fn test_lint_duplicate_names() {
         .stdout(predicate::str::contains("duplicated"));
 }
 
+#[test]
+fn test_all_roots_discovery() {
+    let temp = TempDir::new().unwrap();
+    let home = TempDir::new().unwrap();
+    for (dir, name) in [
+        (".github/skills/review", "review"),
+        (".claude/skills/review", "review"),
+        (".codex/skills/deploy", "deploy"),
+    ] {
+        let skill_dir = temp.path().join(dir);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {}\ndescription: Test\n---\n# Test\n", name),
+        )
+        .unwrap();
+    }
+
+    // Only the first skills directory by default
+    madskills()
+        .env("HOME", home.path())
+        .arg("list")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("deploy").not());
+
+    let output = madskills()
+        .env("HOME", home.path())
+        .args(["list", "--all-roots", "--format", "json"])
+        .arg(temp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut agents: Vec<_> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["agent"].as_str().unwrap().to_string())
+        .collect();
+    agents.sort();
+    assert_eq!(agents, ["claude", "codex", "github"]);
+
+    // list warns about the duplicate too
+    madskills()
+        .env("HOME", home.path())
+        .args(["list", "--all-roots"])
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: Skill name 'review' is duplicated",
+        ));
+
+    // The same name under two agents' directories is a duplicate
+    madskills()
+        .env("HOME", home.path())
+        .args(["lint", "--all-roots"])
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("duplicated"));
+}
+
+#[test]
+fn test_lint_reports_unparseable_skill() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/broken");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: broken\ndescription: a: b\n---\n# Broken\n",
+    )
+    .unwrap();
+
+    madskills()
+        .arg("lint")
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Invalid frontmatter YAML"))
+        .stdout(predicate::str::contains("broken/SKILL.md:3:15"));
+
+    madskills()
+        .args(["lint", "--format", "json"])
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("frontmatter-parse-error"));
+
+    madskills()
+        .args(["lint", "--format", "sarif"])
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("\"startLine\": 3"));
+}
+
 #[test]
 fn test_lint_json_output() {
     let temp = TempDir::new().unwrap();
@@ -118,14 +223,15 @@ fn test_lint_json_output() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg("--format")
         .arg("json")
         .arg(temp.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains("\"results\""));
+        .stdout(predicate::str::contains("\"schema_version\": 1"))
+        .stdout(predicate::str::contains("\"diagnostics\""));
 }
 
 #[test]
@@ -140,7 +246,7 @@ fn test_list_skills() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("list")
         .arg(temp.path())
         .assert()
@@ -148,6 +254,33 @@ fn test_list_skills() {
         .stdout(predicate::str::contains("test-skill"));
 }
 
+#[test]
+fn test_list_exclude_globs_and_ignore_file() {
+    let temp = TempDir::new().unwrap();
+    for name in ["kept", "vendor/dep", "draft"] {
+        let skill_dir = temp.path().join(".github/skills").join(name);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: Test skill\n---\n",
+                name.rsplit('/').next().unwrap()
+            ),
+        )
+        .unwrap();
+    }
+    fs::write(temp.path().join(".madskillsignore"), "draft/\n").unwrap();
+
+    madskills()
+        .args(["list", "--exclude", "**/vendor/**"])
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("kept"))
+        .stdout(predicate::str::contains("dep").not())
+        .stdout(predicate::str::contains("draft").not());
+}
+
 #[test]
 fn test_init_creates_skill() {
     let temp = TempDir::new().unwrap();
@@ -155,7 +288,7 @@ fn test_init_creates_skill() {
     // Create .github directory to trigger fallback to .github/skills
     fs::create_dir_all(temp.path().join(".github")).unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("init")
         .arg("new-skill")
         .arg("--root")
@@ -172,6 +305,30 @@ fn test_init_creates_skill() {
     assert!(content.contains("name: new-skill"));
 }
 
+#[test]
+fn test_init_quotes_description() {
+    let temp = TempDir::new().unwrap();
+    fs::create_dir_all(temp.path().join(".github")).unwrap();
+
+    madskills()
+        .args([
+            "init",
+            "new-skill",
+            "--description",
+            "Use when: the user asks",
+        ])
+        .arg("--root")
+        .arg(temp.path())
+        .assert()
+        .success();
+
+    let content =
+        fs::read_to_string(temp.path().join(".github/skills/new-skill/SKILL.md")).unwrap();
+    assert!(content.contains("description: \"Use when: the user asks\"\n"));
+
+    madskills().arg("lint").arg(temp.path()).assert().success();
+}
+
 #[test]
 fn test_init_auto_detects_claude_location() {
     let temp = TempDir::new().unwrap();
@@ -179,7 +336,7 @@ fn test_init_auto_detects_claude_location() {
     // Create .claude/skills to establish it as the detected location
     fs::create_dir_all(temp.path().join(".claude/skills")).unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("init")
         .arg("auto-skill")
         .arg("--root")
@@ -195,7 +352,7 @@ fn test_init_auto_detects_claude_location() {
 fn test_init_respects_dir_override() {
     let temp = TempDir::new().unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("init")
         .arg("custom-skill")
         .arg("--root")
@@ -216,7 +373,7 @@ fn test_init_respects_dir_override() {
 fn test_init_invalid_name() {
     let temp = TempDir::new().unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("init")
         .arg("Invalid-Name")
         .arg("--root")
@@ -239,7 +396,7 @@ fn test_fmt_normalizes_frontmatter() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("fmt").arg(temp.path()).assert().success();
 
     // Verify fields are reordered
@@ -261,7 +418,7 @@ fn test_fmt_check_mode() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("fmt")
         .arg("--check")
         .arg(temp.path())
@@ -274,6 +431,46 @@ fn test_fmt_check_mode() {
     assert!(content.starts_with("---\ndescription:"));
 }
 
+#[test]
+fn test_fmt_diff_is_read_only() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    let content = "---\ndescription: Test\nname: test-skill\n---\n# Test\n\nTrailing   \n";
+    fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+
+    madskills()
+        .args(["fmt", "--diff"])
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(
+            predicate::str::contains("--- a/")
+                .and(predicate::str::contains("+name: test-skill\n"))
+                .and(predicate::str::contains("-Trailing   \n+Trailing\n")),
+        )
+        .stderr(predicate::str::contains("1 file(s) would be formatted"));
+
+    madskills()
+        .args(["fmt", "--check"])
+        .arg(temp.path())
+        .assert()
+        .code(2);
+    assert_eq!(
+        fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+        content
+    );
+
+    madskills().arg("fmt").arg(temp.path()).assert().success();
+    madskills()
+        .args(["fmt", "--diff"])
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
 #[test]
 fn test_fmt_frontmatter_and_markdown() {
     let temp = TempDir::new().unwrap();
@@ -287,7 +484,7 @@ fn test_fmt_frontmatter_and_markdown() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("fmt").arg(temp.path()).assert().success();
 
     // Verify both frontmatter and markdown were fixed
@@ -311,7 +508,7 @@ fn test_fmt_no_mdlint() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("fmt")
         .arg("--no-mdlint")
         .arg(temp.path())
@@ -338,7 +535,7 @@ fn test_fmt_no_frontmatter() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("fmt")
         .arg("--no-frontmatter")
         .arg(temp.path())
@@ -350,6 +547,81 @@ fn test_fmt_no_frontmatter() {
     assert!(content.starts_with("---\nname: test-skill\ndescription: Test\n---"));
 }
 
+#[test]
+fn test_fmt_preserves_comments_and_unknown_keys() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\ndescription: \"Use when: editing C# files\"\n# Keep me\nname: test-skill\nx-owner: docs\n---\n# Test\n",
+    )
+    .unwrap();
+
+    madskills()
+        .args(["fmt", "--no-mdlint"])
+        .arg(temp.path())
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(skill_dir.join("SKILL.md")).unwrap();
+    assert_eq!(
+        content,
+        "---\n# Keep me\nname: test-skill\ndescription: \"Use when: editing C# files\"\nx-owner: docs\n---\n# Test\n"
+    );
+}
+
+#[test]
+fn test_fmt_toc() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Test\n---\n# Test\n",
+    )
+    .unwrap();
+    let reference = format!(
+        "# Reference\n\n## Setup\n\n{}## Setup\n\n### What's next?\n",
+        "Details.\n".repeat(100)
+    );
+    fs::write(skill_dir.join("REFERENCE.md"), &reference).unwrap();
+
+    madskills()
+        .args(["fmt", "--toc", "--check", "--no-mdlint"])
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(
+            predicate::str::contains("Would format:").and(predicate::str::contains("REFERENCE.md")),
+        );
+    assert_eq!(
+        fs::read_to_string(skill_dir.join("REFERENCE.md")).unwrap(),
+        reference
+    );
+
+    madskills()
+        .args(["fmt", "--toc", "--no-mdlint"])
+        .arg(temp.path())
+        .assert()
+        .success();
+    let content = fs::read_to_string(skill_dir.join("REFERENCE.md")).unwrap();
+    assert!(content.starts_with("# Reference\n\n<!-- madskills-toc-start -->\n"));
+    assert!(
+        content
+            .contains("- [Setup](#setup)\n- [Setup](#setup-1)\n  - [What's next?](#whats-next)\n")
+    );
+
+    // The generated table is current, so a second run changes nothing
+    madskills()
+        .args(["fmt", "--toc", "--check", "--no-mdlint"])
+        .arg(temp.path())
+        .assert()
+        .success();
+}
+
 #[test]
 fn test_lint_unicode_skill_name() {
     let temp = TempDir::new().unwrap();
@@ -362,7 +634,7 @@ fn test_lint_unicode_skill_name() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint").arg(temp.path()).assert().success();
 }
 
@@ -378,7 +650,7 @@ fn test_lint_extra_fields() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
@@ -404,13 +676,13 @@ fn test_lint_best_practices_violations() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
         .success() // Warnings don't cause failure in default mode
         .code(0)
-        .stdout(predicate::str::contains("BP-WARN"))
+        .stdout(predicate::str::contains("warning[AS0"))
         .stdout(predicate::str::contains("AS003"));
 }
 
@@ -427,7 +699,7 @@ fn test_lint_no_best_practices_flag() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg("--no-best-practices")
         .arg(temp.path())
@@ -449,14 +721,14 @@ fn test_lint_best_practices_strict_mode() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg("--strict")
         .arg(temp.path())
         .assert()
         .failure()
         .code(2)
-        .stdout(predicate::str::contains("BP-ERROR")); // In strict mode, violations become errors
+        .stdout(predicate::str::contains("error[AS0")); // In strict mode, violations become errors
 }
 
 #[test]
@@ -472,7 +744,7 @@ fn test_lint_best_practices_reserved_word() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg(temp.path())
         .assert()
@@ -495,7 +767,7 @@ fn test_lint_best_practices_json_output() {
     )
     .unwrap();
 
-    let mut cmd = Command::cargo_bin("madskills").unwrap();
+    let mut cmd = madskills();
     cmd.arg("lint")
         .arg("--format")
         .arg("json")
@@ -503,6 +775,879 @@ fn test_lint_best_practices_json_output() {
         .assert()
         .success()
         .code(0)
-        .stdout(predicate::str::contains("best_practice_violations"))
-        .stdout(predicate::str::contains("AS003"));
+        .stdout(predicate::str::contains("\"kind\": \"best-practice\""))
+        .stdout(predicate::str::contains("\"code\": \"AS003\""));
+}
+
+// Configuration file tests
+
+#[test]
+fn test_lint_config_file_strict() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    // AS003 warning only fails the run when strict comes from madskills.toml
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[lint]\nstrict = true\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(predicate::str::contains("error[AS0"));
+}
+
+#[test]
+fn test_lint_pyproject_tool_table() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("pyproject.toml"),
+        "[tool.madskills.lint]\nbest-practices = false\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS003").not());
+}
+
+#[test]
+fn test_lint_invalid_config_file() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[lint]\nstrcit = true\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("strcit"));
+}
+
+#[test]
+fn test_lint_missing_mdlint_config() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Test\n---\n\n# Test\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--mdlint-config")
+        .arg(temp.path().join("missing.toml"))
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing.toml"))
+        .stderr(predicate::str::contains("file not found"));
+}
+
+#[test]
+fn test_markdownlint_config_is_discovered() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Test\n---\n\n# Test\n\nTrailing spaces   \n",
+    )
+    .unwrap();
+
+    madskills()
+        .args(["lint", "--no-best-practices"])
+        .arg(temp.path())
+        .assert()
+        .stdout(predicate::str::contains("MD009"));
+
+    fs::write(
+        temp.path().join(".markdownlint.json"),
+        r#"{"MD009": false}"#,
+    )
+    .unwrap();
+
+    madskills()
+        .args(["lint", "--no-best-practices"])
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MD009").not());
+    madskills()
+        .args(["fmt", "--check"])
+        .arg(temp.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_markdown_lint_covers_reference_files() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(skill_dir.join("examples")).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Test\n---\n\n# Test\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("examples/basic.md"),
+        "# Basic\n\nTrailing spaces   \n",
+    )
+    .unwrap();
+
+    madskills()
+        .args(["lint", "--no-best-practices"])
+        .arg(temp.path())
+        .assert()
+        .stdout(predicate::str::contains("examples/basic.md:3"))
+        .stdout(predicate::str::contains("MD009"));
+
+    madskills().arg("fmt").arg(temp.path()).assert().success();
+    assert_eq!(
+        fs::read_to_string(skill_dir.join("examples/basic.md")).unwrap(),
+        "# Basic\n\nTrailing spaces\n"
+    );
+}
+
+#[test]
+fn test_config_show() {
+    let temp = TempDir::new().unwrap();
+    let nested = temp.path().join("skills");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[lint]\nmdlint = false\n\n[discovery]\nexclude = [\"vendor\"]\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("config")
+        .arg("show")
+        .arg(&nested)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("mdlint = false  # "))
+        .stdout(predicate::str::contains("madskills.toml"))
+        .stdout(predicate::str::contains("strict = false  # default"))
+        .stdout(predicate::str::contains("exclude = [\"vendor\"]"));
+}
+
+// Rule selection tests
+
+#[test]
+fn test_lint_ignore_rule() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--ignore")
+        .arg("AS003")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS003").not())
+        .stdout(predicate::str::contains("AS014"));
+}
+
+#[test]
+fn test_lint_select_rule_prefix() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--select")
+        .arg("AS00")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS003"))
+        .stdout(predicate::str::contains("AS014").not());
+}
+
+#[test]
+fn test_lint_unknown_rule_selector() {
+    let temp = TempDir::new().unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--ignore")
+        .arg("AS999")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match any rule code"));
+}
+
+#[test]
+fn test_lint_rule_levels_from_config() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[rules]\nAS003 = \"info\"\nAS014 = \"off\"\n",
+    )
+    .unwrap();
+
+    // Info findings never fail the run, even in strict mode
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--strict")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("info[AS0"))
+        .stdout(predicate::str::contains("AS014").not());
+}
+
+/// Skill and config declaring a heading rule and a script pattern rule
+fn custom_rules_project(temp: &TempDir) -> std::path::PathBuf {
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("scripts/clean.sh"),
+        "#!/bin/sh\nrm -rf build\n# madskills: ignore[ACME002]\nrm -rf dist\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        r###"[rules]
+ACME001 = "error"
+
+[[custom-rules]]
+id = "ACME001"
+message = "SKILL.md needs a Safety section"
+target = "body"
+required-heading = "## Safety"
+
+[[custom-rules]]
+id = "ACME002"
+message = "Scripts must not call rm -rf"
+target = "scripts"
+extensions = ["sh"]
+forbidden-pattern = "rm -rf"
+"###,
+    )
+    .unwrap();
+    skill_dir
+}
+
+#[test]
+fn test_lint_custom_rules_from_config() {
+    let temp = TempDir::new().unwrap();
+    custom_rules_project(&temp);
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "error[ACME001]: SKILL.md needs a Safety section",
+        ))
+        .stdout(predicate::str::contains(
+            "warning[ACME002]: Scripts must not call rm -rf",
+        ))
+        .stdout(predicate::str::contains("clean.sh:2:1"))
+        .stdout(predicate::str::contains("clean.sh:4").not());
+
+    // Custom ids work as selectors once the config is loaded
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--select")
+        .arg("ACME002")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ACME001").not())
+        .stdout(predicate::str::contains("ACME002"));
+}
+
+#[test]
+fn test_lint_invalid_custom_rule() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[[custom-rules]]\nid = \"ACME001\"\nmessage = \"m\"\ntarget = \"body\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs exactly one of"));
+}
+
+#[test]
+fn test_lint_rule_level_error_fails() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[rules]\nAS003 = \"error\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(predicate::str::contains("error[AS0"));
+}
+
+#[test]
+fn test_lint_inline_suppressions() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Processes test files. Use when testing.\n---\n# Test Skill\n\n<!-- madskills-disable-next-line AS010 -->\nThis changed in 2024.\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("run.py"),
+        "import sys\n# madskills: ignore[AS018]\nTIMEOUT = 30\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS010").not())
+        .stdout(predicate::str::contains("AS018").not())
+        .stdout(predicate::str::contains("Unused suppression").not());
+}
+
+#[test]
+fn test_lint_unused_suppression() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Processes test files. Use when testing.\n---\n# Test Skill\n\n<!-- madskills-disable AS012 -->\nNothing to see here.\n<!-- madskills-enable -->\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unused suppression for AS012"));
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--strict")
+        .arg(temp.path())
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_lint_baseline_reports_only_new_findings() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    let baseline = temp.path().join(".madskills-baseline.json");
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--write-baseline")
+        .arg(&baseline)
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("to baseline"));
+    assert!(baseline.exists());
+
+    // Existing findings are hidden
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--strict")
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS003").not());
+
+    // Fixing one finding and introducing another
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Processes test files. Use when testing.\n---\n# Test Skill\n\nThis changed in 2024.\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS010"))
+        .stdout(predicate::str::contains("AS014").not())
+        .stderr(predicate::str::contains("no longer occur"))
+        .stderr(predicate::str::contains("[AS003]"));
+}
+
+#[test]
+fn test_lint_missing_baseline_file() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: Processes test files. Use when testing.\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--baseline")
+        .arg(temp.path().join("missing.json"))
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to load baseline"));
+}
+
+#[test]
+fn test_lint_sarif_output() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    let output = cmd
+        .arg("lint")
+        .arg("--format")
+        .arg("sarif")
+        .arg(temp.path())
+        .output()
+        .unwrap();
+
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let as003 = results.iter().find(|r| r["ruleId"] == "AS003").unwrap();
+    assert_eq!(
+        as003["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        ".github/skills/test-skill/SKILL.md"
+    );
+}
+
+#[test]
+fn test_lint_github_format() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.current_dir(temp.path())
+        .arg("lint")
+        .arg("--format")
+        .arg("github")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "::warning file=.github/skills/test-skill/SKILL.md,line=3,col=14,title=AS003::",
+        ));
+}
+
+#[test]
+fn test_lint_fix_and_diff() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    let skill_md = skill_dir.join("SKILL.md");
+    let original = "---\nname: test-skill\ndescription: Processes test data. Use when testing.\n---\n# Test Skill\n\nSee docs\\guide.md\n";
+    fs::write(&skill_md, original).unwrap();
+    let script = skill_dir.join("copy.sh");
+    fs::write(&script, "#!/bin/sh\ncp a b\n").unwrap();
+
+    // --diff previews without writing
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--diff")
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "-See docs\\guide.md\n+See docs/guide.md",
+        ))
+        .stdout(predicate::str::contains("set -e").not());
+    assert_eq!(fs::read_to_string(&skill_md).unwrap(), original);
+
+    // --diff prints no report, so report options are rejected
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .args(["--diff", "--format", "sarif"])
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    // --fix applies safe fixes only
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--fix")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS005").not())
+        .stderr(predicate::str::contains("Fixed 1 finding(s)"))
+        .stderr(predicate::str::contains(
+            "1 more fix(es) available with --unsafe-fixes",
+        ));
+    assert!(
+        fs::read_to_string(&skill_md)
+            .unwrap()
+            .contains("See docs/guide.md")
+    );
+    assert_eq!(fs::read_to_string(&script).unwrap(), "#!/bin/sh\ncp a b\n");
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--fix")
+        .arg("--unsafe-fixes")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AS017").not());
+    assert_eq!(
+        fs::read_to_string(&script).unwrap(),
+        "#!/bin/sh\nset -e\ncp a b\n"
+    );
+}
+
+#[test]
+fn test_lint_color_choice() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    // Piped output is plain by default
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not())
+        .stdout(predicate::str::contains(
+            "3 | description: I help with testing",
+        ));
+
+    let mut cmd = madskills();
+    cmd.arg("--color")
+        .arg("always")
+        .arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[1;33mwarning["))
+        .stdout(predicate::str::contains("\x1b]8;;"));
+}
+
+#[test]
+fn test_lint_github_format_auto_selected() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.env("GITHUB_ACTIONS", "true")
+        .arg("lint")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("::warning file="));
+
+    // An explicit format wins
+    let mut cmd = madskills();
+    cmd.env("GITHUB_ACTIONS", "true")
+        .arg("lint")
+        .arg("--format")
+        .arg("text")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("::warning").not())
+        .stdout(predicate::str::contains("warning[AS0"));
+}
+
+#[test]
+fn test_lint_checkstyle_output_file() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    let report = temp.path().join("checkstyle.xml");
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("checkstyle")
+        .arg("--output")
+        .arg(&report)
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warning[AS0"));
+
+    let xml = fs::read_to_string(&report).unwrap();
+    assert!(xml.contains("<checkstyle version=\"4.3\">"));
+    assert!(xml.contains("source=\"madskills.AS003\""));
+}
+
+#[test]
+fn test_lint_output_file_keeps_custom_rule_help_on_stdout() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/test-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    let report = temp.path().join("report.json");
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: test-skill\ndescription: I help with testing\n---\n# Test Skill\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[[custom-rules]]\nid = \"ACME001\"\nmessage = \"SKILL.md needs a Safety section\"\nhelp = \"add a ## Safety section\"\ntarget = \"body\"\nrequired-heading = \"## Safety\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(&report)
+        .arg(temp.path())
+        .assert()
+        .stdout(predicate::str::contains("warning[ACME001]"))
+        .stdout(predicate::str::contains("= help: add a ## Safety section"));
+
+    assert!(fs::read_to_string(&report).unwrap().contains("ACME001"));
+}
+
+#[test]
+fn test_lint_junit_output() {
+    let temp = TempDir::new().unwrap();
+    let skill_dir = temp.path().join(".github/skills/Test-Skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: Test-Skill\ndescription: Processes test files. Use when testing.\n---\n# Test Skill\n",
+    )
+    .unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("junit")
+        .arg(temp.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("<testsuite name=\"Test-Skill\""))
+        .stdout(predicate::str::contains(
+            "<failure message=\"Name must be lowercase",
+        ));
+}
+
+// Rule documentation tests
+
+#[test]
+fn test_rules_reflects_config() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("madskills.toml"),
+        "[lint]\nignore = [\"AS01\"]\n\n[rules]\nAS004 = \"error\"\n\n[[custom-rules]]\nid = \"ACME001\"\nmessage = \"Scripts must not call rm -rf\"\ntarget = \"scripts\"\nforbidden-pattern = \"rm -rf\"\n",
+    )
+    .unwrap();
+
+    let output = madskills()
+        .arg("rules")
+        .arg("--format")
+        .arg("json")
+        .arg(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rules: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let rule = |code: &str| {
+        rules
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["code"] == code)
+            .unwrap()
+            .clone()
+    };
+
+    assert_eq!(rule("AS004")["level"], "error");
+    assert_eq!(rule("AS004")["default-level"], "warning");
+    assert_eq!(rule("AS005")["fixable"], true);
+    assert_eq!(rule("AS012")["enabled"], false);
+    assert_eq!(rule("ACME001")["enabled"], true);
+    assert_eq!(rule("ACME001")["builtin"], false);
+
+    let mut cmd = madskills();
+    cmd.arg("rules")
+        .arg(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("CODE"))
+        .stdout(predicate::str::contains(
+            "AS012    warning  off           Use consistent terminology",
+        ));
+}
+
+#[test]
+fn test_explain_builtin_rule() {
+    let temp = TempDir::new().unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("explain")
+        .arg("as006")
+        .arg("--path")
+        .arg(temp.path())
+        .arg("--color")
+        .arg("never")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "AS006: Reference Depth Limit\n============================\n",
+        ))
+        .stdout(predicate::str::contains("Bad Example: Too Deep"))
+        .stdout(predicate::str::contains(
+            "This rule is enabled at level warning",
+        ));
+}
+
+#[test]
+fn test_explain_unknown_rule() {
+    let temp = TempDir::new().unwrap();
+
+    let mut cmd = madskills();
+    cmd.arg("explain")
+        .arg("AS999")
+        .arg("--path")
+        .arg(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown rule code 'AS999'"));
 }
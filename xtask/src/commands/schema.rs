@@ -0,0 +1,28 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct SchemaArgs {
+    /// Output file (default: docs/schema/lint-report.schema.json)
+    #[arg(long = "out", default_value = "docs/schema/lint-report.schema.json")]
+    pub out: PathBuf,
+}
+
+pub fn cmd_schema(args: SchemaArgs) -> Result<(), String> {
+    let out = crate::workspace_root().join(args.out);
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+    }
+
+    let schema = madskills_core::output::json_schema();
+    let mut json =
+        serde_json::to_string_pretty(&schema).map_err(|e| format!("serialize schema: {e}"))?;
+    json.push('\n');
+
+    fs::write(&out, json).map_err(|e| format!("{}: {e}", out.display()))?;
+    println!("wrote {}", out.display());
+
+    Ok(())
+}
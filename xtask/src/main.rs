@@ -21,6 +21,9 @@ enum Task {
 
     /// Build and install the madskills CLI into ~/.bin for local testing.
     Install(commands::install::InstallArgs),
+
+    /// Generate the JSON Schema for `madskills lint --format json`.
+    Schema(commands::schema::SchemaArgs),
 }
 
 fn main() -> Result<(), String> {
@@ -28,6 +31,7 @@ fn main() -> Result<(), String> {
     match task.command {
         Task::Man(args) => commands::man::cmd_man(args),
         Task::Install(args) => commands::install::cmd_install(args),
+        Task::Schema(args) => commands::schema::cmd_schema(args),
     }
 }
 